@@ -0,0 +1,681 @@
+use std::fmt;
+
+use anchor_lang::{error::Error, prelude::Pubkey};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use dynamic_bonding_curve::{
+    token::is_supported_token_2022_quote_mint, ConfigParameterError, ConfigParameters,
+    ConfigValidator, PoolError,
+};
+
+/// Quote mint fields that `create_config` reads from the on-chain mint account
+#[derive(Debug, Clone)]
+pub struct QuoteMintInfo {
+    pub address: Pubkey,
+    /// owner of the mint account, spl token or token 2022 program
+    pub token_program: Pubkey,
+    /// token 2022 extensions on the mint, empty for spl token
+    pub extensions: Vec<ExtensionType>,
+}
+
+/// A single reason why `create_config` would reject the parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFinding {
+    /// path of the offending field in `ConfigParameters`, e.g. `curve[2].sqrt_price`
+    pub field: String,
+    pub message: String,
+}
+
+impl From<ConfigParameterError> for ConfigFinding {
+    fn from(config_parameter_error: ConfigParameterError) -> Self {
+        Self {
+            field: config_parameter_error.field,
+            message: error_message(&config_parameter_error.error),
+        }
+    }
+}
+
+impl fmt::Display for ConfigFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Runs the checks of `create_config` without any on-chain account, with the same validation code as the
+/// program. Checks of the values derived from the curve only run once the parameters pass.
+/// Returns an empty list if `create_config` would accept the parameters.
+pub fn validate_config_parameters(
+    config_parameters: &ConfigParameters,
+    quote_mint: &QuoteMintInfo,
+    leftover_receiver: &Pubkey,
+) -> Vec<ConfigFinding> {
+    let mut validator = ConfigValidator::collect_all();
+    let result = validate_quote_mint(quote_mint, &mut validator)
+        .and_then(|_| {
+            config_parameters.validate_parameters(&quote_mint.token_program, &mut validator)
+        })
+        .and_then(|_| {
+            if validator.has_error() {
+                return Ok(());
+            }
+            let mut config_extension = config_parameters.to_config_extension();
+            config_parameters
+                .get_derived_values(leftover_receiver, &mut config_extension, &mut validator)
+                .map(|_| ())
+        });
+
+    let mut findings: Vec<ConfigFinding> = validator
+        .into_errors()
+        .into_iter()
+        .map(ConfigFinding::from)
+        .collect();
+    // validator collects every failed check, this is only reached by an unexpected error
+    if let Err(err) = result {
+        findings.push(ConfigFinding {
+            field: "config_parameters".to_string(),
+            message: error_message(&err),
+        });
+    }
+    findings
+}
+
+fn validate_quote_mint(
+    quote_mint: &QuoteMintInfo,
+    validator: &mut ConfigValidator,
+) -> anchor_lang::Result<bool> {
+    if quote_mint.token_program == anchor_spl::token::ID {
+        return Ok(true);
+    }
+    if quote_mint.token_program != anchor_spl::token_2022::ID {
+        return validator.require("quote_mint", false, PoolError::InvalidQuoteMint);
+    }
+    let result = is_supported_token_2022_quote_mint(&quote_mint.address, &quote_mint.extensions)
+        .and_then(|is_supported| {
+            if is_supported {
+                Ok(())
+            } else {
+                Err(PoolError::InvalidQuoteMint.into())
+            }
+        });
+    validator.check("quote_mint", result)
+}
+
+fn error_message(err: &Error) -> String {
+    match err {
+        Error::AnchorError(err) => err.error_msg.clone(),
+        Error::ProgramError(err) => err.program_error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_spl::token_2022::spl_token_2022;
+    use dynamic_bonding_curve::{
+        constants::{
+            MAX_LOCKED_VESTING_TRANCHES, MAX_MIGRATION_PRICE_TOLERANCE_BPS, MAX_SQRT_PRICE,
+        },
+        params::{
+            fee_parameters::{BaseFeeParameters, MigratedPoolFeeParameters, PoolFeeParameters},
+            liquidity_distribution::{LiquidityDistributionParameters, MigrationPriceRange},
+        },
+        state::{
+            CollectFeeMode, LockedVestingCancelMode, LockedVestingStartAnchor,
+            LockedVestingUpdateRecipientMode, MigrationFeeOption, MigrationOption, RefundFeeMode,
+            TokenType,
+        },
+        LockedVestingParams, LockedVestingTrancheParams, MigrationFee, TokenSupplyParams,
+    };
+
+    use super::*;
+
+    fn spl_quote_mint() -> QuoteMintInfo {
+        QuoteMintInfo {
+            address: spl_token_2022::native_mint::ID,
+            token_program: anchor_spl::token::ID,
+            extensions: vec![],
+        }
+    }
+
+    fn valid_config_parameters() -> ConfigParameters {
+        ConfigParameters {
+            pool_fees: PoolFeeParameters {
+                base_fee: BaseFeeParameters {
+                    cliff_fee_numerator: 2_500_000,
+                    ..Default::default()
+                },
+                dynamic_fee: None,
+            },
+            collect_fee_mode: 0,
+            migration_option: MigrationOption::DammV2.into(),
+            activation_type: 0,
+            token_type: 0,
+            token_decimal: 6,
+            partner_lp_percentage: 50,
+            partner_locked_lp_percentage: 0,
+            creator_lp_percentage: 50,
+            creator_locked_lp_percentage: 0,
+            migration_quote_threshold: 80_000_000_000,
+            sqrt_start_price: 2916686334356757,
+            locked_vesting: LockedVestingParams::default(),
+            migration_fee_option: 0,
+            token_supply: None,
+            creator_trading_fee_percentage: 0,
+            token_update_authority: 0,
+            migration_fee: MigrationFee::default(),
+            locked_vesting_tranches: vec![],
+            locked_vesting_update_recipient_mode: 0,
+            locked_vesting_cancel_mode: 0,
+            locked_vesting_start_anchor: 0,
+            locked_vesting_start_timestamp: 0,
+            pool_creation_fee_token: 0,
+            base_transfer_fee_basis_points: 0,
+            metadata_creator_flag: 0,
+            close_authority: Pubkey::default(),
+            pool_creator_authority: Pubkey::default(),
+            pool_creation_fee: 0,
+            pool_creation_fee_recipient: Pubkey::default(),
+            base_transfer_fee_maximum_fee: 0,
+            metadata_collection_mint: Pubkey::default(),
+            migration_program: Pubkey::default(),
+            migration_crank_reward: 0,
+            migrated_pool_fee: None,
+            migration_price_range: None,
+            dlmm_bin_step: 0,
+            auto_migration_flag: 0,
+            migration_base_fee_bps: 0,
+            migration_price_tolerance_bps: 0,
+            refund_deadline_duration: 0,
+            refund_fee_mode: 0,
+            base_interest_rate: 0,
+            base_non_transferable_flag: 0,
+            padding: [0; 4],
+            curve: vec![
+                LiquidityDistributionParameters {
+                    sqrt_price: 11666745337427032,
+                    liquidity: 3111132089980541388292920297291756,
+                },
+                LiquidityDistributionParameters {
+                    sqrt_price: MAX_SQRT_PRICE,
+                    liquidity: 1,
+                },
+            ],
+        }
+    }
+
+    fn locked_vesting_tranche() -> LockedVestingTrancheParams {
+        LockedVestingTrancheParams {
+            recipient: Pubkey::new_unique(),
+            locked_vesting: LockedVestingParams {
+                amount_per_period: 1_000_000,
+                cliff_duration_from_migration_time: 0,
+                frequency: 86_400,
+                number_of_period: 10,
+                cliff_unlock_amount: 0,
+            },
+        }
+    }
+
+    /// Name, update of valid config parameters and fields of the expected findings in order
+    type Case = (
+        &'static str,
+        fn(&mut ConfigParameters),
+        &'static [&'static str],
+    );
+
+    const CASES: &[Case] = &[
+        ("valid", |_| {}, &[]),
+        (
+            "curve findings point at index",
+            |c| {
+                c.curve[1].sqrt_price = c.curve[0].sqrt_price;
+                c.curve[1].liquidity = 0;
+            },
+            &["curve[1].sqrt_price", "curve[1].liquidity"],
+        ),
+        (
+            "every finding is collected",
+            |c| {
+                c.migration_option = MigrationOption::MeteoraDamm.into();
+                c.token_decimal = 10;
+                c.creator_lp_percentage = 0;
+                c.pool_creation_fee = 1_000_000;
+            },
+            &[
+                "pool_creation_fee_recipient",
+                "token_decimal",
+                "partner_lp_percentage",
+            ],
+        ),
+        (
+            "base transfer fee on spl token",
+            |c| c.base_transfer_fee_basis_points = 10_001,
+            &[
+                "base_transfer_fee_basis_points",
+                "base_transfer_fee_maximum_fee",
+            ],
+        ),
+        (
+            "base transfer fee on token 2022",
+            |c| {
+                c.token_type = TokenType::Token2022.into();
+                c.base_transfer_fee_basis_points = 100;
+                c.base_transfer_fee_maximum_fee = u64::MAX;
+            },
+            &[],
+        ),
+        (
+            "interest bearing and non-transferable base mint on damm v2 migration",
+            |c| {
+                c.token_type = TokenType::Token2022.into();
+                c.base_interest_rate = 500;
+                c.base_non_transferable_flag = 1;
+            },
+            &["base_interest_rate", "base_non_transferable_flag"],
+        ),
+        (
+            "interest bearing and non-transferable base mint on external program migration",
+            |c| {
+                c.migration_option = MigrationOption::ExternalProgram.into();
+                c.migration_program = Pubkey::new_unique();
+                c.token_type = TokenType::Token2022.into();
+                c.base_interest_rate = -500;
+                c.base_non_transferable_flag = 1;
+            },
+            &[],
+        ),
+        (
+            "non-transferable base mint collecting fee on output token",
+            |c| {
+                c.migration_option = MigrationOption::ExternalProgram.into();
+                c.migration_program = Pubkey::new_unique();
+                c.token_type = TokenType::Token2022.into();
+                c.base_non_transferable_flag = 2;
+                c.collect_fee_mode = CollectFeeMode::OutputToken.into();
+            },
+            &["base_non_transferable_flag", "collect_fee_mode"],
+        ),
+        (
+            "metadata creator and collection",
+            |c| {
+                c.metadata_creator_flag = 1;
+                c.metadata_collection_mint = Pubkey::new_unique();
+            },
+            &[],
+        ),
+        (
+            "metadata creator and collection on token 2022",
+            |c| {
+                c.metadata_creator_flag = 2;
+                c.metadata_collection_mint = Pubkey::new_unique();
+                c.token_type = TokenType::Token2022.into();
+            },
+            &["metadata_creator_flag", "metadata_collection_mint"],
+        ),
+        (
+            "migration program without external program migration",
+            |c| c.migration_program = Pubkey::new_unique(),
+            &["migration_program"],
+        ),
+        (
+            "external program migration",
+            |c| {
+                c.migration_option = MigrationOption::ExternalProgram.into();
+                c.migration_program = Pubkey::new_unique();
+            },
+            &[],
+        ),
+        (
+            "external program migration without migration program",
+            |c| c.migration_option = MigrationOption::ExternalProgram.into(),
+            &["migration_program"],
+        ),
+        (
+            "migration base fee bps of fixed migration fee option",
+            |c| c.migration_base_fee_bps = 150,
+            &["migration_base_fee_bps"],
+        ),
+        (
+            "customizable migration fee option",
+            |c| {
+                c.migration_fee_option = MigrationFeeOption::Customizable.into();
+                c.migration_base_fee_bps = 150;
+            },
+            &[],
+        ),
+        (
+            "customizable migration fee option without base fee bps",
+            |c| c.migration_fee_option = MigrationFeeOption::Customizable.into(),
+            &["migration_base_fee_bps"],
+        ),
+        (
+            "unknown migration fee option",
+            |c| c.migration_fee_option = u8::MAX,
+            &["migration_fee_option"],
+        ),
+        (
+            "migration price range",
+            |c| {
+                c.migration_price_range = Some(MigrationPriceRange {
+                    min_price_bps: 5_000,
+                    max_price_bps: 20_000,
+                })
+            },
+            &[],
+        ),
+        (
+            "migration price range without min price",
+            |c| {
+                c.migration_price_range = Some(MigrationPriceRange {
+                    min_price_bps: 0,
+                    max_price_bps: 20_000,
+                })
+            },
+            &["migration_price_range"],
+        ),
+        (
+            "migration price range without damm v2 migration",
+            |c| {
+                c.migration_price_range = Some(MigrationPriceRange {
+                    min_price_bps: 5_000,
+                    max_price_bps: 20_000,
+                });
+                c.migration_option = MigrationOption::MeteoraDamm.into();
+            },
+            &["migration_price_range"],
+        ),
+        (
+            "max locked vesting tranches",
+            |c| {
+                c.locked_vesting_tranches =
+                    vec![locked_vesting_tranche(); MAX_LOCKED_VESTING_TRANCHES]
+            },
+            &[],
+        ),
+        (
+            "too many locked vesting tranches",
+            |c| {
+                c.locked_vesting_tranches =
+                    vec![locked_vesting_tranche(); MAX_LOCKED_VESTING_TRANCHES + 1]
+            },
+            &["locked_vesting_tranches"],
+        ),
+        (
+            "locked vesting tranche without recipient",
+            |c| {
+                c.locked_vesting_tranches = vec![
+                    locked_vesting_tranche(),
+                    LockedVestingTrancheParams {
+                        recipient: Pubkey::default(),
+                        ..locked_vesting_tranche()
+                    },
+                ]
+            },
+            &["locked_vesting_tranches[1]"],
+        ),
+        (
+            "auto migration with locked vesting tranche",
+            |c| {
+                c.locked_vesting_tranches = vec![locked_vesting_tranche()];
+                c.auto_migration_flag = 1;
+            },
+            &["auto_migration_flag"],
+        ),
+        (
+            "locked vesting modes",
+            |c| {
+                c.locked_vesting_update_recipient_mode =
+                    LockedVestingUpdateRecipientMode::PartnerOrRecipient.into();
+                c.locked_vesting_cancel_mode = LockedVestingCancelMode::Partner.into();
+            },
+            &[],
+        ),
+        (
+            "recipient locked vesting cancel mode",
+            |c| c.locked_vesting_cancel_mode = 2,
+            &["locked_vesting_cancel_mode"],
+        ),
+        (
+            "unknown locked vesting modes",
+            |c| {
+                c.locked_vesting_update_recipient_mode = 4;
+                c.locked_vesting_cancel_mode = 4;
+            },
+            &[
+                "locked_vesting_update_recipient_mode",
+                "locked_vesting_cancel_mode",
+            ],
+        ),
+        (
+            "locked vesting starts at activation",
+            |c| c.locked_vesting_start_anchor = LockedVestingStartAnchor::Activation.into(),
+            &[],
+        ),
+        (
+            "locked vesting starts at timestamp",
+            |c| {
+                c.locked_vesting_start_anchor = LockedVestingStartAnchor::Timestamp.into();
+                c.locked_vesting_start_timestamp = 1_700_000_000;
+            },
+            &[],
+        ),
+        (
+            "timestamp start anchor without timestamp",
+            |c| c.locked_vesting_start_anchor = LockedVestingStartAnchor::Timestamp.into(),
+            &["locked_vesting_start_timestamp"],
+        ),
+        (
+            "timestamp without timestamp start anchor",
+            |c| c.locked_vesting_start_timestamp = 1_700_000_000,
+            &["locked_vesting_start_timestamp"],
+        ),
+        (
+            "unknown locked vesting start anchor",
+            |c| c.locked_vesting_start_anchor = 3,
+            &["locked_vesting_start_anchor"],
+        ),
+        (
+            "refund trading fee",
+            |c| {
+                c.refund_deadline_duration = 86_400;
+                c.refund_fee_mode = RefundFeeMode::RefundTradingFee.into();
+            },
+            &[],
+        ),
+        (
+            "unknown refund fee mode",
+            |c| {
+                c.refund_deadline_duration = 86_400;
+                c.refund_fee_mode = 2;
+            },
+            &["refund_fee_mode"],
+        ),
+        (
+            "refund with base transfer fee",
+            |c| {
+                c.token_type = TokenType::Token2022.into();
+                c.base_transfer_fee_basis_points = 100;
+                c.base_transfer_fee_maximum_fee = u64::MAX;
+                c.refund_deadline_duration = 86_400;
+            },
+            &["refund_deadline_duration"],
+        ),
+        (
+            "refund fee mode without refund deadline",
+            |c| c.refund_fee_mode = RefundFeeMode::RefundTradingFee.into(),
+            &["refund_fee_mode"],
+        ),
+        (
+            "max migration price tolerance",
+            |c| c.migration_price_tolerance_bps = MAX_MIGRATION_PRICE_TOLERANCE_BPS,
+            &[],
+        ),
+        (
+            "migration price tolerance above max",
+            |c| c.migration_price_tolerance_bps = MAX_MIGRATION_PRICE_TOLERANCE_BPS + 1,
+            &["migration_price_tolerance_bps"],
+        ),
+        (
+            "migration price tolerance of external program migration",
+            |c| {
+                c.migration_price_tolerance_bps = 100;
+                c.migration_option = MigrationOption::ExternalProgram.into();
+                c.migration_program = Pubkey::new_unique();
+            },
+            &["migration_price_tolerance_bps"],
+        ),
+        (
+            "migrated pool fee",
+            |c| {
+                c.migrated_pool_fee = Some(MigratedPoolFeeParameters {
+                    cliff_fee_numerator: 2_500_000,
+                    ..Default::default()
+                })
+            },
+            &[],
+        ),
+        (
+            "migrated pool partner fee over 100 percent",
+            |c| {
+                c.migrated_pool_fee = Some(MigratedPoolFeeParameters {
+                    cliff_fee_numerator: 2_500_000,
+                    partner_fee_percent: 101,
+                    ..Default::default()
+                })
+            },
+            &["migrated_pool_fee"],
+        ),
+        (
+            "migrated pool fee without damm v2 migration",
+            |c| {
+                c.migrated_pool_fee = Some(MigratedPoolFeeParameters {
+                    cliff_fee_numerator: 2_500_000,
+                    fee_scheduler_mode: 2,
+                    ..Default::default()
+                });
+                c.migration_option = MigrationOption::MeteoraDamm.into();
+            },
+            &["migrated_pool_fee", "migrated_pool_fee"],
+        ),
+        (
+            "migration crank reward without migration fee",
+            |c| c.migration_crank_reward = 1,
+            &["migration_crank_reward"],
+        ),
+        (
+            "migration crank reward from migration fee",
+            |c| {
+                c.migration_crank_reward = 1;
+                c.migration_fee = MigrationFee {
+                    fee_percentage: 10,
+                    creator_fee_percentage: 50,
+                };
+            },
+            &[],
+        ),
+        (
+            "unknown auto migration flag",
+            |c| c.auto_migration_flag = 2,
+            &["auto_migration_flag"],
+        ),
+        ("auto migration", |c| c.auto_migration_flag = 1, &[]),
+        (
+            "auto migration without damm v2 migration",
+            |c| {
+                c.auto_migration_flag = 1;
+                c.migration_option = MigrationOption::MeteoraDamm.into();
+            },
+            &["auto_migration_flag"],
+        ),
+        (
+            "fixed token supply below curve and migration amounts",
+            |c| {
+                c.token_supply = Some(TokenSupplyParams {
+                    pre_migration_token_supply: 1,
+                    post_migration_token_supply: 2,
+                })
+            },
+            &[
+                "leftover_receiver",
+                "token_supply.post_migration_token_supply",
+                "token_supply.pre_migration_token_supply",
+            ],
+        ),
+    ];
+
+    #[test]
+    fn test_config_findings() {
+        for (name, update, expected_fields) in CASES {
+            let mut config_parameters = valid_config_parameters();
+            update(&mut config_parameters);
+            let findings = validate_config_parameters(
+                &config_parameters,
+                &spl_quote_mint(),
+                &Pubkey::default(),
+            );
+            let fields: Vec<&str> = findings.iter().map(|f| f.field.as_str()).collect();
+            assert_eq!(&fields, expected_fields, "{}: {:?}", name, findings);
+        }
+    }
+
+    #[test]
+    fn test_quote_mint_findings() {
+        let quote_mints = [
+            (
+                QuoteMintInfo {
+                    address: Pubkey::new_unique(),
+                    token_program: anchor_spl::token_2022::ID,
+                    extensions: vec![ExtensionType::TransferFeeConfig],
+                },
+                true,
+            ),
+            (
+                QuoteMintInfo {
+                    address: Pubkey::new_unique(),
+                    token_program: anchor_spl::token_2022::ID,
+                    extensions: vec![ExtensionType::PermanentDelegate],
+                },
+                false,
+            ),
+            (
+                QuoteMintInfo {
+                    address: spl_token_2022::native_mint::ID,
+                    token_program: anchor_spl::token_2022::ID,
+                    extensions: vec![],
+                },
+                false,
+            ),
+            (
+                QuoteMintInfo {
+                    address: Pubkey::new_unique(),
+                    token_program: Pubkey::new_unique(),
+                    extensions: vec![],
+                },
+                false,
+            ),
+        ];
+        for (quote_mint, is_supported) in quote_mints {
+            let findings = validate_config_parameters(
+                &valid_config_parameters(),
+                &quote_mint,
+                &Pubkey::default(),
+            );
+            let fields: Vec<&str> = findings.iter().map(|f| f.field.as_str()).collect();
+            let expected_fields: &[&str] = if is_supported { &[] } else { &["quote_mint"] };
+            assert_eq!(fields, expected_fields, "{:?}: {:?}", quote_mint, findings);
+        }
+
+        // damm migration only supports spl token quote mint
+        let mut config_parameters = valid_config_parameters();
+        config_parameters.migration_option = MigrationOption::MeteoraDamm.into();
+        let findings = validate_config_parameters(
+            &config_parameters,
+            &QuoteMintInfo {
+                address: Pubkey::new_unique(),
+                token_program: anchor_spl::token_2022::ID,
+                extensions: vec![],
+            },
+            &Pubkey::default(),
+        );
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        assert_eq!(findings[0].field, "quote_mint");
+    }
+}
@@ -1 +1,2 @@
+pub mod config_validation;
 pub mod quote;
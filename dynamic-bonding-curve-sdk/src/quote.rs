@@ -1,19 +1,35 @@
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
 use anyhow::{ensure, Context, Result};
 use dynamic_bonding_curve::{
     activation_handler::ActivationType,
     params::swap::TradeDirection,
     state::{fee::FeeMode, PoolConfig, SwapResult, VirtualPool},
+    token::get_transfer_fee_excluded_amount,
 };
 
+#[derive(Debug)]
+pub struct QuoteExactInResult {
+    pub swap_result: SwapResult,
+    /// amount the pool receives after transfer fee of input token
+    pub transfer_fee_excluded_amount_in: u64,
+    /// amount the user receives after transfer fee of output token, compare it with minimum amount out
+    pub transfer_fee_excluded_amount_out: u64,
+}
+
+/// Quote swap exact in. Transfer fees are the current epoch fees of token 2022 mints with transfer fee extension,
+/// none for spl token or token 2022 mints without transfer fee
+#[allow(clippy::too_many_arguments)]
 pub fn quote_exact_in(
     virtual_pool: &VirtualPool,
     config: &PoolConfig,
     swap_base_for_quote: bool,
     current_timestamp: u64,
     current_slot: u64,
-    transfer_fee_excluded_amount_in: u64, // must be calculated from outside
+    amount_in: u64,
     has_referral: bool,
-) -> Result<SwapResult> {
+    base_mint_transfer_fee: Option<&TransferFee>,
+    quote_mint_transfer_fee: Option<&TransferFee>,
+) -> Result<QuoteExactInResult> {
     let mut virtual_pool = *virtual_pool;
 
     ensure!(
@@ -21,6 +37,16 @@ pub fn quote_exact_in(
         "virtual pool is completed"
     );
 
+    ensure!(amount_in > 0, "amount is zero");
+
+    let (input_transfer_fee, output_transfer_fee) = if swap_base_for_quote {
+        (base_mint_transfer_fee, quote_mint_transfer_fee)
+    } else {
+        (quote_mint_transfer_fee, base_mint_transfer_fee)
+    };
+
+    let transfer_fee_excluded_amount_in =
+        get_transfer_fee_excluded_amount(input_transfer_fee, amount_in)?.amount;
     ensure!(transfer_fee_excluded_amount_in > 0, "amount is zero");
 
     virtual_pool.update_pre_swap(config, current_timestamp)?;
@@ -45,5 +71,12 @@ pub fn quote_exact_in(
         current_point,
     )?;
 
-    Ok(swap_result)
+    let transfer_fee_excluded_amount_out =
+        get_transfer_fee_excluded_amount(output_transfer_fee, swap_result.output_amount)?.amount;
+
+    Ok(QuoteExactInResult {
+        swap_result,
+        transfer_fee_excluded_amount_in,
+        transfer_fee_excluded_amount_out,
+    })
 }
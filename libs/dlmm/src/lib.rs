@@ -0,0 +1,5 @@
+use anchor_lang::prelude::*;
+
+declare_program!(dlmm);
+
+pub use dlmm::*;
@@ -28,6 +28,15 @@ static_assertions::const_assert!(PARTNER_AND_CREATOR_SURPLUS_SHARE <= 100);
 
 pub const MAX_SWALLOW_PERCENTAGE: u8 = 20; // 20 %
 
+/// Max locked vesting tranches of a config, besides the locked vesting of pool creator
+pub const MAX_LOCKED_VESTING_TRANCHES: usize = 4;
+
+/// Max tolerance of the price of the pool created at migration from the final price of the curve
+pub const MAX_MIGRATION_PRICE_TOLERANCE_BPS: u16 = 1000; // 10%
+
+/// Expected slot duration, used to convert slot activation points to timestamps
+pub const SLOT_DURATION_IN_MS: u64 = 400;
+
 pub const MAX_RATE_LIMITER_DURATION_IN_SECONDS: u64 = 60 * 60 * 12; // 12 hours
 pub const MAX_RATE_LIMITER_DURATION_IN_SLOTS: u64 = 108000; // 12 hours
 static_assertions::const_assert_eq!(
@@ -35,6 +44,19 @@ static_assertions::const_assert_eq!(
     MAX_RATE_LIMITER_DURATION_IN_SLOTS
 );
 
+/// Limits of additional token 2022 metadata fields (e.g. twitter, telegram, website, description)
+pub const MAX_TOKEN_METADATA_FIELDS: usize = 8;
+pub const MAX_TOKEN_METADATA_KEY_LENGTH: usize = 32;
+pub const MAX_TOKEN_METADATA_VALUE_LENGTH: usize = 256;
+
+/// Limits of virtual pool metadata fields
+pub const MAX_POOL_METADATA_NAME_LENGTH: usize = 64;
+pub const MAX_POOL_METADATA_LINK_LENGTH: usize = 128;
+pub const MAX_POOL_METADATA_LOGO_LENGTH: usize = 256;
+pub const MAX_POOL_METADATA_DESCRIPTION_LENGTH: usize = 512;
+pub const MAX_POOL_METADATA_TAGS: usize = 8;
+pub const MAX_POOL_METADATA_TAG_LENGTH: usize = 32;
+
 /// Store constants related to fees
 pub mod fee {
 
@@ -61,11 +83,50 @@ pub mod fee {
         MIN_FEE_NUMERATOR
     );
 
+    /// Max base fee BPS of the pool created at migration, damm v2 base fee is capped at 50%
+    pub const MAX_MIGRATION_BASE_FEE_BPS: u64 = 5000; // 50%
+
     pub const PROTOCOL_FEE_PERCENT: u8 = 20; // 20%
 
     pub const HOST_FEE_PERCENT: u8 = 20; // 20%
 }
 
+pub mod dlmm {
+    pub const MIN_BIN_ID: i32 = -443636;
+    pub const MAX_BIN_ID: i32 = 443636;
+
+    pub const MAX_BIN_STEP: u16 = 400;
+
+    /// Number of bins in a dlmm bin array
+    pub const MAX_BIN_PER_ARRAY: i32 = 70;
+
+    /// Bin arrays with index in [-BIN_ARRAY_BITMAP_SIZE, BIN_ARRAY_BITMAP_SIZE) are tracked by the lb pair without bitmap extension
+    pub const BIN_ARRAY_BITMAP_SIZE: i64 = 512;
+
+    /// Migrated liquidity is placed in bins [active_id - MIGRATION_BIN_RANGE, active_id + MIGRATION_BIN_RANGE]
+    pub const MIGRATION_BIN_RANGE: i32 = 34;
+
+    // the range must fit in a single dlmm position, which spans at most 2 bin arrays
+    static_assertions::const_assert!(MIGRATION_BIN_RANGE * 2 < MAX_BIN_PER_ARRAY);
+}
+
+/// Space of accounts created with pool authority as payer at migration, whose layout is not in the amm idl.
+/// They are upper bounds, rent not spent by the amm is refunded to the migration payer
+pub mod account_space {
+    /// meteora damm pool
+    pub const METEORA_DAMM_POOL: usize = 952;
+    /// metaplex metadata of meteora damm lp mint
+    pub const METAPLEX_METADATA: usize = 679;
+    /// damm v2 position nft mint, token 2022 mint with close authority, metadata pointer and token metadata
+    pub const DAMM_V2_POSITION_NFT_MINT: usize = 628;
+    /// damm v2 position nft account, token 2022 account with immutable owner
+    pub const DAMM_V2_POSITION_NFT_ACCOUNT: usize = 170;
+    /// dlmm lb pair
+    pub const DLMM_LB_PAIR: usize = 904;
+    /// dlmm oracle with default 100 observations
+    pub const DLMM_ORACLE: usize = 3232;
+}
+
 pub mod seeds {
     pub const CONFIG_PREFIX: &[u8] = b"config";
     pub const CUSTOMIZABLE_POOL_PREFIX: &[u8] = b"cpool";
@@ -82,4 +143,12 @@ pub mod seeds {
     pub const PARTNER_METADATA_PREFIX: &[u8] = b"partner_metadata";
     pub const VIRTUAL_POOL_METADATA_PREFIX: &[u8] = b"virtual_pool_metadata";
     pub const BASE_LOCKER_PREFIX: &[u8] = b"base_locker";
+    pub const DLMM_METADATA_PREFIX: &[u8] = b"dlmm";
+    pub const DLMM_POSITION_PREFIX: &[u8] = b"dlmm_position";
+    /// seed of the config scoped PDA approved as metaplex collection authority
+    pub const COLLECTION_AUTHORITY_PREFIX: &[u8] = b"collection_authority";
+    /// seed of the migration authority PDA of external migration program
+    pub const EXTERNAL_MIGRATION_AUTHORITY_PREFIX: &[u8] = b"dbc_migration";
+    /// seed of the PDA signing the invocation of external migration program
+    pub const EXTERNAL_MIGRATION_SIGNER_PREFIX: &[u8] = b"migration_signer";
 }
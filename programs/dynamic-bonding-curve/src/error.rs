@@ -136,4 +136,76 @@ pub enum PoolError {
 
     #[msg("Fail to validate single swap instruction in rate limiter")]
     FailToValidateSingleSwapInstruction,
+
+    #[msg("Config is deprecated")]
+    ConfigIsDeprecated,
+
+    #[msg("Config is not closable")]
+    ConfigIsNotClosable,
+
+    #[msg("Pool creation is not approved by pool creator authority")]
+    UnauthorizedPoolCreator,
+
+    #[msg("Invalid pool creation fee")]
+    InvalidPoolCreationFee,
+
+    #[msg("Invalid base token transfer fee")]
+    InvalidBaseTransferFee,
+
+    #[msg("Unsupported base mint")]
+    UnsupportedBaseMint,
+
+    #[msg("Insufficient base token deposit")]
+    InsufficientBaseDeposit,
+
+    #[msg("Invalid token metadata field")]
+    InvalidTokenMetadataField,
+
+    #[msg("Token metadata is immutable")]
+    TokenMetadataIsImmutable,
+
+    #[msg("Invalid token metadata config")]
+    InvalidTokenMetadataConfig,
+
+    #[msg("Invalid virtual pool metadata")]
+    InvalidVirtualPoolMetadata,
+
+    #[msg("Invalid dlmm config")]
+    InvalidDlmmConfig,
+
+    #[msg("Invalid migration program")]
+    InvalidMigrationProgram,
+
+    #[msg("Invalid auto migration config")]
+    InvalidAutoMigrationConfig,
+
+    #[msg("Invalid migration crank reward")]
+    InvalidMigrationCrankReward,
+
+    #[msg("Invalid migrated pool fee")]
+    InvalidMigratedPoolFee,
+
+    #[msg("Invalid migration price range")]
+    InvalidMigrationPriceRange,
+
+    #[msg("Invalid migration price tolerance")]
+    InvalidMigrationPriceTolerance,
+
+    #[msg("Exceeded migration slippage")]
+    ExceededMigrationSlippage,
+
+    #[msg("Invalid refund config")]
+    InvalidRefundConfig,
+
+    #[msg("Pool is refunding")]
+    PoolIsRefunding,
+
+    #[msg("Invalid base token extension")]
+    InvalidBaseTokenExtension,
+
+    #[msg("Invalid token metadata update authority")]
+    InvalidTokenMetadataUpdateAuthority,
+
+    #[msg("Invalid partner metadata")]
+    InvalidPartnerMetadata,
 }
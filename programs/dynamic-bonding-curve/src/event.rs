@@ -6,7 +6,7 @@ use crate::{
         fee_parameters::PoolFeeParameters, liquidity_distribution::LiquidityDistributionParameters,
     },
     state::SwapResult,
-    LockedVestingParams, SwapParameters,
+    LockedVestingParams, SwapParameters, TokenMetadataField,
 };
 
 /// Create partner metadata
@@ -22,6 +22,35 @@ pub struct EvtVirtualPoolMetadata {
     pub virtual_pool_metadata: Pubkey,
     pub virtual_pool: Pubkey,
 }
+
+/// Update partner metadata
+#[event]
+pub struct EvtUpdatePartnerMetadata {
+    pub partner_metadata: Pubkey,
+    pub fee_claimer: Pubkey,
+}
+
+/// Close partner metadata
+#[event]
+pub struct EvtClosePartnerMetadata {
+    pub partner_metadata: Pubkey,
+    pub fee_claimer: Pubkey,
+}
+
+/// Update virtual pool metadata
+#[event]
+pub struct EvtUpdateVirtualPoolMetadata {
+    pub virtual_pool_metadata: Pubkey,
+    pub virtual_pool: Pubkey,
+}
+
+/// Close virtual pool metadata
+#[event]
+pub struct EvtCloseVirtualPoolMetadata {
+    pub virtual_pool_metadata: Pubkey,
+    pub virtual_pool: Pubkey,
+}
+
 /// Create config
 #[event]
 pub struct EvtCreateConfig {
@@ -64,6 +93,21 @@ pub struct EvtCloseClaimFeeOperator {
     pub operator: Pubkey,
 }
 
+/// Close config
+#[event]
+pub struct EvtCloseConfig {
+    pub config: Pubkey,
+    pub close_authority: Pubkey,
+    pub pool_count: u32,
+}
+
+/// Deprecate config
+#[event]
+pub struct EvtDeprecateConfig {
+    pub config: Pubkey,
+    pub pool_count: u32,
+}
+
 #[event]
 pub struct EvtInitializePool {
     pub pool: Pubkey,
@@ -72,6 +116,7 @@ pub struct EvtInitializePool {
     pub base_mint: Pubkey,
     pub pool_type: u8,
     pub activation_point: u64,
+    pub pool_creation_fee: u64,
 }
 
 #[event]
@@ -125,6 +170,11 @@ pub struct EvtCreateDammV2MigrationMetadata {
     pub virtual_pool: Pubkey,
 }
 
+#[event]
+pub struct EvtCreateDlmmMigrationMetadata {
+    pub virtual_pool: Pubkey,
+}
+
 #[event]
 pub struct EvtProtocolWithdrawSurplus {
     pub pool: Pubkey,
@@ -157,6 +207,13 @@ pub struct EvtUpdatePoolCreator {
     pub new_creator: Pubkey,
 }
 
+#[event]
+pub struct EvtUpdateTokenMetadataFields {
+    pub pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub fields: Vec<TokenMetadataField>,
+}
+
 #[event]
 pub struct EvtWithdrawMigrationFee {
     pub pool: Pubkey,
@@ -169,3 +226,128 @@ pub struct EvtPartnerWithdrawMigrationFee {
     pub pool: Pubkey,
     pub fee: u64,
 }
+
+#[event]
+pub struct EvtMigrateMeteoraDamm {
+    pub virtual_pool: Pubkey,
+    pub damm_pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub deposited_base_amount: u64,
+    pub deposited_quote_amount: u64,
+    pub partner_lp: u64,
+    pub partner_locked_lp: u64,
+    pub creator_lp: u64,
+    pub creator_locked_lp: u64,
+    pub burned_base_amount: u64,
+}
+
+#[event]
+pub struct EvtMigrateDammV2 {
+    pub virtual_pool: Pubkey,
+    pub damm_pool: Pubkey,
+    pub first_position: Pubkey,
+    pub first_position_owner: Pubkey,
+    pub first_position_unlocked_liquidity: u128,
+    pub first_position_locked_liquidity: u128,
+    /// default pubkey and 0 liquidity when the second position is not created
+    pub second_position: Pubkey,
+    pub second_position_owner: Pubkey,
+    pub second_position_unlocked_liquidity: u128,
+    pub second_position_locked_liquidity: u128,
+    pub deposited_base_amount: u64,
+    pub deposited_quote_amount: u64,
+    pub burned_base_amount: u64,
+}
+
+/// Liquidity of dlmm positions is spread over bins, so it is given as the token taken by each position
+#[event]
+pub struct EvtMigrateDlmm {
+    pub virtual_pool: Pubkey,
+    pub lb_pair: Pubkey,
+    /// default pubkey and 0 amounts when the position is not created
+    pub partner_position: Pubkey,
+    pub partner_position_base_amount: u64,
+    pub partner_position_quote_amount: u64,
+    pub creator_position: Pubkey,
+    pub creator_position_base_amount: u64,
+    pub creator_position_quote_amount: u64,
+    pub deposited_base_amount: u64,
+    pub deposited_quote_amount: u64,
+    /// quote token of the migration deposit not taken by the positions, left in quote vault
+    pub leftover_quote_amount: u64,
+    pub burned_base_amount: u64,
+}
+
+#[event]
+pub struct EvtMigrateExternalProgram {
+    pub virtual_pool: Pubkey,
+    pub migration_program: Pubkey,
+    pub migration_authority: Pubkey,
+    /// base and quote token sent to migration authority, before transfer fee
+    pub deposited_base_amount: u64,
+    pub deposited_quote_amount: u64,
+    pub burned_base_amount: u64,
+}
+
+#[event]
+pub struct EvtMeteoraDammLockLpToken {
+    pub virtual_pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub owner: Pubkey,
+    pub lock_escrow: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EvtMeteoraDammClaimLpToken {
+    pub virtual_pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EvtCreateLocker {
+    pub virtual_pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub escrow: Pubkey,
+    pub recipient: Pubkey,
+    pub locked_amount: u64,
+}
+
+#[event]
+pub struct EvtCancelLocker {
+    pub virtual_pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub escrow: Pubkey,
+    pub recipient: Pubkey,
+    /// unvested token transferred to partner
+    pub cancelled_amount: u64,
+}
+
+#[event]
+pub struct EvtUpdateLockerRecipient {
+    pub virtual_pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub escrow: Pubkey,
+    pub old_recipient: Pubkey,
+    pub new_recipient: Pubkey,
+}
+
+#[event]
+pub struct EvtStartRefund {
+    pub pool: Pubkey,
+    pub config: Pubkey,
+    /// base token redeemable for the refunded quote token
+    pub refund_base_amount: u64,
+    pub refund_quote_amount: u64,
+}
+
+#[event]
+pub struct EvtRedeemRefund {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    /// base token burned
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{assert_eq_admin, state::PoolConfig, EvtDeprecateConfig, PoolError};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeprecateConfigCtx<'info> {
+    #[account(mut)]
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        constraint = assert_eq_admin(admin.key()) @ PoolError::InvalidAdmin,
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// Stop new pools from being created on the config, a deprecated config with pools still can't be closed
+pub fn handle_deprecate_config(ctx: Context<DeprecateConfigCtx>) -> Result<()> {
+    let mut config = ctx.accounts.config.load_mut()?;
+    require!(!config.is_deprecated(), PoolError::ConfigIsDeprecated);
+    config.deprecate();
+
+    emit_cpi!(EvtDeprecateConfig {
+        config: ctx.accounts.config.key(),
+        pool_count: config.pool_count,
+    });
+
+    Ok(())
+}
@@ -8,3 +8,5 @@ pub mod ix_close_claim_protocol_fee_operator;
 pub use ix_close_claim_protocol_fee_operator::*;
 pub mod ix_withdraw_protocol_surplus;
 pub use ix_withdraw_protocol_surplus::*;
+pub mod ix_deprecate_config;
+pub use ix_deprecate_config::*;
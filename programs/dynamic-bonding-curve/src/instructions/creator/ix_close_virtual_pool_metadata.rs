@@ -0,0 +1,37 @@
+use crate::constants::seeds::VIRTUAL_POOL_METADATA_PREFIX;
+use crate::state::{VirtualPool, VirtualPoolMetadata};
+use crate::EvtCloseVirtualPoolMetadata;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseVirtualPoolMetadataCtx<'info> {
+    #[account(has_one = creator)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+    /// Virtual pool metadata
+    #[account(
+        mut,
+        seeds = [
+            VIRTUAL_POOL_METADATA_PREFIX,
+            virtual_pool.key().as_ref()
+        ],
+        bump,
+        has_one = virtual_pool,
+        close = rent_receiver,
+    )]
+    pub virtual_pool_metadata: Box<Account<'info, VirtualPoolMetadata>>,
+
+    pub creator: Signer<'info>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle_close_virtual_pool_metadata(ctx: Context<CloseVirtualPoolMetadataCtx>) -> Result<()> {
+    emit_cpi!(EvtCloseVirtualPoolMetadata {
+        virtual_pool_metadata: ctx.accounts.virtual_pool_metadata.key(),
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+    });
+    Ok(())
+}
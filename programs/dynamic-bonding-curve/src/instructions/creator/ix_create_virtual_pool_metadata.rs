@@ -1,5 +1,7 @@
 use crate::constants::seeds::VIRTUAL_POOL_METADATA_PREFIX;
-use crate::state::{VirtualPool, VirtualPoolMetadata};
+use crate::state::{
+    VirtualPool, VirtualPoolMetadata, VirtualPoolMetadataVersion, VirtualPoolSocials,
+};
 use crate::EvtVirtualPoolMetadata;
 use anchor_lang::prelude::*;
 
@@ -10,6 +12,7 @@ pub struct CreateVirtualPoolMetadataParameters {
     pub name: String,
     pub website: String,
     pub logo: String,
+    pub socials: VirtualPoolSocials,
 }
 
 #[event_cpi]
@@ -30,7 +33,12 @@ pub struct CreateVirtualPoolMetadataCtx<'info> {
         ],
         bump,
         payer = payer,
-        space = 8 + VirtualPoolMetadata::space(&metadata)
+        space = 8 + VirtualPoolMetadata::space(
+            &metadata.name,
+            &metadata.website,
+            &metadata.logo,
+            &metadata.socials
+        )
     )]
     pub virtual_pool_metadata: Box<Account<'info, VirtualPoolMetadata>>,
 
@@ -48,11 +56,20 @@ pub fn handle_create_virtual_pool_metadata(
     ctx: Context<CreateVirtualPoolMetadataCtx>,
     metadata: CreateVirtualPoolMetadataParameters,
 ) -> Result<()> {
+    VirtualPoolMetadata::validate(
+        &metadata.name,
+        &metadata.website,
+        &metadata.logo,
+        &metadata.socials,
+    )?;
+
     let virtual_pool_metadata = &mut ctx.accounts.virtual_pool_metadata;
     virtual_pool_metadata.virtual_pool = ctx.accounts.virtual_pool.key();
     virtual_pool_metadata.name = metadata.name;
     virtual_pool_metadata.website = metadata.website;
     virtual_pool_metadata.logo = metadata.logo;
+    virtual_pool_metadata.socials = metadata.socials;
+    virtual_pool_metadata.version = VirtualPoolMetadataVersion::V1.into();
     emit_cpi!(EvtVirtualPoolMetadata {
         virtual_pool_metadata: ctx.accounts.virtual_pool_metadata.key(),
         virtual_pool: ctx.accounts.virtual_pool.key(),
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, Token2022};
+
+use crate::{
+    process_update_token_metadata_fields,
+    state::{PoolConfig, PoolType, TokenUpdateAuthorityOption, VirtualPool},
+    EvtUpdateTokenMetadataFields, PoolError, ProcessUpdateTokenMetadataFieldsParams,
+    TokenMetadataField,
+};
+
+/// Accounts for the metadata update authority, pool creator at launch, to update additional metadata fields of
+/// token 2022 base mint
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTokenMetadataFieldsCtx<'info> {
+    #[account(
+        has_one = config,
+        has_one = base_mint,
+    )]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// metadata update authority of base mint, checked against the token metadata
+    pub update_authority: Signer<'info>,
+
+    /// Address paying rent of the extra mint account space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_update_token_metadata_fields(
+    ctx: Context<UpdateTokenMetadataFieldsCtx>,
+    fields: Vec<TokenMetadataField>,
+) -> Result<()> {
+    {
+        let pool = ctx.accounts.virtual_pool.load()?;
+        require!(
+            pool.pool_type == u8::from(PoolType::Token2022),
+            PoolError::InvalidTokenType
+        );
+        let config = ctx.accounts.config.load()?;
+        require!(
+            config.get_token_update_authority()? == TokenUpdateAuthorityOption::Mutable,
+            PoolError::TokenMetadataIsImmutable
+        );
+    }
+
+    process_update_token_metadata_fields(ProcessUpdateTokenMetadataFieldsParams {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
+        update_authority: ctx.accounts.update_authority.to_account_info(),
+        payer: ctx.accounts.payer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        fields: &fields,
+    })?;
+
+    emit_cpi!(EvtUpdateTokenMetadataFields {
+        pool: ctx.accounts.virtual_pool.key(),
+        base_mint: ctx.accounts.base_mint.key(),
+        fields,
+    });
+
+    Ok(())
+}
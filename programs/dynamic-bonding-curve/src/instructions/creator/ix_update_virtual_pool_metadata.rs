@@ -0,0 +1,74 @@
+use crate::constants::seeds::VIRTUAL_POOL_METADATA_PREFIX;
+use crate::state::{
+    VirtualPool, VirtualPoolMetadata, VirtualPoolMetadataVersion, VirtualPoolSocials,
+};
+use crate::EvtUpdateVirtualPoolMetadata;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateVirtualPoolMetadataParameters {
+    pub name: String,
+    pub website: String,
+    pub logo: String,
+    pub socials: VirtualPoolSocials,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(metadata: UpdateVirtualPoolMetadataParameters)]
+pub struct UpdateVirtualPoolMetadataCtx<'info> {
+    #[account(has_one = creator)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+    /// Virtual pool metadata, reallocated to the new string lengths
+    #[account(
+        mut,
+        seeds = [
+            VIRTUAL_POOL_METADATA_PREFIX,
+            virtual_pool.key().as_ref()
+        ],
+        bump,
+        has_one = virtual_pool,
+        realloc = 8 + VirtualPoolMetadata::space(
+            &metadata.name,
+            &metadata.website,
+            &metadata.logo,
+            &metadata.socials
+        ),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub virtual_pool_metadata: Box<Account<'info, VirtualPoolMetadata>>,
+
+    pub creator: Signer<'info>,
+
+    /// Payer of the extra rent, or receiver of the refunded rent when the metadata shrinks
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_update_virtual_pool_metadata(
+    ctx: Context<UpdateVirtualPoolMetadataCtx>,
+    metadata: UpdateVirtualPoolMetadataParameters,
+) -> Result<()> {
+    VirtualPoolMetadata::validate(
+        &metadata.name,
+        &metadata.website,
+        &metadata.logo,
+        &metadata.socials,
+    )?;
+
+    let virtual_pool_metadata = &mut ctx.accounts.virtual_pool_metadata;
+    virtual_pool_metadata.name = metadata.name;
+    virtual_pool_metadata.website = metadata.website;
+    virtual_pool_metadata.logo = metadata.logo;
+    virtual_pool_metadata.socials = metadata.socials;
+    virtual_pool_metadata.version = VirtualPoolMetadataVersion::V1.into();
+    emit_cpi!(EvtUpdateVirtualPoolMetadata {
+        virtual_pool_metadata: ctx.accounts.virtual_pool_metadata.key(),
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+    });
+    Ok(())
+}
@@ -6,3 +6,9 @@ pub mod ix_withdraw_creator_surplus;
 pub use ix_withdraw_creator_surplus::*;
 pub mod ix_transfer_pool_creator;
 pub use ix_transfer_pool_creator::*;
+pub mod ix_update_token_metadata_fields;
+pub use ix_update_token_metadata_fields::*;
+pub mod ix_update_virtual_pool_metadata;
+pub use ix_update_virtual_pool_metadata::*;
+pub mod ix_close_virtual_pool_metadata;
+pub use ix_close_virtual_pool_metadata::*;
@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    activation_handler::get_current_point,
+    const_pda,
+    constants::seeds::{POOL_PREFIX, TOKEN_VAULT_PREFIX},
+    max_key, min_key, process_pool_creation_fee,
+    state::{
+        fee::VolatilityTracker, PoolConfig, PoolConfigExtension, PoolType, TokenType, VirtualPool,
+    },
+    token::{is_supported_base_mint, transfer_from_user},
+    validate_pool_creator_authority, EvtInitializePool, PoolError, ProcessPoolCreationFeeParams,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeVirtualPoolWithExistingTokenCtx<'info> {
+    /// Which config the pool belongs to.
+    #[account(mut, has_one = quote_mint)]
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    /// CHECK: pool authority
+    #[account(
+        address = const_pda::pool_authority::ID
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub creator: Signer<'info>,
+
+    /// Existing base mint, the curve sells tokens deposited by payer instead of minting
+    #[account(
+        mint::token_program = token_program,
+        constraint = base_mint.key() != quote_mint.key() @ PoolError::InvalidQuoteMint,
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mint::token_program = token_quote_program,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Initialize an account to store the pool state
+    #[account(
+        init,
+        seeds = [
+            POOL_PREFIX,
+            config.key().as_ref(),
+            &max_key(&base_mint.key(), &quote_mint.key()),
+            &min_key(&base_mint.key(), &quote_mint.key()),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + VirtualPool::INIT_SPACE
+    )]
+    pub pool: AccountLoader<'info, VirtualPool>,
+
+    /// Token a vault for the pool
+    #[account(
+        init,
+        seeds = [
+            TOKEN_VAULT_PREFIX,
+            base_mint.key().as_ref(),
+            pool.key().as_ref(),
+        ],
+        token::mint = base_mint,
+        token::authority = pool_authority,
+        token::token_program = token_program,
+        payer = payer,
+        bump,
+    )]
+    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token b vault for the pool
+    #[account(
+        init,
+        seeds = [
+            TOKEN_VAULT_PREFIX,
+            quote_mint.key().as_ref(),
+            pool.key().as_ref(),
+        ],
+        token::mint = quote_mint,
+        token::authority = pool_authority,
+        token::token_program = token_quote_program,
+        payer = payer,
+        bump,
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payer token account to deposit base token from
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::token_program = token_program,
+    )]
+    pub payer_base_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Address paying to create the pool and depositing base token. Can be anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Program to create mint account and mint tokens
+    pub token_quote_program: Interface<'info, TokenInterface>,
+
+    /// Program of base mint, spl token or token 2022 as specified by config token type
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // Sysvar for program account
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_virtual_pool_with_existing_token<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithExistingTokenCtx<'info>>,
+) -> Result<()> {
+    let config_extension = PoolConfigExtension::load(&ctx.accounts.config)?;
+    validate_pool_creator_authority(
+        &config_extension,
+        &ctx.accounts.config.key(),
+        &ctx.accounts.creator.key(),
+        &ctx.accounts.base_mint.key(),
+        ctx.remaining_accounts,
+    )?;
+    // base transfer fee is only applied to base mint created by the program
+    require!(
+        !config_extension.has_base_transfer_fee(),
+        PoolError::InvalidBaseTransferFee
+    );
+    // holders of an existing mint didn't all buy from the curve, so they can't share its refund
+    require!(
+        !config_extension.has_refund_deadline(),
+        PoolError::InvalidRefundConfig
+    );
+    require!(
+        is_supported_base_mint(&ctx.accounts.base_mint)?,
+        PoolError::UnsupportedBaseMint
+    );
+
+    let pool_creation_fee = process_pool_creation_fee(ProcessPoolCreationFeeParams {
+        config_extension: &config_extension,
+        payer: &ctx.accounts.payer,
+        quote_mint: &ctx.accounts.quote_mint,
+        token_quote_program: &ctx.accounts.token_quote_program,
+        system_program: ctx.accounts.system_program.to_account_info(),
+        remaining_accounts: ctx.remaining_accounts,
+    })?;
+
+    let mut config = ctx.accounts.config.load_mut()?;
+    require!(!config.is_deprecated(), PoolError::ConfigIsDeprecated);
+    config.increase_pool_count()?;
+
+    require!(
+        ctx.accounts.base_mint.decimals == config.token_decimal,
+        PoolError::InvalidTokenDecimals
+    );
+
+    let token_type_value =
+        TokenType::try_from(config.token_type).map_err(|_| PoolError::InvalidTokenType)?;
+    let pool_type = match token_type_value {
+        TokenType::SplToken => PoolType::SplToken,
+        TokenType::Token2022 => PoolType::Token2022,
+    };
+    let expected_token_program = match token_type_value {
+        TokenType::SplToken => anchor_spl::token::ID,
+        TokenType::Token2022 => anchor_spl::token_2022::ID,
+    };
+    require!(
+        ctx.accounts.token_program.key() == expected_token_program,
+        PoolError::InvalidTokenType
+    );
+
+    // deposit base token instead of minting, metadata is managed by the mint owner
+    let initial_base_supply = config.get_initial_base_supply(&config_extension)?;
+    transfer_from_user(
+        &ctx.accounts.payer,
+        &ctx.accounts.base_mint,
+        &ctx.accounts.payer_base_token,
+        &ctx.accounts.base_vault,
+        &ctx.accounts.token_program,
+        initial_base_supply,
+    )?;
+
+    ctx.accounts.base_vault.reload()?;
+    require!(
+        ctx.accounts.base_vault.amount == initial_base_supply,
+        PoolError::InsufficientBaseDeposit
+    );
+
+    // init pool
+    let mut pool = ctx.accounts.pool.load_init()?;
+
+    let activation_point = get_current_point(config.activation_type)?;
+
+    pool.initialize(
+        VolatilityTracker::default(),
+        ctx.accounts.config.key(),
+        ctx.accounts.creator.key(),
+        ctx.accounts.base_mint.key(),
+        ctx.accounts.base_vault.key(),
+        ctx.accounts.quote_vault.key(),
+        config.sqrt_start_price,
+        pool_type.into(),
+        activation_point,
+        initial_base_supply,
+    );
+
+    emit_cpi!(EvtInitializePool {
+        pool: ctx.accounts.pool.key(),
+        config: ctx.accounts.config.key(),
+        creator: ctx.accounts.creator.key(),
+        base_mint: ctx.accounts.base_mint.key(),
+        pool_type: pool_type.into(),
+        activation_point,
+        pool_creation_fee,
+    });
+    Ok(())
+}
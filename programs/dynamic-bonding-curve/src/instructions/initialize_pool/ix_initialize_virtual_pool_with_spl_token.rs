@@ -12,9 +12,12 @@ use crate::{
     activation_handler::get_current_point,
     const_pda,
     constants::seeds::{POOL_PREFIX, TOKEN_VAULT_PREFIX},
-    process_create_token_metadata,
-    state::{fee::VolatilityTracker, PoolConfig, PoolType, TokenType, VirtualPool},
-    EvtInitializePool, PoolError, ProcessCreateTokenMetadataParams,
+    process_create_token_metadata, process_pool_creation_fee,
+    state::{
+        fee::VolatilityTracker, PoolConfig, PoolConfigExtension, PoolType, TokenType, VirtualPool,
+    },
+    validate_pool_creator_authority, EvtInitializePool, PoolError,
+    ProcessCreateTokenMetadataParams, ProcessPoolCreationFeeParams,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -37,7 +40,7 @@ pub fn min_key(left: &Pubkey, right: &Pubkey) -> [u8; 32] {
 #[derive(Accounts)]
 pub struct InitializeVirtualPoolWithSplTokenCtx<'info> {
     /// Which config the pool belongs to.
-    #[account(has_one = quote_mint)]
+    #[account(mut, has_one = quote_mint)]
     pub config: AccountLoader<'info, PoolConfig>,
 
     /// CHECK: pool authority
@@ -135,8 +138,29 @@ pub fn handle_initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithSplTokenCtx<'info>>,
     params: InitializePoolParameters,
 ) -> Result<()> {
-    let config = ctx.accounts.config.load()?;
-    let initial_base_supply = config.get_initial_base_supply()?;
+    let config_extension = PoolConfigExtension::load(&ctx.accounts.config)?;
+    validate_pool_creator_authority(
+        &config_extension,
+        &ctx.accounts.config.key(),
+        &ctx.accounts.creator.key(),
+        &ctx.accounts.base_mint.key(),
+        ctx.remaining_accounts,
+    )?;
+
+    let pool_creation_fee = process_pool_creation_fee(ProcessPoolCreationFeeParams {
+        config_extension: &config_extension,
+        payer: &ctx.accounts.payer,
+        quote_mint: &ctx.accounts.quote_mint,
+        token_quote_program: &ctx.accounts.token_quote_program,
+        system_program: ctx.accounts.system_program.to_account_info(),
+        remaining_accounts: ctx.remaining_accounts,
+    })?;
+
+    let mut config = ctx.accounts.config.load_mut()?;
+    require!(!config.is_deprecated(), PoolError::ConfigIsDeprecated);
+    config.increase_pool_count()?;
+
+    let initial_base_supply = config.get_initial_base_supply(&config_extension)?;
 
     let token_type_value =
         TokenType::try_from(config.token_type).map_err(|_| PoolError::InvalidTokenType)?;
@@ -161,6 +185,10 @@ pub fn handle_initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
         uri: &uri,
         pool_authority_bump: const_pda::pool_authority::BUMP,
         update_authority: config.get_token_update_authority()?,
+        metadata_creator: config_extension.has_metadata_creator(),
+        metadata_collection_mint: config_extension.get_metadata_collection_mint(),
+        config: ctx.accounts.config.key(),
+        remaining_accounts: ctx.remaining_accounts,
     })?;
 
     // mint token
@@ -217,6 +245,7 @@ pub fn handle_initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
         base_mint: ctx.accounts.base_mint.key(),
         pool_type: PoolType::SplToken.into(),
         activation_point,
+        pool_creation_fee,
     });
     Ok(())
 }
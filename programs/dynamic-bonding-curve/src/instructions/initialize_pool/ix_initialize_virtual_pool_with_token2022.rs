@@ -1,12 +1,18 @@
 use super::InitializePoolParameters;
-use super::{max_key, min_key};
+use super::{
+    max_key, min_key, process_create_token2022_base_mint, process_create_token2022_base_vault,
+    process_pool_creation_fee, process_update_token_metadata_fields,
+    validate_pool_creator_authority, validate_token_metadata_fields,
+    ProcessCreateToken2022BaseMintParams, ProcessCreateToken2022BaseVaultParams,
+    ProcessPoolCreationFeeParams, ProcessUpdateTokenMetadataFieldsParams, TokenMetadataField,
+};
 use crate::state::TokenUpdateAuthorityOption;
 use crate::{
     activation_handler::get_current_point,
     const_pda,
     constants::seeds::{POOL_PREFIX, TOKEN_VAULT_PREFIX},
     state::fee::VolatilityTracker,
-    state::{PoolConfig, PoolType, TokenType, VirtualPool},
+    state::{PoolConfig, PoolConfigExtension, PoolType, TokenType, VirtualPool},
     token::update_account_lamports_to_minimum_balance,
     EvtInitializePool, PoolError,
 };
@@ -23,7 +29,7 @@ use anchor_spl::{
 #[derive(Accounts)]
 pub struct InitializeVirtualPoolWithToken2022Ctx<'info> {
     /// Which config the pool belongs to.
-    #[account(has_one = quote_mint)]
+    #[account(mut, has_one = quote_mint)]
     pub config: AccountLoader<'info, PoolConfig>,
 
     /// CHECK: pool authority
@@ -34,18 +40,9 @@ pub struct InitializeVirtualPoolWithToken2022Ctx<'info> {
 
     pub creator: Signer<'info>,
 
-    /// Unique token mint address, initialize in contract
-    #[account(
-        init,
-        signer,
-        payer = payer,
-        mint::token_program = token_program,
-        mint::decimals = config.load()?.token_decimal,
-        mint::authority = pool_authority,
-        extensions::metadata_pointer::authority = pool_authority,
-        extensions::metadata_pointer::metadata_address = base_mint,
-    )]
-    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Unique token mint address, initialize in contract with extensions selected by config
+    #[account(mut, signer)]
+    pub base_mint: UncheckedAccount<'info>,
 
     #[account(
         mint::token_program = token_quote_program,
@@ -67,21 +64,17 @@ pub struct InitializeVirtualPoolWithToken2022Ctx<'info> {
     )]
     pub pool: AccountLoader<'info, VirtualPool>,
 
-    /// CHECK: Token base vault for the pool
+    /// CHECK: Token base vault for the pool, initialize in contract after base mint
     #[account(
-        init,
+        mut,
         seeds = [
             TOKEN_VAULT_PREFIX.as_ref(),
             base_mint.key().as_ref(),
             pool.key().as_ref(),
         ],
-        token::mint = base_mint,
-        token::authority = pool_authority,
-        token::token_program = token_program,
-        payer = payer,
         bump,
     )]
-    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub base_vault: UncheckedAccount<'info>,
 
     /// Token quote vault for the pool
     #[account(
@@ -114,8 +107,32 @@ pub struct InitializeVirtualPoolWithToken2022Ctx<'info> {
 pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithToken2022Ctx<'info>>,
     params: InitializePoolParameters,
+    additional_metadata: Vec<TokenMetadataField>,
 ) -> Result<()> {
-    let config = ctx.accounts.config.load()?;
+    validate_token_metadata_fields(&additional_metadata)?;
+
+    let config_extension = PoolConfigExtension::load(&ctx.accounts.config)?;
+    validate_pool_creator_authority(
+        &config_extension,
+        &ctx.accounts.config.key(),
+        &ctx.accounts.creator.key(),
+        &ctx.accounts.base_mint.key(),
+        ctx.remaining_accounts,
+    )?;
+
+    let pool_creation_fee = process_pool_creation_fee(ProcessPoolCreationFeeParams {
+        config_extension: &config_extension,
+        payer: &ctx.accounts.payer,
+        quote_mint: &ctx.accounts.quote_mint,
+        token_quote_program: &ctx.accounts.token_quote_program,
+        system_program: ctx.accounts.system_program.to_account_info(),
+        remaining_accounts: ctx.remaining_accounts,
+    })?;
+
+    let mut config = ctx.accounts.config.load_mut()?;
+    require!(!config.is_deprecated(), PoolError::ConfigIsDeprecated);
+    config.increase_pool_count()?;
+
     let token_type_value =
         TokenType::try_from(config.token_type).map_err(|_| PoolError::InvalidTokenType)?;
     require!(
@@ -125,6 +142,28 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
 
     let InitializePoolParameters { name, symbol, uri } = params;
 
+    // create base mint and base vault
+    process_create_token2022_base_mint(ProcessCreateToken2022BaseMintParams {
+        system_program: ctx.accounts.system_program.to_account_info(),
+        payer: ctx.accounts.payer.to_account_info(),
+        pool_authority: ctx.accounts.pool_authority.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
+        creator: ctx.accounts.creator.to_account_info(),
+        decimals: config.token_decimal,
+        config_extension: &config_extension,
+    })?;
+    process_create_token2022_base_vault(ProcessCreateToken2022BaseVaultParams {
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        payer: ctx.accounts.payer.to_account_info(),
+        pool_authority: ctx.accounts.pool_authority.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
+        vault: ctx.accounts.base_vault.to_account_info(),
+        pool: &ctx.accounts.pool.key(),
+        vault_bump: ctx.bumps.base_vault,
+        config_extension: &config_extension,
+    })?;
+
     // initialize metadata
     let cpi_accounts = TokenMetadataInitialize {
         program_id: ctx.accounts.token_program.to_account_info(),
@@ -142,6 +181,16 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
     );
     token_metadata_initialize(cpi_ctx, name, symbol, uri)?;
 
+    // write additional metadata fields, signed by creator as metadata update authority
+    process_update_token_metadata_fields(ProcessUpdateTokenMetadataFieldsParams {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.base_mint.to_account_info(),
+        update_authority: ctx.accounts.creator.to_account_info(),
+        payer: ctx.accounts.payer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        fields: &additional_metadata,
+    })?;
+
     // transfer minimum rent to mint account
     update_account_lamports_to_minimum_balance(
         ctx.accounts.base_mint.to_account_info(),
@@ -168,8 +217,7 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
         token_update_authority,
     )?;
 
-    let config = ctx.accounts.config.load()?;
-    let initial_base_supply = config.get_initial_base_supply()?;
+    let initial_base_supply = config.get_initial_base_supply(&config_extension)?;
 
     // mint token
     let seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
@@ -225,6 +273,7 @@ pub fn handle_initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
         base_mint: ctx.accounts.base_mint.key(),
         pool_type: PoolType::Token2022.into(),
         activation_point,
+        pool_creation_fee,
     });
     Ok(())
 }
@@ -2,5 +2,15 @@ pub mod ix_initialize_virtual_pool_with_spl_token;
 pub use ix_initialize_virtual_pool_with_spl_token::*;
 pub mod ix_initialize_virtual_pool_with_token2022;
 pub use ix_initialize_virtual_pool_with_token2022::*;
+pub mod ix_initialize_virtual_pool_with_existing_token;
+pub use ix_initialize_virtual_pool_with_existing_token::*;
 pub mod process_create_token_metadata;
 pub use process_create_token_metadata::*;
+pub mod validate_pool_creator_authority;
+pub use validate_pool_creator_authority::*;
+pub mod process_pool_creation_fee;
+pub use process_pool_creation_fee::*;
+pub mod process_create_token2022_base_mint;
+pub use process_create_token2022_base_mint::*;
+pub mod process_update_token_metadata_fields;
+pub use process_update_token_metadata_fields::*;
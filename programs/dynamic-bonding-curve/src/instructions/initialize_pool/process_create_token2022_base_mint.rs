@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction::{allocate, assign, create_account, transfer},
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        default_account_state::instruction::initialize_default_account_state,
+        interest_bearing_mint, metadata_pointer,
+        transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType,
+    },
+    state::AccountState,
+};
+
+use crate::{
+    constants::seeds::TOKEN_VAULT_PREFIX, state::PoolConfigExtension,
+    token::thaw_base_token_account,
+};
+
+pub struct ProcessCreateToken2022BaseMintParams<'a, 'info> {
+    pub system_program: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub pool_authority: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub creator: AccountInfo<'info>,
+    pub decimals: u8,
+    pub config_extension: &'a PoolConfigExtension,
+}
+
+/// Base mint extensions selected by config, metadata pointer is always initialized
+pub fn get_base_mint_extensions(config_extension: &PoolConfigExtension) -> Vec<ExtensionType> {
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if config_extension.has_base_transfer_fee() {
+        extensions.push(ExtensionType::TransferFeeConfig);
+    }
+    if config_extension.has_base_interest_rate() {
+        extensions.push(ExtensionType::InterestBearingConfig);
+    }
+    if config_extension.is_base_non_transferable() {
+        extensions.push(ExtensionType::DefaultAccountState);
+    }
+    extensions
+}
+
+/// Create base mint with selected token 2022 extensions.
+/// Transfer fee and interest rate can't be updated, withheld fee can be withdrawn by pool creator.
+/// Non-transferable base mint creates token accounts frozen, with pool authority as freeze authority
+pub fn process_create_token2022_base_mint(
+    params: ProcessCreateToken2022BaseMintParams,
+) -> Result<()> {
+    let ProcessCreateToken2022BaseMintParams {
+        system_program,
+        payer,
+        pool_authority,
+        mint,
+        creator,
+        decimals,
+        config_extension,
+    } = params;
+
+    let extensions = get_base_mint_extensions(config_extension);
+    let space =
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
+    create_token_account(&payer, &mint, &system_program, space, &[])?;
+
+    invoke(
+        &metadata_pointer::instruction::initialize(
+            &spl_token_2022::ID,
+            mint.key,
+            Some(pool_authority.key()),
+            Some(mint.key()),
+        )?,
+        &[mint.clone()],
+    )?;
+
+    if config_extension.has_base_transfer_fee() {
+        invoke(
+            &initialize_transfer_fee_config(
+                &spl_token_2022::ID,
+                mint.key,
+                None,
+                Some(creator.key),
+                config_extension.base_transfer_fee_basis_points,
+                config_extension.base_transfer_fee_maximum_fee,
+            )?,
+            &[mint.clone()],
+        )?;
+    }
+
+    if config_extension.has_base_interest_rate() {
+        invoke(
+            &interest_bearing_mint::instruction::initialize(
+                &spl_token_2022::ID,
+                mint.key,
+                None,
+                config_extension.base_interest_rate,
+            )?,
+            &[mint.clone()],
+        )?;
+    }
+
+    let freeze_authority = if config_extension.is_base_non_transferable() {
+        invoke(
+            &initialize_default_account_state(
+                &spl_token_2022::ID,
+                mint.key,
+                &AccountState::Frozen,
+            )?,
+            &[mint.clone()],
+        )?;
+        Some(pool_authority.key)
+    } else {
+        None
+    };
+
+    invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            mint.key,
+            pool_authority.key,
+            freeze_authority,
+            decimals,
+        )?,
+        &[mint],
+    )?;
+
+    Ok(())
+}
+
+pub struct ProcessCreateToken2022BaseVaultParams<'a, 'info> {
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub pool_authority: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub pool: &'a Pubkey,
+    pub vault_bump: u8,
+    pub config_extension: &'a PoolConfigExtension,
+}
+
+/// Create base vault, sized for the account extensions required by base mint extensions
+pub fn process_create_token2022_base_vault(
+    params: ProcessCreateToken2022BaseVaultParams,
+) -> Result<()> {
+    let ProcessCreateToken2022BaseVaultParams {
+        system_program,
+        token_program,
+        payer,
+        pool_authority,
+        mint,
+        vault,
+        pool,
+        vault_bump,
+        config_extension,
+    } = params;
+
+    let mint_extensions = get_base_mint_extensions(config_extension);
+    let account_extensions = ExtensionType::get_required_init_account_extensions(&mint_extensions);
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+        &account_extensions,
+    )?;
+    let mint_key = mint.key();
+    let vault_seeds = &[
+        TOKEN_VAULT_PREFIX,
+        mint_key.as_ref(),
+        pool.as_ref(),
+        &[vault_bump],
+    ];
+    create_token_account(&payer, &vault, &system_program, space, &[&vault_seeds[..]])?;
+
+    invoke(
+        &spl_token_2022::instruction::initialize_account3(
+            &spl_token_2022::ID,
+            vault.key,
+            mint.key,
+            pool_authority.key,
+        )?,
+        &[vault.clone(), mint.clone()],
+    )?;
+
+    // base vault of non-transferable base mint is created frozen
+    if config_extension.is_base_non_transferable() {
+        thaw_base_token_account(pool_authority, mint, vault, token_program)?;
+    }
+
+    Ok(())
+}
+
+/// Create account owned by token 2022 program. Like anchor init, the account may already hold lamports,
+/// in that case we top up rent, allocate and assign instead of create account
+fn create_token_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+    if current_lamports == 0 {
+        invoke_signed(
+            &create_account(
+                payer.key,
+                account.key,
+                rent_lamports,
+                space as u64,
+                &spl_token_2022::ID,
+            ),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            signer_seeds,
+        )?;
+        return Ok(());
+    }
+
+    let required_lamports = rent_lamports.saturating_sub(current_lamports);
+    if required_lamports > 0 {
+        invoke(
+            &transfer(payer.key, account.key, required_lamports),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &allocate(account.key, space as u64),
+        &[account.clone(), system_program.clone()],
+        signer_seeds,
+    )?;
+    invoke_signed(
+        &assign(account.key, &spl_token_2022::ID),
+        &[account.clone(), system_program.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
@@ -1,7 +1,14 @@
 use anchor_lang::prelude::*;
-use mpl_token_metadata::types::DataV2;
+use anchor_lang::solana_program::sysvar;
+use mpl_token_metadata::{
+    accounts::{CollectionAuthorityRecord, MasterEdition, Metadata},
+    instructions::{SignMetadataCpiBuilder, VerifyCollectionV1CpiBuilder},
+    types::{Collection, Creator, DataV2},
+};
 
-use crate::state::TokenUpdateAuthorityOption;
+use crate::{
+    constants::seeds::COLLECTION_AUTHORITY_PREFIX, state::TokenUpdateAuthorityOption, PoolError,
+};
 pub struct ProcessCreateTokenMetadataParams<'a, 'info> {
     pub system_program: AccountInfo<'info>,
     pub payer: AccountInfo<'info>,
@@ -15,6 +22,12 @@ pub struct ProcessCreateTokenMetadataParams<'a, 'info> {
     pub uri: &'a str,
     pub pool_authority_bump: u8,
     pub update_authority: TokenUpdateAuthorityOption,
+    /// list creator as verified creator
+    pub metadata_creator: bool,
+    pub metadata_collection_mint: Option<Pubkey>,
+    /// config of the pool, scopes the collection authority
+    pub config: Pubkey,
+    pub remaining_accounts: &'a [AccountInfo<'info>],
 }
 
 pub fn process_create_token_metadata(params: ProcessCreateTokenMetadataParams) -> Result<()> {
@@ -37,9 +50,22 @@ pub fn process_create_token_metadata(params: ProcessCreateTokenMetadataParams) -
 
     builder.payer(&params.payer);
     builder.system_program(&params.system_program);
+    let creators = params.metadata_creator.then(|| {
+        vec![Creator {
+            address: params.creator.key(),
+            verified: false,
+            share: 100,
+        }]
+    });
+    let collection = params
+        .metadata_collection_mint
+        .map(|collection_mint| Collection {
+            verified: false,
+            key: collection_mint,
+        });
     let data = DataV2 {
-        collection: None,
-        creators: None,
+        collection,
+        creators,
         name: params.name.to_string(),
         symbol: params.symbol.to_string(),
         seller_fee_basis_points: 0,
@@ -50,5 +76,69 @@ pub fn process_create_token_metadata(params: ProcessCreateTokenMetadataParams) -
 
     builder.invoke_signed(&[&seeds[..]])?;
 
+    // creator always signs pool creation, so creator can be verified
+    if params.metadata_creator {
+        SignMetadataCpiBuilder::new(&params.metadata_program)
+            .metadata(&params.mint_metadata)
+            .creator(&params.creator)
+            .invoke()?;
+    }
+
+    if let Some(collection_mint) = params.metadata_collection_mint {
+        verify_collection(&params, &collection_mint)?;
+    }
+
+    Ok(())
+}
+
+/// Collection is verified by the collection authority PDA of the config, which collection update authority must have
+/// approved. The collection authority with its record, collection accounts and instruction sysvar must be passed in
+/// remaining accounts, otherwise pool creation fails
+fn verify_collection(
+    params: &ProcessCreateTokenMetadataParams,
+    collection_mint: &Pubkey,
+) -> Result<()> {
+    let find_account = |key: &Pubkey| {
+        params
+            .remaining_accounts
+            .iter()
+            .find(|account| account.key == key)
+    };
+
+    let (collection_authority, collection_authority_bump) = Pubkey::find_program_address(
+        &[COLLECTION_AUTHORITY_PREFIX, params.config.as_ref()],
+        &crate::ID,
+    );
+    let collection_authority =
+        find_account(&collection_authority).ok_or(PoolError::InvalidAccount)?;
+    let (collection_authority_record, _) =
+        CollectionAuthorityRecord::find_pda(collection_mint, collection_authority.key);
+    let collection_authority_record =
+        find_account(&collection_authority_record).ok_or(PoolError::InvalidAccount)?;
+
+    let collection_mint = find_account(collection_mint).ok_or(PoolError::InvalidAccount)?;
+    let (collection_metadata, _) = Metadata::find_pda(collection_mint.key);
+    let collection_metadata =
+        find_account(&collection_metadata).ok_or(PoolError::InvalidAccount)?;
+    let (collection_master_edition, _) = MasterEdition::find_pda(collection_mint.key);
+    let collection_master_edition =
+        find_account(&collection_master_edition).ok_or(PoolError::InvalidAccount)?;
+    let sysvar_instructions =
+        find_account(&sysvar::instructions::ID).ok_or(PoolError::InvalidAccount)?;
+
+    VerifyCollectionV1CpiBuilder::new(&params.metadata_program)
+        .authority(collection_authority)
+        .delegate_record(Some(collection_authority_record))
+        .metadata(&params.mint_metadata)
+        .collection_mint(collection_mint)
+        .collection_metadata(Some(collection_metadata))
+        .collection_master_edition(Some(collection_master_edition))
+        .system_program(&params.system_program)
+        .sysvar_instructions(sysvar_instructions)
+        .invoke_signed(&[collection_authority_seeds!(
+            params.config,
+            collection_authority_bump
+        )])?;
+
     Ok(())
 }
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction::transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    state::{PoolConfigExtension, PoolCreationFeeToken},
+    token::transfer_from_user,
+    PoolError,
+};
+
+pub struct ProcessPoolCreationFeeParams<'a, 'info> {
+    pub config_extension: &'a PoolConfigExtension,
+    pub payer: &'a Signer<'info>,
+    pub quote_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_quote_program: &'a Interface<'info, TokenInterface>,
+    pub system_program: AccountInfo<'info>,
+    pub remaining_accounts: &'info [AccountInfo<'info>],
+}
+
+/// Charge pool creation fee from payer to the fee recipient of the config, return the charged amount.
+/// In SOL mode the recipient account is passed in remaining accounts,
+/// in quote token mode both payer and recipient quote token accounts are passed in remaining accounts
+pub fn process_pool_creation_fee<'info>(
+    params: ProcessPoolCreationFeeParams<'_, 'info>,
+) -> Result<u64> {
+    let ProcessPoolCreationFeeParams {
+        config_extension,
+        payer,
+        quote_mint,
+        token_quote_program,
+        system_program,
+        remaining_accounts,
+    } = params;
+
+    let fee = config_extension.pool_creation_fee;
+    if fee == 0 {
+        return Ok(0);
+    }
+    let recipient = config_extension.pool_creation_fee_recipient;
+
+    match config_extension.get_pool_creation_fee_token()? {
+        PoolCreationFeeToken::Sol => {
+            let recipient_account = remaining_accounts
+                .iter()
+                .find(|account| account.key() == recipient && account.is_writable)
+                .ok_or(PoolError::InvalidAccount)?;
+            invoke(
+                &transfer(payer.key, &recipient, fee),
+                &[
+                    payer.to_account_info(),
+                    recipient_account.clone(),
+                    system_program,
+                ],
+            )?;
+        }
+        PoolCreationFeeToken::QuoteToken => {
+            let quote_token_accounts: Vec<InterfaceAccount<'info, TokenAccount>> =
+                remaining_accounts
+                    .iter()
+                    .filter(|account| account.owner == token_quote_program.key)
+                    .filter_map(|account| InterfaceAccount::<TokenAccount>::try_from(account).ok())
+                    .filter(|token_account| token_account.mint == quote_mint.key())
+                    .collect();
+            let payer_token_account = quote_token_accounts
+                .iter()
+                .find(|token_account| token_account.owner == payer.key())
+                .ok_or(PoolError::InvalidAccount)?;
+            let recipient_token_account = quote_token_accounts
+                .iter()
+                .find(|token_account| token_account.owner == recipient)
+                .ok_or(PoolError::InvalidAccount)?;
+            transfer_from_user(
+                payer,
+                quote_mint,
+                payer_token_account,
+                recipient_token_account,
+                token_quote_program,
+                fee,
+            )?;
+        }
+    }
+
+    Ok(fee)
+}
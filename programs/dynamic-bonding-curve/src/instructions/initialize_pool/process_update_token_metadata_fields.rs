@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+};
+use spl_token_metadata_interface::{
+    instruction::{remove_key, update_field},
+    state::{Field, TokenMetadata},
+};
+
+use crate::{
+    constants::{
+        MAX_TOKEN_METADATA_FIELDS, MAX_TOKEN_METADATA_KEY_LENGTH, MAX_TOKEN_METADATA_VALUE_LENGTH,
+    },
+    token::update_account_lamports_to_minimum_balance,
+    PoolError,
+};
+
+/// Additional key-value field of token 2022 metadata, e.g. twitter, telegram, website, description
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TokenMetadataField {
+    pub key: String,
+    /// empty value removes the key
+    pub value: String,
+}
+
+// name, symbol and uri are set by their own fields, they can't be used as additional keys
+const RESERVED_TOKEN_METADATA_KEYS: [&str; 3] = ["name", "symbol", "uri"];
+
+pub fn validate_token_metadata_fields(fields: &[TokenMetadataField]) -> Result<()> {
+    require!(
+        fields.len() <= MAX_TOKEN_METADATA_FIELDS,
+        PoolError::InvalidTokenMetadataField
+    );
+    for (i, field) in fields.iter().enumerate() {
+        require!(
+            !field.key.is_empty()
+                && field.key.len() <= MAX_TOKEN_METADATA_KEY_LENGTH
+                && field.value.len() <= MAX_TOKEN_METADATA_VALUE_LENGTH
+                && !RESERVED_TOKEN_METADATA_KEYS.contains(&field.key.as_str()),
+            PoolError::InvalidTokenMetadataField
+        );
+        require!(
+            fields[..i].iter().all(|other| other.key != field.key),
+            PoolError::InvalidTokenMetadataField
+        );
+    }
+    Ok(())
+}
+
+/// Number of additional fields of token metadata after the update of fields, an empty value removes the key
+pub fn get_token_metadata_field_count(
+    additional_metadata: &[(String, String)],
+    fields: &[TokenMetadataField],
+) -> usize {
+    let kept_field_count = additional_metadata
+        .iter()
+        .filter(|(key, _)| fields.iter().all(|field| field.key != *key))
+        .count();
+    let written_field_count = fields
+        .iter()
+        .filter(|field| !field.value.is_empty())
+        .count();
+    kept_field_count + written_field_count
+}
+
+pub struct ProcessUpdateTokenMetadataFieldsParams<'a, 'info> {
+    pub token_program: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub update_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub fields: &'a [TokenMetadataField],
+}
+
+/// Write additional fields to token 2022 metadata stored in the mint account, then top up the mint account rent
+/// as token 2022 reallocates the mint account without transferring lamports
+pub fn process_update_token_metadata_fields(
+    params: ProcessUpdateTokenMetadataFieldsParams,
+) -> Result<()> {
+    let ProcessUpdateTokenMetadataFieldsParams {
+        token_program,
+        mint,
+        update_authority,
+        payer,
+        system_program,
+        fields,
+    } = params;
+
+    if fields.is_empty() {
+        return Ok(());
+    }
+    validate_token_metadata_fields(fields)?;
+
+    {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        let token_metadata = mint_state.get_variable_len_extension::<TokenMetadata>()?;
+        // metadata update authority stays with the mint when pool creator is transferred
+        require!(
+            Option::<Pubkey>::from(token_metadata.update_authority) == Some(update_authority.key()),
+            PoolError::InvalidTokenMetadataUpdateAuthority
+        );
+        // the total number of additional fields is capped, not only the fields of a single update
+        require!(
+            get_token_metadata_field_count(&token_metadata.additional_metadata, fields)
+                <= MAX_TOKEN_METADATA_FIELDS,
+            PoolError::InvalidTokenMetadataField
+        );
+    }
+
+    for field in fields {
+        let instruction = if field.value.is_empty() {
+            remove_key(
+                token_program.key,
+                mint.key,
+                update_authority.key,
+                field.key.clone(),
+                true,
+            )
+        } else {
+            update_field(
+                token_program.key,
+                mint.key,
+                update_authority.key,
+                Field::Key(field.key.clone()),
+                field.value.clone(),
+            )
+        };
+        invoke(&instruction, &[mint.clone(), update_authority.clone()])?;
+    }
+
+    update_account_lamports_to_minimum_balance(mint, payer, system_program)?;
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, sysvar};
+
+use crate::{state::PoolConfigExtension, PoolError};
+
+// ed25519 instruction layout: num_signatures (u8), padding (u8), then 14 bytes offsets for each signature
+const ED25519_SIGNATURE_OFFSETS_START: usize = 2;
+const ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const ED25519_PUBKEY_SERIALIZED_SIZE: usize = 32;
+
+/// Message the pool creator authority signs to permit pool creation without co-signing
+pub fn get_pool_creator_permit_message(
+    config: &Pubkey,
+    creator: &Pubkey,
+    base_mint: &Pubkey,
+) -> [u8; 96] {
+    let mut message = [0u8; 96];
+    message[..32].copy_from_slice(config.as_ref());
+    message[32..64].copy_from_slice(creator.as_ref());
+    message[64..].copy_from_slice(base_mint.as_ref());
+    message
+}
+
+/// When the config has a pool creator authority, pool creation must either be co-signed by the authority,
+/// or carry an ed25519 instruction (before the initialize pool instruction) with a permit signed by the authority.
+/// Authority signer or instruction sysvar is passed in remaining accounts
+pub fn validate_pool_creator_authority(
+    config_extension: &PoolConfigExtension,
+    config: &Pubkey,
+    creator: &Pubkey,
+    base_mint: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if !config_extension.has_pool_creator_authority() {
+        return Ok(());
+    }
+    let authority = config_extension.pool_creator_authority;
+
+    // co-signed by pool creator authority
+    if remaining_accounts
+        .iter()
+        .any(|account| account.is_signer && account.key() == authority)
+    {
+        return Ok(());
+    }
+
+    // ed25519 signed permit
+    let instruction_sysvar_account_info = remaining_accounts
+        .iter()
+        .find(|account| account.key() == sysvar::instructions::ID)
+        .ok_or(PoolError::UnauthorizedPoolCreator)?;
+
+    let message = get_pool_creator_permit_message(config, creator, base_mint);
+    let current_index =
+        sysvar::instructions::load_current_index_checked(instruction_sysvar_account_info)?;
+    for i in 0..current_index {
+        let instruction = sysvar::instructions::load_instruction_at_checked(
+            i.into(),
+            instruction_sysvar_account_info,
+        )?;
+        if instruction.program_id == ed25519_program::ID
+            && is_signed_by(&instruction.data, &authority, &message)
+        {
+            return Ok(());
+        }
+    }
+
+    Err(PoolError::UnauthorizedPoolCreator.into())
+}
+
+/// Check whether ed25519 instruction data contains a signature of the signer over the message.
+/// The signature itself is verified by the ed25519 program, we only need to check what has been verified.
+/// Only accept signatures referencing data inside the same instruction
+pub fn is_signed_by(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let Some(&num_signatures) = data.first() else {
+        return false;
+    };
+    for i in 0..usize::from(num_signatures) {
+        let start = ED25519_SIGNATURE_OFFSETS_START + i * ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let Some(offsets) = data.get(start..start + ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+        else {
+            return false;
+        };
+        let read_u16 = |index: usize| -> usize {
+            u16::from_le_bytes([offsets[index * 2], offsets[index * 2 + 1]]).into()
+        };
+        // signature_offset, signature_instruction_index, public_key_offset, public_key_instruction_index,
+        // message_data_offset, message_data_size, message_instruction_index
+        let same_instruction = usize::from(u16::MAX);
+        if read_u16(1) != same_instruction
+            || read_u16(3) != same_instruction
+            || read_u16(6) != same_instruction
+        {
+            continue;
+        }
+        let public_key_offset = read_u16(2);
+        let message_data_offset = read_u16(4);
+        let message_data_size = read_u16(5);
+
+        let public_key =
+            data.get(public_key_offset..public_key_offset + ED25519_PUBKEY_SERIALIZED_SIZE);
+        let signed_message = data.get(message_data_offset..message_data_offset + message_data_size);
+        if public_key == Some(signer.as_ref()) && signed_message == Some(message) {
+            return true;
+        }
+    }
+    false
+}
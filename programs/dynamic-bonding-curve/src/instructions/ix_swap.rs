@@ -6,10 +6,14 @@ use crate::{
     const_pda,
     params::swap::TradeDirection,
     state::fee::FeeMode,
-    state::{PoolConfig, VirtualPool},
-    token::{transfer_from_pool, transfer_from_user},
+    state::{PoolConfig, PoolConfigExtension, VirtualPool},
+    token::{
+        calculate_transfer_fee_excluded_amount, freeze_base_token_account,
+        thaw_restricted_base_token_account, transfer_from_pool, transfer_from_user,
+    },
     EvtSwap, PoolError,
 };
+use crate::{auto_migrate_damm_v2, get_total_locked_vesting_amount};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::{
     get_processed_sibling_instruction, get_stack_height,
@@ -88,7 +92,10 @@ impl<'info> SwapCtx<'info> {
 }
 
 // TODO impl swap exact out
-pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()> {
+pub fn handle_swap<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+    params: SwapParameters,
+) -> Result<()> {
     let SwapParameters {
         amount_in,
         minimum_amount_out,
@@ -123,6 +130,11 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
 
     require!(amount_in > 0, PoolError::AmountIsZero);
 
+    // vault only receives amount excluded transfer fee of the input token
+    let transfer_fee_excluded_amount_in =
+        calculate_transfer_fee_excluded_amount(&token_in_mint.to_account_info(), amount_in)?.amount;
+    require!(transfer_fee_excluded_amount_in > 0, PoolError::AmountIsZero);
+
     let has_referral = ctx.accounts.referral_token_account.is_some();
 
     let config = ctx.accounts.config.load()?;
@@ -148,17 +160,35 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         PoolError::PoolIsCompleted
     );
 
+    // pool not completed before the refund deadline only refunds holders
+    require!(
+        !PoolConfigExtension::load(&ctx.accounts.config)?
+            .is_refund_deadline_passed(pool.activation_point, current_point)?,
+        PoolError::PoolIsRefunding
+    );
+
     // update for dynamic fee reference
     let current_timestamp = Clock::get()?.unix_timestamp as u64;
     pool.update_pre_swap(&config, current_timestamp)?;
 
     let fee_mode = &FeeMode::get_fee_mode(config.collect_fee_mode, trade_direction, has_referral)?;
 
-    let swap_result =
-        pool.get_swap_result(&config, amount_in, fee_mode, trade_direction, current_point)?;
+    let swap_result = pool.get_swap_result(
+        &config,
+        transfer_fee_excluded_amount_in,
+        fee_mode,
+        trade_direction,
+        current_point,
+    )?;
 
+    // user only receives amount excluded transfer fee of the output token
+    let transfer_fee_excluded_amount_out = calculate_transfer_fee_excluded_amount(
+        &token_out_mint.to_account_info(),
+        swap_result.output_amount,
+    )?
+    .amount;
     require!(
-        swap_result.output_amount >= minimum_amount_out,
+        transfer_fee_excluded_amount_out >= minimum_amount_out,
         PoolError::ExceededSlippage
     );
 
@@ -170,6 +200,18 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         current_timestamp,
     )?;
 
+    // user base token account is frozen while base mint is non-transferable, it is only thawed for the swap
+    let user_base_token_account = match trade_direction {
+        TradeDirection::BaseToQuote => ctx.accounts.input_token_account.to_account_info(),
+        TradeDirection::QuoteToBase => ctx.accounts.output_token_account.to_account_info(),
+    };
+    let refreeze_base_token_account = thaw_restricted_base_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        user_base_token_account.clone(),
+        ctx.accounts.token_base_program.to_account_info(),
+    )?;
+
     // send to reserve
     transfer_from_user(
         &ctx.accounts.payer,
@@ -216,6 +258,15 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         }
     }
 
+    if refreeze_base_token_account {
+        freeze_base_token_account(
+            ctx.accounts.pool_authority.to_account_info(),
+            ctx.accounts.base_mint.to_account_info(),
+            user_base_token_account,
+            ctx.accounts.token_base_program.to_account_info(),
+        )?;
+    }
+
     emit_cpi!(EvtSwap {
         pool: ctx.accounts.pool.key(),
         config: ctx.accounts.config.key(),
@@ -227,20 +278,18 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         current_timestamp,
     });
 
+    let mut auto_migration = false;
     if pool.is_curve_complete(config.migration_quote_threshold) {
         ctx.accounts.base_vault.reload()?;
         // validate if base reserve is enough token for migration
         let base_vault_balance = ctx.accounts.base_vault.amount;
 
+        let config_extension = PoolConfigExtension::load(&ctx.accounts.config)?;
+        let locked_vestings = config.get_locked_vestings(&config_extension);
         let required_base_balance = config
             .migration_base_threshold
             .safe_add(pool.get_protocol_and_trading_base_fee()?)?
-            .safe_add(
-                config
-                    .locked_vesting_config
-                    .to_locked_vesting_params()
-                    .get_total_amount()?,
-            )?;
+            .safe_add(get_total_locked_vesting_amount(&locked_vestings)?)?;
 
         require!(
             base_vault_balance >= required_base_balance,
@@ -250,8 +299,7 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
         // set finish time and migration progress
         pool.finish_curve_timestamp = current_timestamp;
 
-        let locked_vesting_params = config.locked_vesting_config.to_locked_vesting_params();
-        if locked_vesting_params.has_vesting() {
+        if !locked_vestings.is_empty() {
             pool.set_migration_progress(MigrationProgress::PostBondingCurve.into());
         } else {
             pool.set_migration_progress(MigrationProgress::LockedVesting.into());
@@ -262,12 +310,39 @@ pub fn handle_swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()>
             config: ctx.accounts.config.key(),
             base_reserve: pool.base_reserve,
             quote_reserve: pool.quote_reserve,
-        })
+        });
+
+        auto_migration = config_extension.is_auto_migration_enabled();
+    }
+
+    // virtual pool and config are loaded again by migration
+    drop(pool);
+    drop(config);
+
+    if auto_migration {
+        let migration_accounts = get_auto_migration_accounts(ctx.remaining_accounts);
+        // without migration accounts, the pool is migrated later by migration_damm_v2
+        if !migration_accounts.is_empty() {
+            msg!("auto migration");
+            let migration_event =
+                auto_migrate_damm_v2(ctx.accounts.pool.key(), migration_accounts)?;
+            emit_cpi!(migration_event);
+        }
     }
 
     Ok(())
 }
 
+/// Migration accounts of auto migration, after the instruction sysvar used by rate limiter
+fn get_auto_migration_accounts<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+) -> &'c [AccountInfo<'info>] {
+    match remaining_accounts.first() {
+        Some(account) if account.key() == sysvar::instructions::ID => &remaining_accounts[1..],
+        _ => remaining_accounts,
+    }
+}
+
 pub fn validate_single_swap_instruction<'c, 'info>(
     pool: &Pubkey,
     remaining_accounts: &'c [AccountInfo<'info>],
@@ -1,13 +1,17 @@
 use crate::{
     const_pda,
     constants::seeds::BASE_LOCKER_PREFIX,
-    state::{MigrationProgress, PoolConfig, VirtualPool},
+    rent::{fund_pool_authority, get_rent_exempt_lamports, refund_pool_authority},
+    state::{
+        LockedVestingTrancheConfig, MigrationProgress, PoolConfig, PoolConfigExtension, VirtualPool,
+    },
+    token::thaw_restricted_base_token_account,
     *,
 };
-use anchor_lang::solana_program::{program::invoke, system_instruction};
 use anchor_spl::token_interface::{TokenAccount, TokenInterface};
-use locker::cpi::accounts::CreateVestingEscrowV2;
+use locker::{cpi::accounts::CreateVestingEscrowV2, types::CreateVestingEscrowParameters};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateLockerCtx<'info> {
     /// Virtual pool
@@ -45,7 +49,7 @@ pub struct CreateLockerCtx<'info> {
     pub base: UncheckedAccount<'info>,
     /// CHECK: owner
     pub creator: UncheckedAccount<'info>,
-    /// CHECK: escrow of locker, derived from base
+    /// CHECK: escrow of locker, derived from base, unused when config only sets locked vesting tranches
     #[account(mut)]
     pub escrow: UncheckedAccount<'info>,
 
@@ -70,7 +74,82 @@ pub struct CreateLockerCtx<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle_create_locker(ctx: Context<CreateLockerCtx>) -> Result<()> {
+/// Accounts of a locked vesting tranche passed in remaining accounts of [CreateLockerCtx], in tranche order:
+/// base (PDA of `[BASE_LOCKER_PREFIX, virtual_pool, tranche_index]`), escrow, escrow token and recipient
+pub const LOCKED_VESTING_TRANCHE_ACCOUNTS_LEN: usize = 4;
+
+impl<'info> CreateLockerCtx<'info> {
+    fn create_vesting_escrow(
+        &self,
+        base: AccountInfo<'info>,
+        base_seeds: &[&[u8]],
+        escrow: AccountInfo<'info>,
+        escrow_token: AccountInfo<'info>,
+        recipient: AccountInfo<'info>,
+        vesting_params: CreateVestingEscrowParameters,
+    ) -> Result<()> {
+        // escrow token account is created frozen while the base mint is non-transferable, it stays thawed so vested
+        // token is claimable once migration lifts the restriction
+        thaw_restricted_base_token_account(
+            self.pool_authority.to_account_info(),
+            self.base_mint.to_account_info(),
+            escrow_token.clone(),
+            self.token_program.to_account_info(),
+        )?;
+
+        let pool_authority_seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+        locker::cpi::create_vesting_escrow_v2(
+            CpiContext::new_with_signer(
+                self.locker_program.to_account_info(),
+                CreateVestingEscrowV2 {
+                    base,
+                    escrow,
+                    escrow_token,
+                    token_mint: self.base_mint.to_account_info(),
+                    sender: self.pool_authority.to_account_info(),
+                    sender_token: self.base_vault.to_account_info(),
+                    recipient,
+                    token_program: self.token_program.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    event_authority: self.locker_event_authority.to_account_info(),
+                    program: self.locker_program.to_account_info(),
+                },
+                &[base_seeds, &pool_authority_seeds[..]],
+            ),
+            vesting_params,
+            None,
+        )
+    }
+}
+
+/// Load a locker escrow created by [handle_create_locker] for the base mint of a virtual pool
+pub fn load_vesting_escrow(
+    escrow: &AccountInfo,
+    base_mint: &Pubkey,
+) -> Result<locker::accounts::VestingEscrow> {
+    let data = escrow.try_borrow_data()?;
+    let discriminator = locker::accounts::VestingEscrow::DISCRIMINATOR;
+    let end = discriminator.len() + std::mem::size_of::<locker::accounts::VestingEscrow>();
+    require!(
+        *escrow.owner == locker::ID && data.len() >= end && data.starts_with(discriminator),
+        PoolError::InvalidAccount
+    );
+    let vesting_escrow: locker::accounts::VestingEscrow =
+        bytemuck::pod_read_unaligned(&data[discriminator.len()..end]);
+    // base mint is unique to the virtual pool, so escrows of pool authority on it are escrows of the pool
+    require!(
+        vesting_escrow.creator == const_pda::pool_authority::ID
+            && vesting_escrow.token_mint == *base_mint,
+        PoolError::InvalidAccount
+    );
+    Ok(vesting_escrow)
+}
+
+/// Create the locker escrow of pool creator locked vesting with the accounts of the context, and an escrow for
+/// each locked vesting tranche with the accounts in remaining accounts
+pub fn handle_create_locker<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CreateLockerCtx<'info>>,
+) -> Result<()> {
     let mut virtual_pool = ctx.accounts.virtual_pool.load_mut()?;
 
     require!(
@@ -79,55 +158,119 @@ pub fn handle_create_locker(ctx: Context<CreateLockerCtx>) -> Result<()> {
     );
 
     let config = ctx.accounts.config.load()?;
+    let config_extension = PoolConfigExtension::load(&ctx.accounts.config)?;
 
     let locked_vesting_params = config.locked_vesting_config.to_locked_vesting_params();
-
-    let vesting_params = locked_vesting_params
-        .to_create_vesting_escrow_params(virtual_pool.finish_curve_timestamp)?;
+    let tranches: Vec<LockedVestingTrancheConfig> = config_extension
+        .get_locked_vesting_tranches()
+        .copied()
+        .collect();
+    require!(
+        ctx.remaining_accounts.len() == tranches.len() * LOCKED_VESTING_TRANCHE_ACCOUNTS_LEN,
+        PoolError::InvalidAccount
+    );
 
     let virtual_pool_key = ctx.accounts.virtual_pool.key();
-    let base_seeds = base_locker_seeds!(virtual_pool_key, ctx.bumps.base);
-
-    // Send some lamport to pool authority to pay rent fee?
-    msg!("transfer lamport to pool authority");
-    invoke(
-        &system_instruction::transfer(
-            &ctx.accounts.payer.key(),
-            &ctx.accounts.pool_authority.key(),
-            10_000_000, // TODO calculate correct lamport here
-        ),
-        &[
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.pool_authority.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    let vesting_start_time = config_extension.get_locked_vesting_start_time(
+        &virtual_pool,
+        config.activation_type,
+        &Clock::get()?,
     )?;
 
-    let pool_authority_seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
-    msg!("create vesting escrow for creator");
-    locker::cpi::create_vesting_escrow_v2(
-        CpiContext::new_with_signer(
-            ctx.accounts.locker_program.to_account_info(),
-            CreateVestingEscrowV2 {
-                base: ctx.accounts.base.to_account_info(), // use payer token account for base key, unique
-                escrow: ctx.accounts.escrow.to_account_info(),
-                escrow_token: ctx.accounts.escrow_token.to_account_info(),
-                token_mint: ctx.accounts.base_mint.to_account_info(),
-                sender: ctx.accounts.pool_authority.to_account_info(),
-                sender_token: ctx.accounts.base_vault.to_account_info(),
-                recipient: ctx.accounts.creator.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                event_authority: ctx.accounts.locker_event_authority.to_account_info(),
-                program: ctx.accounts.locker_program.to_account_info(),
-            },
-            &[&base_seeds[..], &pool_authority_seeds[..]],
-        ),
-        vesting_params,
-        None,
+    // pool authority is the escrow sender, which pays rent of the escrows
+    let escrow_count = tranches.len() + usize::from(locked_vesting_params.has_vesting());
+    let lamports_before = fund_pool_authority(
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        get_rent_exempt_lamports(&vec![
+            8 + std::mem::size_of::<locker::accounts::VestingEscrow>(
+            );
+            escrow_count
+        ])?,
+    )?;
+
+    if locked_vesting_params.has_vesting() {
+        msg!("create vesting escrow for creator");
+        let base_seeds = base_locker_seeds!(virtual_pool_key, ctx.bumps.base);
+        ctx.accounts.create_vesting_escrow(
+            ctx.accounts.base.to_account_info(),
+            &base_seeds[..],
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.escrow_token.to_account_info(),
+            ctx.accounts.creator.to_account_info(),
+            config
+                .locked_vesting_config
+                .to_create_vesting_escrow_params(vesting_start_time)?,
+        )?;
+
+        emit_cpi!(EvtCreateLocker {
+            virtual_pool: virtual_pool_key,
+            base_mint: ctx.accounts.base_mint.key(),
+            escrow: ctx.accounts.escrow.key(),
+            recipient: ctx.accounts.creator.key(),
+            locked_amount: locked_vesting_params.get_total_amount()?,
+        });
+    }
+
+    for (tranche_index, (tranche, accounts)) in tranches
+        .iter()
+        .zip(
+            ctx.remaining_accounts
+                .chunks(LOCKED_VESTING_TRANCHE_ACCOUNTS_LEN),
+        )
+        .enumerate()
+    {
+        let tranche_index = tranche_index as u8;
+        let (base, escrow, escrow_token, recipient) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+        let (base_key, base_bump) = Pubkey::find_program_address(
+            &[
+                BASE_LOCKER_PREFIX,
+                virtual_pool_key.as_ref(),
+                &[tranche_index],
+            ],
+            &crate::ID,
+        );
+        require!(
+            base.key() == base_key && recipient.key() == tranche.recipient,
+            PoolError::InvalidAccount
+        );
+
+        msg!("create vesting escrow for tranche {}", tranche_index);
+        let tranche_locked_vesting_params =
+            tranche.locked_vesting_config.to_locked_vesting_params();
+        let base_seeds = base_locker_tranche_seeds!(virtual_pool_key, tranche_index, base_bump);
+        ctx.accounts.create_vesting_escrow(
+            base.clone(),
+            &base_seeds[..],
+            escrow.clone(),
+            escrow_token.clone(),
+            recipient.clone(),
+            tranche
+                .locked_vesting_config
+                .to_create_vesting_escrow_params(vesting_start_time)?,
+        )?;
+
+        emit_cpi!(EvtCreateLocker {
+            virtual_pool: virtual_pool_key,
+            base_mint: ctx.accounts.base_mint.key(),
+            escrow: escrow.key(),
+            recipient: recipient.key(),
+            locked_amount: tranche_locked_vesting_params.get_total_amount()?,
+        });
+    }
+
+    refund_pool_authority(
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        lamports_before,
     )?;
 
     // set progress
+    virtual_pool.locker_payer = ctx.accounts.payer.key();
     virtual_pool.set_migration_progress(MigrationProgress::LockedVesting.into());
+
     Ok(())
 }
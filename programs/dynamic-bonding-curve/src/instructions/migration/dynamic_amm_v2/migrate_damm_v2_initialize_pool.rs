@@ -1,26 +1,31 @@
-use std::u64;
+use std::{collections::BTreeSet, u64};
 
-use anchor_lang::solana_program::{program::invoke, system_instruction};
 use anchor_spl::{
+    token::accessor,
     token_2022::{set_authority, spl_token_2022::instruction::AuthorityType, SetAuthority},
     token_interface::{TokenAccount, TokenInterface},
 };
-use damm_v2::types::{AddLiquidityParameters, InitializePoolParameters};
+use damm_v2::types::{
+    AddLiquidityParameters, InitializeCustomizablePoolParameters, InitializePoolParameters,
+};
 use ruint::aliases::U512;
 
 use crate::{
     const_pda,
-    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    constants::account_space,
     curve::{get_initial_liquidity_from_delta_base, get_initial_liquidity_from_delta_quote},
     params::fee_parameters::to_bps,
+    rent::{fund_pool_authority, get_rent_exempt_lamports, refund_pool_authority},
     safe_math::SafeMath,
     state::{
-        LiquidityDistribution, MigrationAmount, MigrationFeeOption, MigrationOption,
-        MigrationProgress, PoolConfig, VirtualPool,
+        LiquidityDistribution, MigratedPoolFeeConfig, MigrationFeeOption, MigrationOption,
+        PoolConfig, PoolConfigExtension, VirtualPool,
     },
+    token::{calculate_transfer_fee_excluded_amount, get_token_account_space},
     *,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MigrateDammV2Ctx<'info> {
     /// virtual pool
@@ -117,13 +122,55 @@ pub struct MigrateDammV2Ctx<'info> {
     pub damm_event_authority: UncheckedAccount<'info>,
     /// System program.
     pub system_program: Program<'info, System>,
+    /// quote token account receiving the migration crank reward, required when config sets a reward
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::token_program = token_quote_program
+    )]
+    pub crank_reward_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
 impl<'info> MigrateDammV2Ctx<'info> {
+    fn get_migration_event(
+        &self,
+        migration_result: &MigrationResult<DammV2DistributedLiquidity>,
+    ) -> EvtMigrateDammV2 {
+        let &MigrationResult {
+            distributed_liquidity,
+            burned_base_amount,
+            ..
+        } = migration_result;
+        EvtMigrateDammV2 {
+            virtual_pool: self.virtual_pool.key(),
+            damm_pool: self.pool.key(),
+            first_position: self.first_position.key(),
+            first_position_owner: distributed_liquidity.first_position_owner,
+            first_position_unlocked_liquidity: distributed_liquidity
+                .first_position_unlocked_liquidity,
+            first_position_locked_liquidity: distributed_liquidity.first_position_locked_liquidity,
+            second_position: self
+                .second_position
+                .as_ref()
+                .map(|position| position.key())
+                .unwrap_or_default(),
+            second_position_owner: distributed_liquidity.second_position_owner,
+            second_position_unlocked_liquidity: distributed_liquidity
+                .second_position_unlocked_liquidity,
+            second_position_locked_liquidity: distributed_liquidity
+                .second_position_locked_liquidity,
+            deposited_base_amount: distributed_liquidity.deposited_base_amount,
+            deposited_quote_amount: distributed_liquidity.deposited_quote_amount,
+            burned_base_amount,
+        }
+    }
+
     fn validate_config_key(
         &self,
         damm_config: &damm_v2::accounts::Config,
         migration_fee_option: u8,
+        migration_base_fee_bps: u16,
+        migration_sqrt_price_range: (u128, u128),
     ) -> Result<()> {
         let migration_fee_option = MigrationFeeOption::try_from(migration_fee_option)
             .map_err(|_| PoolError::InvalidMigrationFeeOption)?;
@@ -131,22 +178,13 @@ impl<'info> MigrateDammV2Ctx<'info> {
             damm_config.pool_fees.base_fee.cliff_fee_numerator.into(),
             1_000_000_000, // damm v2 using the same fee denominator with virtual curve
         )?;
-        migration_fee_option.validate_base_fee(base_fee_bps)?;
+        migration_fee_option.validate_base_fee(base_fee_bps, migration_base_fee_bps)?;
 
         // validate non fee scheduler
-        match migration_fee_option {
-            MigrationFeeOption::FixedBps25
-            | MigrationFeeOption::FixedBps30
-            | MigrationFeeOption::FixedBps100
-            | MigrationFeeOption::FixedBps200
-            | MigrationFeeOption::FixedBps400
-            | MigrationFeeOption::FixedBps600 => {
-                require!(
-                    damm_config.pool_fees.base_fee.period_frequency == 0,
-                    PoolError::InvalidConfigAccount
-                );
-            }
-        }
+        require!(
+            damm_config.pool_fees.base_fee.period_frequency == 0,
+            PoolError::InvalidConfigAccount
+        );
 
         require!(
             damm_config.pool_creator_authority == self.pool_authority.key(),
@@ -158,12 +196,12 @@ impl<'info> MigrateDammV2Ctx<'info> {
         );
 
         require!(
-            damm_config.sqrt_min_price == MIN_SQRT_PRICE,
+            damm_config.sqrt_min_price == migration_sqrt_price_range.0,
             PoolError::InvalidConfigAccount
         );
 
         require!(
-            damm_config.sqrt_max_price == MAX_SQRT_PRICE,
+            damm_config.sqrt_max_price == migration_sqrt_price_range.1,
             PoolError::InvalidConfigAccount
         );
 
@@ -174,7 +212,49 @@ impl<'info> MigrateDammV2Ctx<'info> {
         Ok(())
     }
 
-    fn create_pool(
+    /// Fund pool authority with rent of pool, first position and token vaults created by damm v2, returns
+    /// lamports of pool authority before funding
+    fn fund_pool_authority_for_pool_rent(&self) -> Result<u64> {
+        let lamports = get_rent_exempt_lamports(&[
+            8 + std::mem::size_of::<damm_v2::accounts::Pool>(),
+            8 + std::mem::size_of::<damm_v2::accounts::Position>(),
+            account_space::DAMM_V2_POSITION_NFT_MINT,
+            account_space::DAMM_V2_POSITION_NFT_ACCOUNT,
+            get_token_account_space(&self.base_mint.to_account_info())?,
+            get_token_account_space(&self.quote_mint.to_account_info())?,
+        ])?;
+        fund_pool_authority(
+            self.payer.to_account_info(),
+            self.pool_authority.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports,
+        )
+    }
+
+    /// Sqrt price of the damm v2 pool after it's created
+    fn get_pool_sqrt_price(&self) -> Result<u128> {
+        let data = self.pool.try_borrow_data()?;
+        let discriminator = damm_v2::accounts::Pool::DISCRIMINATOR;
+        let end = discriminator.len() + std::mem::size_of::<damm_v2::accounts::Pool>();
+        require!(
+            *self.pool.owner == damm_v2::ID && data.len() >= end && data.starts_with(discriminator),
+            PoolError::InvalidAccount
+        );
+        let pool: damm_v2::accounts::Pool =
+            bytemuck::pod_read_unaligned(&data[discriminator.len()..end]);
+        Ok(pool.sqrt_price)
+    }
+
+    fn refund_pool_authority_for_pool_rent(&self, lamports_before: u64) -> Result<()> {
+        refund_pool_authority(
+            self.pool_authority.to_account_info(),
+            self.payer.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports_before,
+        )
+    }
+
+    fn create_damm_pool(
         &self,
         pool_config: AccountInfo<'info>,
         liquidity: u128,
@@ -183,20 +263,7 @@ impl<'info> MigrateDammV2Ctx<'info> {
     ) -> Result<()> {
         let pool_authority_seeds = pool_authority_seeds!(bump);
 
-        // Send some lamport to pool authority to pay rent fee?
-        msg!("transfer lamport to pool_authority");
-        invoke(
-            &system_instruction::transfer(
-                &self.payer.key(),
-                &self.pool_authority.key(),
-                50_000_000, // TODO calculate correct lamport here
-            ),
-            &[
-                self.payer.to_account_info(),
-                self.pool_authority.to_account_info(),
-                self.system_program.to_account_info(),
-            ],
-        )?;
+        let lamports_before = self.fund_pool_authority_for_pool_rent()?;
 
         damm_v2::cpi::initialize_pool(
             CpiContext::new_with_signer(
@@ -232,7 +299,64 @@ impl<'info> MigrateDammV2Ctx<'info> {
             },
         )?;
 
-        Ok(())
+        self.refund_pool_authority_for_pool_rent(lamports_before)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_customizable_damm_pool(
+        &self,
+        migrated_pool_fee: &MigratedPoolFeeConfig,
+        activation_type: u8,
+        sqrt_min_price: u128,
+        sqrt_max_price: u128,
+        liquidity: u128,
+        sqrt_price: u128,
+        bump: u8,
+    ) -> Result<()> {
+        let pool_authority_seeds = pool_authority_seeds!(bump);
+
+        let lamports_before = self.fund_pool_authority_for_pool_rent()?;
+
+        damm_v2::cpi::initialize_customizable_pool(
+            CpiContext::new_with_signer(
+                self.amm_program.to_account_info(),
+                damm_v2::cpi::accounts::InitializeCustomizablePool {
+                    creator: self.pool_authority.to_account_info(),
+                    position_nft_mint: self.first_position_nft_mint.to_account_info(),
+                    position_nft_account: self.first_position_nft_account.to_account_info(),
+                    payer: self.pool_authority.to_account_info(),
+                    pool_authority: self.damm_pool_authority.to_account_info(),
+                    pool: self.pool.to_account_info(),
+                    position: self.first_position.to_account_info(),
+                    token_a_mint: self.base_mint.to_account_info(),
+                    token_b_mint: self.quote_mint.to_account_info(),
+                    token_a_vault: self.token_a_vault.to_account_info(),
+                    token_b_vault: self.token_b_vault.to_account_info(),
+                    payer_token_a: self.base_vault.to_account_info(),
+                    payer_token_b: self.quote_vault.to_account_info(),
+                    token_a_program: self.token_base_program.to_account_info(),
+                    token_b_program: self.token_quote_program.to_account_info(),
+                    token_2022_program: self.token_2022_program.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    event_authority: self.damm_event_authority.to_account_info(),
+                    program: self.amm_program.to_account_info(),
+                },
+                &[&pool_authority_seeds[..]],
+            ),
+            InitializeCustomizablePoolParameters {
+                pool_fees: migrated_pool_fee.to_damm_v2_pool_fee_parameters(),
+                sqrt_min_price,
+                sqrt_max_price,
+                has_alpha_vault: false,
+                liquidity,
+                sqrt_price,
+                activation_type,
+                collect_fee_mode: migrated_pool_fee.collect_fee_mode,
+                activation_point: None,
+            },
+        )?;
+
+        self.refund_pool_authority_for_pool_rent(lamports_before)
     }
 
     fn lock_permanent_liquidity_for_first_position(
@@ -275,11 +399,14 @@ impl<'info> MigrateDammV2Ctx<'info> {
         )?;
         Ok(())
     }
+    #[allow(clippy::too_many_arguments)]
     fn create_second_position(
         &self,
         owner: Pubkey,
         liquidity: u128,
         locked_liquidity: u128,
+        token_a_amount_threshold: u64,
+        token_b_amount_threshold: u64,
         bump: u8,
     ) -> Result<()> {
         let pool_authority_seeds = pool_authority_seeds!(bump);
@@ -339,8 +466,8 @@ impl<'info> MigrateDammV2Ctx<'info> {
             ),
             AddLiquidityParameters {
                 liquidity_delta: total_liquidity,
-                token_a_amount_threshold: u64::MAX, // TODO should we take care for that
-                token_b_amount_threshold: u64::MAX,
+                token_a_amount_threshold,
+                token_b_amount_threshold,
             },
         )?;
 
@@ -389,185 +516,351 @@ impl<'info> MigrateDammV2Ctx<'info> {
     }
 }
 
-pub fn handle_migrate_damm_v2<'c: 'info, 'info>(
-    ctx: Context<'_, '_, 'c, 'info, MigrateDammV2Ctx<'info>>,
-) -> Result<()> {
-    let config = ctx.accounts.config.load()?;
-    {
+/// Pool created with the first position, the position with more liquidity
+pub struct DammV2CreatedPool {
+    first_position_unlocked_liquidity: u128,
+    first_position_locked_liquidity: u128,
+    first_position_owner: Pubkey,
+    second_position_unlocked_liquidity: u128,
+    second_position_owner: Pubkey,
+    initial_base_vault_amount: u64,
+    initial_quote_vault_amount: u64,
+}
+
+/// Liquidity of the first and second positions, and token deposited to the pool by both positions
+#[derive(Debug, Clone, Copy)]
+pub struct DammV2DistributedLiquidity {
+    pub first_position_owner: Pubkey,
+    pub first_position_unlocked_liquidity: u128,
+    pub first_position_locked_liquidity: u128,
+    pub second_position_owner: Pubkey,
+    pub second_position_unlocked_liquidity: u128,
+    pub second_position_locked_liquidity: u128,
+    pub deposited_base_amount: u64,
+    pub deposited_quote_amount: u64,
+}
+
+impl<'info> MigrationAdapter<'info> for MigrateDammV2Ctx<'info> {
+    const MIGRATION_OPTION: MigrationOption = MigrationOption::DammV2;
+
+    type CreatedPool = DammV2CreatedPool;
+
+    type DistributedLiquidity = DammV2DistributedLiquidity;
+
+    fn virtual_pool(&self) -> &AccountLoader<'info, VirtualPool> {
+        &self.virtual_pool
+    }
+
+    fn config(&self) -> &AccountLoader<'info, PoolConfig> {
+        &self.config
+    }
+
+    fn pool_authority(&self) -> AccountInfo<'info> {
+        self.pool_authority.to_account_info()
+    }
+
+    fn base_mint(&self) -> AccountInfo<'info> {
+        self.base_mint.to_account_info()
+    }
+
+    fn base_vault(&self) -> AccountInfo<'info> {
+        self.base_vault.to_account_info()
+    }
+
+    fn token_base_program(&self) -> AccountInfo<'info> {
+        self.token_base_program.to_account_info()
+    }
+
+    fn validate(
+        &self,
+        config: &PoolConfig,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        // customizable pool is created with fees of config, without damm v2 config key
+        let config_extension = PoolConfigExtension::load(&self.config)?;
+        if config_extension.migrated_pool_fee.is_initialized() {
+            return Ok(());
+        }
         require!(
-            ctx.remaining_accounts.len() == 1,
+            remaining_accounts.len() == 1,
             PoolError::MissingPoolConfigInRemainingAccount
         );
         let damm_config_loader: AccountLoader<'_, damm_v2::accounts::Config> =
-            AccountLoader::try_from(&ctx.remaining_accounts[0])?; // TODO fix damm config in remaning accounts
+            AccountLoader::try_from(&remaining_accounts[0])?; // TODO fix damm config in remaning accounts
         let damm_config = damm_config_loader.load()?;
-        ctx.accounts
-            .validate_config_key(&damm_config, config.migration_fee_option)?;
+        self.validate_config_key(
+            &damm_config,
+            config.migration_fee_option,
+            config_extension.migration_base_fee_bps,
+            config_extension.get_migration_sqrt_price_range(),
+        )
     }
 
-    let mut virtual_pool = ctx.accounts.virtual_pool.load_mut()?;
-
-    require!(
-        virtual_pool.get_migration_progress()? == MigrationProgress::LockedVesting,
-        PoolError::NotPermitToDoThisAction
-    );
-
-    let migration_metadata = ctx.accounts.migration_metadata.load()?;
+    fn create_pool(
+        &self,
+        config: &PoolConfig,
+        virtual_pool: &VirtualPool,
+        deposit: &MigrationDeposit,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<DammV2CreatedPool> {
+        let migration_metadata = self.migration_metadata.load()?;
+        let initial_quote_vault_amount = self.quote_vault.amount;
+        let initial_base_vault_amount = self.base_vault.amount;
+
+        // damm v2 pulls transfer fee included amount from vault, so liquidity is computed from transfer fee excluded amount
+        let base_mint_info = self.base_mint.to_account_info();
+        let quote_mint_info = self.quote_mint.to_account_info();
+        let transfer_fee_excluded_base_amount =
+            calculate_transfer_fee_excluded_amount(&base_mint_info, deposit.base_amount)?.amount;
+        let transfer_fee_excluded_quote_amount =
+            calculate_transfer_fee_excluded_amount(&quote_mint_info, deposit.quote_amount)?.amount;
+
+        // calculate initial liquidity
+        let config_extension = PoolConfigExtension::load(&self.config)?;
+        let (sqrt_min_price, sqrt_max_price) = config_extension.get_migration_sqrt_price_range();
+        let initial_liquidity = get_liquidity_for_adding_liquidity(
+            transfer_fee_excluded_base_amount,
+            transfer_fee_excluded_quote_amount,
+            config.migration_sqrt_price,
+            sqrt_min_price,
+            sqrt_max_price,
+        )?;
 
-    require!(
-        virtual_pool.is_curve_complete(config.migration_quote_threshold),
-        PoolError::PoolIsIncompleted
-    );
+        let LiquidityDistribution {
+            partner: partner_liquidity_distribution,
+            creator: creator_liquidity_distribution,
+        } = config.get_liquidity_distribution(initial_liquidity)?;
 
-    let migration_option = MigrationOption::try_from(config.migration_option)
-        .map_err(|_| PoolError::InvalidMigrationOption)?;
-    require!(
-        migration_option == MigrationOption::DammV2,
-        PoolError::InvalidMigrationOption
-    );
-    let initial_quote_vault_amount = ctx.accounts.quote_vault.amount;
-    let initial_base_vault_amount = ctx.accounts.base_vault.amount;
+        let (
+            first_position_liquidity_distribution,
+            second_position_liquidity_distribution,
+            first_position_owner,
+            second_position_owner,
+        ) = if partner_liquidity_distribution.get_total_liquidity()?
+            > creator_liquidity_distribution.get_total_liquidity()?
+        {
+            (
+                partner_liquidity_distribution,
+                creator_liquidity_distribution,
+                migration_metadata.partner,
+                virtual_pool.creator,
+            )
+        } else {
+            (
+                creator_liquidity_distribution,
+                partner_liquidity_distribution,
+                virtual_pool.creator,
+                migration_metadata.partner,
+            )
+        };
+
+        let migrated_pool_fee = config_extension.migrated_pool_fee;
+        if migrated_pool_fee.is_initialized() {
+            self.create_customizable_damm_pool(
+                &migrated_pool_fee,
+                config.activation_type,
+                sqrt_min_price,
+                sqrt_max_price,
+                first_position_liquidity_distribution.get_total_liquidity()?,
+                config.migration_sqrt_price,
+                const_pda::pool_authority::BUMP,
+            )?;
+        } else {
+            self.create_damm_pool(
+                remaining_accounts[0].clone(),
+                first_position_liquidity_distribution.get_total_liquidity()?,
+                config.migration_sqrt_price,
+                const_pda::pool_authority::BUMP,
+            )?;
+        }
 
-    let protocol_and_partner_base_fee = virtual_pool.get_protocol_and_trading_base_fee()?;
-    let migration_sqrt_price = config.migration_sqrt_price;
+        if let Some(migration_price_tolerance_bps) =
+            config_extension.get_migration_price_tolerance_bps()
+        {
+            validate_migration_price(
+                get_price_from_sqrt_price(self.get_pool_sqrt_price()?)?,
+                virtual_pool.sqrt_price,
+                migration_price_tolerance_bps,
+            )?;
+        }
 
-    let MigrationAmount { quote_amount, .. } = config.get_migration_quote_amount_for_config()?;
-    let excluded_fee_base_reserve =
-        initial_base_vault_amount.safe_sub(protocol_and_partner_base_fee)?;
+        Ok(DammV2CreatedPool {
+            first_position_unlocked_liquidity: first_position_liquidity_distribution
+                .unlocked_liquidity,
+            first_position_locked_liquidity: first_position_liquidity_distribution.locked_liquidity,
+            first_position_owner,
+            second_position_unlocked_liquidity: second_position_liquidity_distribution
+                .unlocked_liquidity,
+            second_position_owner,
+            initial_base_vault_amount,
+            initial_quote_vault_amount,
+        })
+    }
 
-    // calculate initial liquidity
-    let initial_liquidity = get_liquidity_for_adding_liquidity(
-        excluded_fee_base_reserve,
-        quote_amount,
-        migration_sqrt_price,
-    )?;
+    fn distribute_liquidity(
+        &self,
+        config: &PoolConfig,
+        _virtual_pool: &VirtualPool,
+        deposit: &MigrationDeposit,
+        created_pool: DammV2CreatedPool,
+    ) -> Result<DammV2DistributedLiquidity> {
+        let DammV2CreatedPool {
+            first_position_unlocked_liquidity,
+            first_position_locked_liquidity,
+            first_position_owner,
+            second_position_unlocked_liquidity,
+            second_position_owner,
+            initial_base_vault_amount,
+            initial_quote_vault_amount,
+        } = created_pool;
+
+        // lock permanent liquidity
+        if first_position_locked_liquidity > 0 {
+            msg!("lock permanent liquidity for first position");
+            self.lock_permanent_liquidity_for_first_position(
+                first_position_locked_liquidity,
+                const_pda::pool_authority::BUMP,
+            )?;
+        }
 
-    let LiquidityDistribution {
-        partner: partner_liquidity_distribution,
-        creator: creator_liquidity_distribution,
-    } = config.get_liquidity_distribution(initial_liquidity)?;
-
-    let (
-        first_position_liquidity_distribution,
-        second_position_liquidity_distribution,
-        first_position_owner,
-        second_position_owner,
-    ) = if partner_liquidity_distribution.get_total_liquidity()?
-        > creator_liquidity_distribution.get_total_liquidity()?
-    {
-        (
-            partner_liquidity_distribution,
-            creator_liquidity_distribution,
-            migration_metadata.partner,
-            virtual_pool.creator,
-        )
-    } else {
-        (
-            creator_liquidity_distribution,
-            partner_liquidity_distribution,
-            virtual_pool.creator,
-            migration_metadata.partner,
-        )
-    };
-
-    // create pool
-    msg!("create pool");
-    ctx.accounts.create_pool(
-        ctx.remaining_accounts[0].clone(),
-        first_position_liquidity_distribution.get_total_liquidity()?,
-        config.migration_sqrt_price,
-        const_pda::pool_authority::BUMP,
-    )?;
-    // lock permanent liquidity
-    if first_position_liquidity_distribution.locked_liquidity > 0 {
-        msg!("lock permanent liquidity for first position");
-        ctx.accounts.lock_permanent_liquidity_for_first_position(
-            first_position_liquidity_distribution.locked_liquidity,
+        msg!("transfer ownership of the first position");
+        self.set_authority_for_first_position(
+            first_position_owner,
             const_pda::pool_authority::BUMP,
         )?;
-    }
 
-    msg!("transfer ownership of the first position");
-    ctx.accounts
-        .set_authority_for_first_position(first_position_owner, const_pda::pool_authority::BUMP)?;
-
-    // reload quote reserve and base reserve
-    ctx.accounts.quote_vault.reload()?;
-    ctx.accounts.base_vault.reload()?;
-    let deposited_base_amount =
-        initial_base_vault_amount.safe_sub(ctx.accounts.base_vault.amount)?;
-    let deposited_quote_amount =
-        initial_quote_vault_amount.safe_sub(ctx.accounts.quote_vault.amount)?;
-
-    let updated_excluded_fee_base_reserve =
-        excluded_fee_base_reserve.safe_sub(deposited_base_amount)?;
-    let updated_quote_threshold = quote_amount.safe_sub(deposited_quote_amount)?;
-    let liquidity_for_second_position = get_liquidity_for_adding_liquidity(
-        updated_excluded_fee_base_reserve,
-        updated_quote_threshold,
-        migration_sqrt_price,
-    )?;
+        // read quote reserve and base reserve after the first position
+        let deposited_base_amount = initial_base_vault_amount
+            .safe_sub(accessor::amount(&self.base_vault.to_account_info())?)?;
+        let deposited_quote_amount = initial_quote_vault_amount
+            .safe_sub(accessor::amount(&self.quote_vault.to_account_info())?)?;
+
+        let base_mint_info = self.base_mint.to_account_info();
+        let quote_mint_info = self.quote_mint.to_account_info();
+        let updated_excluded_fee_base_reserve =
+            deposit.base_amount.safe_sub(deposited_base_amount)?;
+        let updated_quote_threshold = deposit.quote_amount.safe_sub(deposited_quote_amount)?;
+        let transfer_fee_excluded_updated_base_reserve = calculate_transfer_fee_excluded_amount(
+            &base_mint_info,
+            updated_excluded_fee_base_reserve,
+        )?
+        .amount;
+        let transfer_fee_excluded_updated_quote_threshold =
+            calculate_transfer_fee_excluded_amount(&quote_mint_info, updated_quote_threshold)?
+                .amount;
+        let config_extension = PoolConfigExtension::load(&self.config)?;
+        let (sqrt_min_price, sqrt_max_price) = config_extension.get_migration_sqrt_price_range();
+        let liquidity_for_second_position = get_liquidity_for_adding_liquidity(
+            transfer_fee_excluded_updated_base_reserve,
+            transfer_fee_excluded_updated_quote_threshold,
+            config.migration_sqrt_price,
+            sqrt_min_price,
+            sqrt_max_price,
+        )?;
 
-    if liquidity_for_second_position > 0 {
-        msg!("create second position");
-        let unlocked_lp = liquidity_for_second_position
-            .min(second_position_liquidity_distribution.unlocked_liquidity);
+        let unlocked_lp = liquidity_for_second_position.min(second_position_unlocked_liquidity);
         let locked_lp = liquidity_for_second_position.safe_sub(unlocked_lp)?;
-        ctx.accounts.create_second_position(
-            second_position_owner,
-            unlocked_lp,
-            locked_lp,
-            const_pda::pool_authority::BUMP,
+        if liquidity_for_second_position > 0 {
+            msg!("create second position");
+            // second position takes at most the deposit left after the first position
+            self.create_second_position(
+                second_position_owner,
+                unlocked_lp,
+                locked_lp,
+                updated_excluded_fee_base_reserve,
+                updated_quote_threshold,
+                const_pda::pool_authority::BUMP,
+            )?;
+        }
+
+        // quote token binds the liquidity of both positions, base token left is burned
+        let deposited_base_amount = initial_base_vault_amount
+            .safe_sub(accessor::amount(&self.base_vault.to_account_info())?)?;
+        let deposited_quote_amount = initial_quote_vault_amount
+            .safe_sub(accessor::amount(&self.quote_vault.to_account_info())?)?;
+        validate_migration_deposit_amount(deposited_base_amount, deposit.base_amount, None)?;
+        validate_migration_deposit_amount(
+            deposited_quote_amount,
+            deposit.quote_amount,
+            config_extension.get_migration_price_tolerance_bps(),
         )?;
-    }
 
-    virtual_pool.update_after_create_pool();
+        Ok(DammV2DistributedLiquidity {
+            first_position_owner,
+            first_position_unlocked_liquidity,
+            first_position_locked_liquidity,
+            second_position_owner,
+            second_position_unlocked_liquidity: unlocked_lp,
+            second_position_locked_liquidity: locked_lp,
+            deposited_base_amount,
+            deposited_quote_amount,
+        })
+    }
 
-    // burn the rest of token in pool authority after migrated amount and fee
-    ctx.accounts.base_vault.reload()?;
+    fn pay_crank_reward(&self, amount: u64) -> Result<()> {
+        transfer_crank_reward(
+            self.pool_authority.to_account_info(),
+            self.quote_mint.to_account_info(),
+            self.quote_vault.to_account_info(),
+            self.crank_reward_token_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            self.token_quote_program.to_account_info(),
+            amount,
+        )
+    }
+}
 
-    // check whether we should burn token
+pub fn handle_migrate_damm_v2<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MigrateDammV2Ctx<'info>>,
+) -> Result<()> {
+    let migration_result = process_migration(ctx.accounts, ctx.remaining_accounts)?;
 
-    let left_base_token = ctx
-        .accounts
-        .base_vault
-        .amount
-        .safe_sub(protocol_and_partner_base_fee)?;
+    emit_cpi!(ctx.accounts.get_migration_event(&migration_result));
 
-    let burnable_amount = config.get_burnable_amount_post_migration(left_base_token)?;
+    Ok(())
+}
 
-    if burnable_amount > 0 {
-        let seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
-        anchor_spl::token_interface::burn(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_base_program.to_account_info(),
-                anchor_spl::token_interface::Burn {
-                    mint: ctx.accounts.base_mint.to_account_info(),
-                    from: ctx.accounts.base_vault.to_account_info(),
-                    authority: ctx.accounts.pool_authority.to_account_info(),
-                },
-                &[&seeds[..]],
-            ),
-            burnable_amount,
-        )?;
-    }
+/// Migrate to damm v2 in the swap completing the curve, returns the migration event emitted by swap. Accounts
+/// are laid out as [MigrateDammV2Ctx] accounts followed by the damm v2 config, which is omitted when config
+/// sets migrated pool fees
+pub fn auto_migrate_damm_v2<'info>(
+    virtual_pool: Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<EvtMigrateDammV2> {
+    let mut remaining_accounts = accounts;
+    let mut bumps = MigrateDammV2CtxBumps::default();
+    let mut reallocs = BTreeSet::new();
+    let migrate_accounts = MigrateDammV2Ctx::try_accounts(
+        &crate::ID,
+        &mut remaining_accounts,
+        &[],
+        &mut bumps,
+        &mut reallocs,
+    )?;
+    require!(
+        migrate_accounts.virtual_pool.key() == virtual_pool,
+        PoolError::InvalidAccount
+    );
 
-    virtual_pool.set_migration_progress(MigrationProgress::CreatedPool.into());
+    let migration_result = process_migration(&migrate_accounts, remaining_accounts)?;
 
-    // TODO emit event
+    migrate_accounts.exit(&crate::ID)?;
 
-    Ok(())
+    Ok(migrate_accounts.get_migration_event(&migration_result))
 }
 
 fn get_liquidity_for_adding_liquidity(
     base_amount: u64,
     quote_amount: u64,
     sqrt_price: u128,
+    sqrt_min_price: u128,
+    sqrt_max_price: u128,
 ) -> Result<u128> {
     let liquidity_from_base =
-        get_initial_liquidity_from_delta_base(base_amount, MAX_SQRT_PRICE, sqrt_price)?;
+        get_initial_liquidity_from_delta_base(base_amount, sqrt_max_price, sqrt_price)?;
     let liquidity_from_quote =
-        get_initial_liquidity_from_delta_quote(quote_amount, MIN_SQRT_PRICE, sqrt_price)?;
+        get_initial_liquidity_from_delta_quote(quote_amount, sqrt_min_price, sqrt_price)?;
     if liquidity_from_base > U512::from(liquidity_from_quote) {
         Ok(liquidity_from_quote)
     } else {
@@ -0,0 +1,419 @@
+//! Migration to a partner owned program.
+//!
+//! Migrated base and quote token are transferred to token accounts owned by the migration authority of the
+//! external program, a PDA of the external program with seeds
+//! `[EXTERNAL_MIGRATION_AUTHORITY_PREFIX, virtual_pool]`. After the virtual pool is marked as migrated, the
+//! external program is invoked with instruction data `EXTERNAL_MIGRATION_DISCRIMINATOR ||
+//! borsh(ExternalMigrationParameters)` and accounts:
+//!
+//! 0. `[]` virtual pool, already marked as migrated
+//! 1. `[]` config
+//! 2. `[]` migration authority
+//! 3. `[]` base mint
+//! 4. `[]` quote mint
+//! 5. `[writable]` base token account of migration authority
+//! 6. `[writable]` quote token account of migration authority
+//! 7. `[signer]` migration signer, PDA of this program with seeds
+//!    `[EXTERNAL_MIGRATION_SIGNER_PREFIX, virtual_pool]`
+//! 8. remaining accounts of `migrate_external_program`, passed as is but never as signer
+//!
+//! The external program creates its pool and distributes liquidity to partner and creator following the lp
+//! percentages in the parameters. It must verify that the migration signer is the PDA of this program for the
+//! virtual pool given in account 0, so only the migration of that virtual pool can invoke it, and must handle a
+//! virtual pool only once. No signer of the transaction is forwarded, so the external program can't charge a
+//! payer and must pre-fund the accounts it creates, e.g. from lamports held by its own PDA or by creating them
+//! in an earlier instruction of the migration transaction.
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::{
+    token::accessor,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    const_pda,
+    constants::seeds::{EXTERNAL_MIGRATION_AUTHORITY_PREFIX, EXTERNAL_MIGRATION_SIGNER_PREFIX},
+    safe_math::SafeMath,
+    state::{MigrationAmount, MigrationOption, PoolConfig, PoolConfigExtension, VirtualPool},
+    token::{
+        calculate_transfer_fee_excluded_amount, lift_base_transfer_restriction,
+        thaw_base_token_account, transfer_from_pool,
+    },
+    *,
+};
+
+/// Discriminator of the migration instruction of external program, sha256("global:on_dbc_migration")[..8]
+pub const EXTERNAL_MIGRATION_DISCRIMINATOR: [u8; 8] = [116, 231, 111, 60, 130, 224, 107, 212];
+
+/// Parameters of the migration instruction of external program
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExternalMigrationParameters {
+    pub virtual_pool: Pubkey,
+    pub config: Pubkey,
+    /// base token sent from base vault, transfer fee of token 2022 base mint is deducted on receive
+    pub base_amount: u64,
+    /// quote token sent from quote vault, transfer fee of token 2022 quote mint is deducted on receive
+    pub quote_amount: u64,
+    /// sqrt price of the virtual pool at migration
+    pub sqrt_price: u128,
+    pub partner: Pubkey,
+    pub creator: Pubkey,
+    pub partner_lp_percentage: u8,
+    pub partner_locked_lp_percentage: u8,
+    pub creator_lp_percentage: u8,
+    pub creator_locked_lp_percentage: u8,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateExternalProgramCtx<'info> {
+    /// virtual pool
+    #[account(mut, has_one = base_vault, has_one = quote_vault, has_one = config)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    /// virtual pool config key
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    /// CHECK: pool authority
+    #[account(
+        mut,
+        address = const_pda::pool_authority::ID,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: migration authority of external program, validated against config migration program
+    pub migration_authority: UncheckedAccount<'info>,
+
+    /// CHECK: signer of the external program invocation
+    #[account(
+        seeds = [EXTERNAL_MIGRATION_SIGNER_PREFIX, virtual_pool.key().as_ref()],
+        bump,
+    )]
+    pub migration_signer: UncheckedAccount<'info>,
+
+    /// base token account of migration authority
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = migration_authority,
+        token::token_program = token_base_program
+    )]
+    pub base_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// quote token account of migration authority
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = migration_authority,
+        token::token_program = token_quote_program
+    )]
+    pub quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// base token mint
+    #[account(mut)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// quote token mint
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// base vault
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::token_program = token_base_program
+    )]
+    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// quote vault
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::token_program = token_quote_program
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: external migration program, validated against config migration program
+    pub migration_program: UncheckedAccount<'info>,
+
+    /// payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// token program of base mint
+    pub token_base_program: Interface<'info, TokenInterface>,
+    /// token program of quote mint
+    pub token_quote_program: Interface<'info, TokenInterface>,
+}
+
+/// Vault sends exactly the deposit, migration authority receives the deposit less transfer fee
+fn validate_deposit_amount(
+    mint: &AccountInfo,
+    vault: &AccountInfo,
+    token_account: &AccountInfo,
+    vault_amount_before: u64,
+    token_account_amount_before: u64,
+    deposit_amount: u64,
+) -> Result<()> {
+    let sent_amount = vault_amount_before.safe_sub(accessor::amount(vault)?)?;
+    validate_migration_deposit_amount(sent_amount, deposit_amount, Some(0))?;
+
+    let received_amount = accessor::amount(token_account)?.safe_sub(token_account_amount_before)?;
+    let minimum_received_amount =
+        calculate_transfer_fee_excluded_amount(mint, deposit_amount)?.amount;
+    validate_migration_deposit_amount(received_amount, deposit_amount, None)?;
+    require!(
+        received_amount >= minimum_received_amount,
+        PoolError::ExceededMigrationSlippage
+    );
+    Ok(())
+}
+
+impl<'info> MigrateExternalProgramCtx<'info> {
+    fn invoke_migration_program(
+        &self,
+        params: &ExternalMigrationParameters,
+        migration_signer_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.virtual_pool.key(), false),
+            AccountMeta::new_readonly(self.config.key(), false),
+            AccountMeta::new_readonly(self.migration_authority.key(), false),
+            AccountMeta::new_readonly(self.base_mint.key(), false),
+            AccountMeta::new_readonly(self.quote_mint.key(), false),
+            AccountMeta::new(self.base_token_account.key(), false),
+            AccountMeta::new(self.quote_token_account.key(), false),
+            AccountMeta::new_readonly(self.migration_signer.key(), true),
+        ];
+        let mut account_infos = vec![
+            self.virtual_pool.to_account_info(),
+            self.config.to_account_info(),
+            self.migration_authority.to_account_info(),
+            self.base_mint.to_account_info(),
+            self.quote_mint.to_account_info(),
+            self.base_token_account.to_account_info(),
+            self.quote_token_account.to_account_info(),
+            self.migration_signer.to_account_info(),
+        ];
+        for account in remaining_accounts {
+            accounts.push(AccountMeta {
+                pubkey: account.key(),
+                is_signer: false,
+                is_writable: account.is_writable,
+            });
+            account_infos.push(account.clone());
+        }
+
+        let mut data = EXTERNAL_MIGRATION_DISCRIMINATOR.to_vec();
+        params.serialize(&mut data)?;
+
+        let virtual_pool = self.virtual_pool.key();
+        invoke_signed(
+            &Instruction {
+                program_id: self.migration_program.key(),
+                accounts,
+                data,
+            },
+            &account_infos,
+            &[external_migration_signer_seeds!(
+                virtual_pool,
+                migration_signer_bump
+            )],
+        )?;
+        Ok(())
+    }
+}
+
+impl<'info> MigrationAdapter<'info> for MigrateExternalProgramCtx<'info> {
+    const MIGRATION_OPTION: MigrationOption = MigrationOption::ExternalProgram;
+
+    type CreatedPool = ();
+
+    type DistributedLiquidity = ();
+
+    fn virtual_pool(&self) -> &AccountLoader<'info, VirtualPool> {
+        &self.virtual_pool
+    }
+
+    fn config(&self) -> &AccountLoader<'info, PoolConfig> {
+        &self.config
+    }
+
+    fn pool_authority(&self) -> AccountInfo<'info> {
+        self.pool_authority.to_account_info()
+    }
+
+    fn base_mint(&self) -> AccountInfo<'info> {
+        self.base_mint.to_account_info()
+    }
+
+    fn base_vault(&self) -> AccountInfo<'info> {
+        self.base_vault.to_account_info()
+    }
+
+    fn token_base_program(&self) -> AccountInfo<'info> {
+        self.token_base_program.to_account_info()
+    }
+
+    fn validate(
+        &self,
+        _config: &PoolConfig,
+        _remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let config_extension = PoolConfigExtension::load(&self.config)?;
+        require!(
+            self.migration_program.key() == config_extension.migration_program
+                && self.migration_program.executable,
+            PoolError::InvalidMigrationProgram
+        );
+        let (migration_authority, _bump) = Pubkey::find_program_address(
+            &[
+                EXTERNAL_MIGRATION_AUTHORITY_PREFIX,
+                self.virtual_pool.key().as_ref(),
+            ],
+            &config_extension.migration_program,
+        );
+        require!(
+            self.migration_authority.key() == migration_authority,
+            PoolError::InvalidAccount
+        );
+        Ok(())
+    }
+
+    fn compute_amounts(
+        &self,
+        config: &PoolConfig,
+        _virtual_pool: &VirtualPool,
+    ) -> Result<MigrationDeposit> {
+        let MigrationAmount { quote_amount, .. } =
+            config.get_migration_quote_amount_for_config()?;
+        Ok(MigrationDeposit {
+            base_amount: config.migration_base_threshold,
+            quote_amount,
+        })
+    }
+
+    fn create_pool(
+        &self,
+        _config: &PoolConfig,
+        _virtual_pool: &VirtualPool,
+        deposit: &MigrationDeposit,
+        _remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        // non-transferable base mint is transferable from migration, base token account of migration authority
+        // created before is frozen
+        if lift_base_transfer_restriction(
+            self.pool_authority.to_account_info(),
+            self.base_mint.to_account_info(),
+            self.token_base_program.to_account_info(),
+        )? {
+            thaw_base_token_account(
+                self.pool_authority.to_account_info(),
+                self.base_mint.to_account_info(),
+                self.base_token_account.to_account_info(),
+                self.token_base_program.to_account_info(),
+            )?;
+        }
+
+        let base_vault_amount_before = self.base_vault.amount;
+        let quote_vault_amount_before = self.quote_vault.amount;
+        let base_token_amount_before = self.base_token_account.amount;
+        let quote_token_amount_before = self.quote_token_account.amount;
+
+        // the external program creates its pool after the virtual pool is marked as migrated
+        msg!("transfer migrated token to migration authority");
+        transfer_from_pool(
+            self.pool_authority.to_account_info(),
+            &self.base_mint,
+            &self.base_vault,
+            &self.base_token_account,
+            &self.token_base_program,
+            deposit.base_amount,
+            const_pda::pool_authority::BUMP,
+        )?;
+        transfer_from_pool(
+            self.pool_authority.to_account_info(),
+            &self.quote_mint,
+            &self.quote_vault,
+            &self.quote_token_account,
+            &self.token_quote_program,
+            deposit.quote_amount,
+            const_pda::pool_authority::BUMP,
+        )?;
+
+        validate_deposit_amount(
+            &self.base_mint.to_account_info(),
+            &self.base_vault.to_account_info(),
+            &self.base_token_account.to_account_info(),
+            base_vault_amount_before,
+            base_token_amount_before,
+            deposit.base_amount,
+        )?;
+        validate_deposit_amount(
+            &self.quote_mint.to_account_info(),
+            &self.quote_vault.to_account_info(),
+            &self.quote_token_account.to_account_info(),
+            quote_vault_amount_before,
+            quote_token_amount_before,
+            deposit.quote_amount,
+        )?;
+        Ok(())
+    }
+
+    fn distribute_liquidity(
+        &self,
+        _config: &PoolConfig,
+        _virtual_pool: &VirtualPool,
+        _deposit: &MigrationDeposit,
+        _created_pool: (),
+    ) -> Result<()> {
+        // lp percentages are passed to the external program, which distributes its liquidity
+        Ok(())
+    }
+}
+
+pub fn handle_migrate_external_program<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MigrateExternalProgramCtx<'info>>,
+) -> Result<()> {
+    let MigrationResult {
+        deposit,
+        burned_base_amount,
+        ..
+    } = process_migration(ctx.accounts, ctx.remaining_accounts)?;
+
+    let params = {
+        let config = ctx.accounts.config.load()?;
+        let virtual_pool = ctx.accounts.virtual_pool.load()?;
+        let MigrationAmount { quote_amount, .. } =
+            config.get_migration_quote_amount_for_config()?;
+        ExternalMigrationParameters {
+            virtual_pool: ctx.accounts.virtual_pool.key(),
+            config: ctx.accounts.config.key(),
+            base_amount: config.migration_base_threshold,
+            quote_amount,
+            sqrt_price: virtual_pool.sqrt_price,
+            partner: config.fee_claimer,
+            creator: virtual_pool.creator,
+            partner_lp_percentage: config.partner_lp_percentage,
+            partner_locked_lp_percentage: config.partner_locked_lp_percentage,
+            creator_lp_percentage: config.creator_lp_percentage,
+            creator_locked_lp_percentage: config.creator_locked_lp_percentage,
+        }
+    };
+
+    msg!("invoke migration program");
+    ctx.accounts.invoke_migration_program(
+        &params,
+        ctx.bumps.migration_signer,
+        ctx.remaining_accounts,
+    )?;
+
+    emit_cpi!(EvtMigrateExternalProgram {
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+        migration_program: ctx.accounts.migration_program.key(),
+        migration_authority: ctx.accounts.migration_authority.key(),
+        deposited_base_amount: deposit.base_amount,
+        deposited_quote_amount: deposit.quote_amount,
+        burned_base_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub mod migrate_external_program;
+pub use migrate_external_program::*;
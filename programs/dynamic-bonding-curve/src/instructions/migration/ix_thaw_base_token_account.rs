@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    const_pda,
+    state::{MigrationProgress, VirtualPool},
+    token::{is_base_transfer_restricted, thaw_base_token_account},
+    PoolError,
+};
+
+/// Accounts to thaw a base token account created while the base mint was non-transferable
+#[derive(Accounts)]
+pub struct ThawBaseTokenAccountCtx<'info> {
+    /// CHECK: pool authority
+    #[account(
+        address = const_pda::pool_authority::ID
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(has_one = base_mint)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    /// The frozen base token account
+    #[account(mut, token::mint = base_mint, token::token_program = token_base_program)]
+    pub base_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of base token
+    #[account(
+        constraint = base_mint.freeze_authority == Some(const_pda::pool_authority::ID).into() @ PoolError::InvalidAccount
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token base program
+    pub token_base_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_thaw_base_token_account(ctx: Context<ThawBaseTokenAccountCtx>) -> Result<()> {
+    let virtual_pool = ctx.accounts.virtual_pool.load()?;
+    require!(
+        virtual_pool.get_migration_progress()? == MigrationProgress::CreatedPool,
+        PoolError::NotPermitToDoThisAction
+    );
+
+    // migration lifts the restriction of base mint
+    require!(
+        !is_base_transfer_restricted(&ctx.accounts.base_mint.to_account_info())?,
+        PoolError::NotPermitToDoThisAction
+    );
+
+    let thawed = thaw_base_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.base_token_account.to_account_info(),
+        ctx.accounts.token_base_program.to_account_info(),
+    )?;
+    require!(thawed, PoolError::NotPermitToDoThisAction);
+
+    Ok(())
+}
@@ -4,7 +4,10 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 use crate::{
     const_pda,
-    state::{MigrationFeeDistribution, PoolConfig, VirtualPool, CREATOR_MASK, PARTNER_MASK},
+    state::{
+        MigrationFeeDistribution, PoolConfig, PoolConfigExtension, VirtualPool, CREATOR_MASK,
+        PARTNER_MASK,
+    },
     token::transfer_from_pool,
     EvtWithdrawMigrationFee, PoolError,
 };
@@ -79,7 +82,9 @@ pub fn handle_withdraw_migration_fee(
     let MigrationFeeDistribution {
         creator_migration_fee,
         partner_migration_fee,
-    } = config.get_migration_fee_distribution()?;
+    } = config.get_migration_fee_distribution(
+        PoolConfigExtension::load(&ctx.accounts.config)?.migration_crank_reward,
+    )?;
 
     let sender_flag = SenderFlag::try_from(flag).map_err(|_| PoolError::TypeCastFailed)?;
     let fee = if sender_flag == SenderFlag::Partner {
@@ -7,6 +7,7 @@ use crate::{
 };
 use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MigrateMeteoraDammClaimLpTokenCtx<'info> {
     pub virtual_pool: AccountLoader<'info, VirtualPool>,
@@ -94,5 +95,14 @@ pub fn handle_migrate_meteora_damm_claim_lp_token<'info>(
     };
 
     ctx.accounts
-        .transfer(const_pda::pool_authority::BUMP, lp_to_claim)
+        .transfer(const_pda::pool_authority::BUMP, lp_to_claim)?;
+
+    emit_cpi!(EvtMeteoraDammClaimLpToken {
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+        lp_mint: ctx.accounts.lp_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        amount: lp_to_claim,
+    });
+
+    Ok(())
 }
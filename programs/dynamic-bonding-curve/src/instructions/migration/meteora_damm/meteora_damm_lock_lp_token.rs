@@ -9,6 +9,7 @@ use anchor_spl::token::{Token, TokenAccount};
 use dynamic_amm::accounts::LockEscrow;
 
 /// create lock escrow must be before that transaction
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MigrateMeteoraDammLockLpTokenCtx<'info> {
     pub virtual_pool: AccountLoader<'info, VirtualPool>,
@@ -127,5 +128,15 @@ pub fn handle_migrate_meteora_damm_lock_lp_token<'info>(
     };
 
     ctx.accounts
-        .lock(const_pda::pool_authority::BUMP, lp_to_lock)
+        .lock(const_pda::pool_authority::BUMP, lp_to_lock)?;
+
+    emit_cpi!(EvtMeteoraDammLockLpToken {
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+        lp_mint: ctx.accounts.lp_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        lock_escrow: ctx.accounts.lock_escrow.key(),
+        amount: lp_to_lock,
+    });
+
+    Ok(())
 }
@@ -1,17 +1,19 @@
-use anchor_lang::solana_program::{program::invoke, system_instruction};
-use anchor_spl::token::{Burn, Token, TokenAccount};
+use anchor_spl::token::{accessor, Mint, Token, TokenAccount};
 
 use crate::{
     const_pda,
+    constants::account_space,
     params::fee_parameters::to_bps,
+    rent::{fund_pool_authority, get_rent_exempt_lamports, refund_pool_authority},
     safe_math::SafeMath,
     state::{
-        MigrationAmount, MigrationFeeOption, MigrationOption, MigrationProgress, PoolConfig,
-        VirtualPool,
+        LiquidityDistributionU64, MigrationAmount, MigrationFeeOption, MigrationOption, PoolConfig,
+        PoolConfigExtension, VirtualPool,
     },
     *,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MigrateMeteoraDammCtx<'info> {
     /// virtual pool
@@ -115,17 +117,22 @@ pub struct MigrateMeteoraDammCtx<'info> {
     pub associated_token_program: UncheckedAccount<'info>,
     /// System program.
     pub system_program: Program<'info, System>,
+    /// quote token account receiving the migration crank reward, required when config sets a reward
+    #[account(mut, token::mint = token_b_mint)]
+    pub crank_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
 }
 
 impl<'info> MigrateMeteoraDammCtx<'info> {
     fn validate_config_key(&self, migration_fee_option: u8) -> Result<()> {
+        let migration_base_fee_bps =
+            PoolConfigExtension::load(&self.config)?.migration_base_fee_bps;
         let migration_fee_option = MigrationFeeOption::try_from(migration_fee_option)
             .map_err(|_| PoolError::InvalidMigrationFeeOption)?;
         let base_fee_bps = to_bps(
             self.damm_config.pool_fees.trade_fee_numerator.into(),
             self.damm_config.pool_fees.trade_fee_denominator.into(),
         )?;
-        migration_fee_option.validate_base_fee(base_fee_bps)?;
+        migration_fee_option.validate_base_fee(base_fee_bps, migration_base_fee_bps)?;
         require!(
             self.damm_config.pool_creator_authority == self.pool_authority.key(),
             PoolError::InvalidConfigAccount
@@ -145,7 +152,7 @@ impl<'info> MigrateMeteoraDammCtx<'info> {
         Ok(())
     }
 
-    fn create_pool(
+    fn create_damm_pool(
         &self,
         initial_base_amount: u64,
         initial_quote_amount: u64,
@@ -153,22 +160,24 @@ impl<'info> MigrateMeteoraDammCtx<'info> {
     ) -> Result<()> {
         let pool_authority_seeds = pool_authority_seeds!(bump);
 
-        // Send some lamport to pool authority to pay rent fee?
-        msg!("transfer lamport to pool_authority");
-        invoke(
-            &system_instruction::transfer(
-                &self.payer.key(),
-                &self.pool_authority.key(),
-                50_000_000, // TODO calculate correct lamport here
-            ),
-            &[
-                self.payer.to_account_info(),
-                self.pool_authority.to_account_info(),
-                self.system_program.to_account_info(),
-            ],
+        // pool authority pays rent of pool, lp mint and its metadata, vault lp, pool authority lp and protocol fee accounts
+        let lamports = get_rent_exempt_lamports(&[
+            account_space::METEORA_DAMM_POOL,
+            Mint::LEN,
+            account_space::METAPLEX_METADATA,
+            TokenAccount::LEN,
+            TokenAccount::LEN,
+            TokenAccount::LEN,
+            TokenAccount::LEN,
+            TokenAccount::LEN,
+        ])?;
+        let lamports_before = fund_pool_authority(
+            self.payer.to_account_info(),
+            self.pool_authority.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports,
         )?;
         // Vault authority create pool
-        msg!("create pool");
         dynamic_amm::cpi::initialize_permissionless_constant_product_pool_with_config2(
             CpiContext::new_with_signer(
                 self.amm_program.to_account_info(),
@@ -207,76 +216,157 @@ impl<'info> MigrateMeteoraDammCtx<'info> {
             None,
         )?;
 
-        Ok(())
+        refund_pool_authority(
+            self.pool_authority.to_account_info(),
+            self.payer.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports_before,
+        )
     }
 }
 
-pub fn handle_migrate_meteora_damm<'info>(
-    ctx: Context<'_, '_, '_, 'info, MigrateMeteoraDammCtx<'info>>,
-) -> Result<()> {
-    let config = ctx.accounts.config.load()?;
-    ctx.accounts
-        .validate_config_key(config.migration_fee_option)?;
-
-    let mut virtual_pool = ctx.accounts.virtual_pool.load_mut()?;
-    require!(
-        virtual_pool.get_migration_progress()? == MigrationProgress::LockedVesting,
-        PoolError::NotPermitToDoThisAction
-    );
-
-    let mut migration_metadata = ctx.accounts.migration_metadata.load_mut()?;
-
-    require!(
-        virtual_pool.is_curve_complete(config.migration_quote_threshold),
-        PoolError::PoolIsIncompleted
-    );
-
-    let migration_option = MigrationOption::try_from(config.migration_option)
-        .map_err(|_| PoolError::InvalidMigrationOption)?;
-    require!(
-        migration_option == MigrationOption::MeteoraDamm,
-        PoolError::InvalidMigrationOption
-    );
-    let base_reserve = config.migration_base_threshold;
-    let MigrationAmount { quote_amount, .. } = config.get_migration_quote_amount_for_config()?;
-
-    ctx.accounts
-        .create_pool(base_reserve, quote_amount, const_pda::pool_authority::BUMP)?;
-
-    virtual_pool.update_after_create_pool();
-
-    // burn the rest of token in pool authority after migrated amount and fee
-    ctx.accounts.base_vault.reload()?;
-    let left_base_token = ctx
-        .accounts
-        .base_vault
-        .amount
-        .safe_sub(virtual_pool.get_protocol_and_trading_base_fee()?)?;
-
-    let burnable_amount = config.get_burnable_amount_post_migration(left_base_token)?;
-    if burnable_amount > 0 {
-        let seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
-        anchor_spl::token::burn(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.token_a_mint.to_account_info(),
-                    from: ctx.accounts.base_vault.to_account_info(),
-                    authority: ctx.accounts.pool_authority.to_account_info(),
-                },
-                &[&seeds[..]],
-            ),
-            burnable_amount,
+impl<'info> MigrationAdapter<'info> for MigrateMeteoraDammCtx<'info> {
+    const MIGRATION_OPTION: MigrationOption = MigrationOption::MeteoraDamm;
+
+    type CreatedPool = ();
+
+    type DistributedLiquidity = LiquidityDistributionU64;
+
+    fn virtual_pool(&self) -> &AccountLoader<'info, VirtualPool> {
+        &self.virtual_pool
+    }
+
+    fn config(&self) -> &AccountLoader<'info, PoolConfig> {
+        &self.config
+    }
+
+    fn pool_authority(&self) -> AccountInfo<'info> {
+        self.pool_authority.to_account_info()
+    }
+
+    fn base_mint(&self) -> AccountInfo<'info> {
+        self.token_a_mint.to_account_info()
+    }
+
+    fn base_vault(&self) -> AccountInfo<'info> {
+        self.base_vault.to_account_info()
+    }
+
+    fn token_base_program(&self) -> AccountInfo<'info> {
+        self.token_program.to_account_info()
+    }
+
+    fn validate(
+        &self,
+        config: &PoolConfig,
+        _remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        self.validate_config_key(config.migration_fee_option)
+    }
+
+    fn compute_amounts(
+        &self,
+        config: &PoolConfig,
+        _virtual_pool: &VirtualPool,
+    ) -> Result<MigrationDeposit> {
+        let MigrationAmount { quote_amount, .. } =
+            config.get_migration_quote_amount_for_config()?;
+        Ok(MigrationDeposit {
+            base_amount: config.migration_base_threshold,
+            quote_amount,
+        })
+    }
+
+    fn create_pool(
+        &self,
+        _config: &PoolConfig,
+        virtual_pool: &VirtualPool,
+        deposit: &MigrationDeposit,
+        _remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        // constant product pool is priced by the deposited amounts
+        let migration_price_tolerance_bps =
+            PoolConfigExtension::load(&self.config)?.get_migration_price_tolerance_bps();
+        if let Some(migration_price_tolerance_bps) = migration_price_tolerance_bps {
+            validate_migration_price(
+                get_price_from_amounts(deposit.base_amount, deposit.quote_amount)?,
+                virtual_pool.sqrt_price,
+                migration_price_tolerance_bps,
+            )?;
+        }
+
+        let initial_base_vault_amount = self.base_vault.amount;
+        let initial_quote_vault_amount = self.quote_vault.amount;
+        self.create_damm_pool(
+            deposit.base_amount,
+            deposit.quote_amount,
+            const_pda::pool_authority::BUMP,
         )?;
+
+        validate_migration_deposit_amount(
+            initial_base_vault_amount
+                .safe_sub(accessor::amount(&self.base_vault.to_account_info())?)?,
+            deposit.base_amount,
+            migration_price_tolerance_bps,
+        )?;
+        validate_migration_deposit_amount(
+            initial_quote_vault_amount
+                .safe_sub(accessor::amount(&self.quote_vault.to_account_info())?)?,
+            deposit.quote_amount,
+            migration_price_tolerance_bps,
+        )
     }
 
-    let lp_minted_amount = anchor_spl::token::accessor::amount(&ctx.accounts.virtual_pool_lp)?;
+    fn distribute_liquidity(
+        &self,
+        config: &PoolConfig,
+        _virtual_pool: &VirtualPool,
+        _deposit: &MigrationDeposit,
+        _created_pool: (),
+    ) -> Result<LiquidityDistributionU64> {
+        // lp is held by pool authority, partner and creator lock or claim their share later
+        let lp_minted_amount = anchor_spl::token::accessor::amount(&self.virtual_pool_lp)?;
+        let lp_distribution = config.get_lp_distribution(lp_minted_amount)?;
+        let mut migration_metadata = self.migration_metadata.load_mut()?;
+        migration_metadata.set_lp_minted(self.lp_mint.key(), &lp_distribution);
+        Ok(lp_distribution)
+    }
 
-    let lp_distribution = config.get_lp_distribution(lp_minted_amount)?;
-    migration_metadata.set_lp_minted(ctx.accounts.lp_mint.key(), &lp_distribution);
-    virtual_pool.set_migration_progress(MigrationProgress::CreatedPool.into());
+    fn pay_crank_reward(&self, amount: u64) -> Result<()> {
+        transfer_crank_reward(
+            self.pool_authority.to_account_info(),
+            self.token_b_mint.to_account_info(),
+            self.quote_vault.to_account_info(),
+            self.crank_reward_token_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            self.token_program.to_account_info(),
+            amount,
+        )
+    }
+}
 
-    // TODO emit event
+pub fn handle_migrate_meteora_damm<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MigrateMeteoraDammCtx<'info>>,
+) -> Result<()> {
+    let MigrationResult {
+        deposit,
+        distributed_liquidity,
+        burned_base_amount,
+    } = process_migration(ctx.accounts, ctx.remaining_accounts)?;
+
+    emit_cpi!(EvtMigrateMeteoraDamm {
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+        damm_pool: ctx.accounts.pool.key(),
+        lp_mint: ctx.accounts.lp_mint.key(),
+        deposited_base_amount: deposit.base_amount,
+        deposited_quote_amount: deposit.quote_amount,
+        partner_lp: distributed_liquidity.partner_lp,
+        partner_locked_lp: distributed_liquidity.partner_locked_lp,
+        creator_lp: distributed_liquidity.creator_lp,
+        creator_locked_lp: distributed_liquidity.creator_locked_lp,
+        burned_base_amount,
+    });
 
     Ok(())
 }
@@ -0,0 +1,123 @@
+use anchor_spl::token::Token;
+
+use crate::{
+    const_pda,
+    state::{MigrationProgress, PoolConfig, VirtualPool},
+    *,
+};
+
+#[derive(Accounts)]
+pub struct DlmmClaimPositionFeeCtx<'info> {
+    #[account(has_one = config)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    /// migration metadata
+    #[account(has_one = virtual_pool, has_one = lb_pair)]
+    pub migration_metadata: AccountLoader<'info, MeteoraDlmmMetadata>,
+
+    /// CHECK: pool authority
+    #[account(address = const_pda::pool_authority::ID)]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: lb pair
+    #[account(mut)]
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: dlmm position of partner or creator, validated against migration metadata
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: validated by dlmm program
+    #[account(mut)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: validated by dlmm program
+    #[account(mut)]
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: validated by dlmm program
+    #[account(mut)]
+    pub reserve_x: UncheckedAccount<'info>,
+
+    /// CHECK: validated by dlmm program
+    #[account(mut)]
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: receiver token account of base token
+    #[account(mut)]
+    pub user_token_x: UncheckedAccount<'info>,
+
+    /// CHECK: receiver token account of quote token
+    #[account(mut)]
+    pub user_token_y: UncheckedAccount<'info>,
+
+    /// CHECK: base token mint
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: quote token mint
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    /// partner for partner position or creator for creator position
+    pub sender: Signer<'info>,
+
+    /// token_program
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK:
+    #[account(address = dlmm::ID)]
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: dlmm event authority
+    pub dlmm_event_authority: UncheckedAccount<'info>,
+}
+
+pub fn handle_dlmm_claim_position_fee(ctx: Context<DlmmClaimPositionFeeCtx>) -> Result<()> {
+    {
+        let virtual_pool = ctx.accounts.virtual_pool.load()?;
+        require!(
+            virtual_pool.get_migration_progress()? == MigrationProgress::CreatedPool,
+            PoolError::NotPermitToDoThisAction
+        );
+
+        let config = ctx.accounts.config.load()?;
+        let migration_metadata = ctx.accounts.migration_metadata.load()?;
+        let position = ctx.accounts.position.key();
+        let sender = ctx.accounts.sender.key();
+        let is_partner_position = position != Pubkey::default()
+            && position == migration_metadata.partner_position
+            && sender == config.fee_claimer;
+        let is_creator_position = position != Pubkey::default()
+            && position == migration_metadata.creator_position
+            && sender == virtual_pool.creator;
+        require!(
+            is_partner_position || is_creator_position,
+            PoolError::NotPermitToDoThisAction
+        );
+    }
+
+    let pool_authority_seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+    dlmm::cpi::claim_fee(CpiContext::new_with_signer(
+        ctx.accounts.dlmm_program.to_account_info(),
+        dlmm::cpi::accounts::ClaimFee {
+            lb_pair: ctx.accounts.lb_pair.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+            bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+            sender: ctx.accounts.pool_authority.to_account_info(),
+            reserve_x: ctx.accounts.reserve_x.to_account_info(),
+            reserve_y: ctx.accounts.reserve_y.to_account_info(),
+            user_token_x: ctx.accounts.user_token_x.to_account_info(),
+            user_token_y: ctx.accounts.user_token_y.to_account_info(),
+            token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+            token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            event_authority: ctx.accounts.dlmm_event_authority.to_account_info(),
+            program: ctx.accounts.dlmm_program.to_account_info(),
+        },
+        &[&pool_authority_seeds[..]],
+    ))?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+/// seed index of the dlmm position holding partner liquidity
+pub const PARTNER_POSITION_INDEX: u8 = 0;
+/// seed index of the dlmm position holding creator liquidity
+pub const CREATOR_POSITION_INDEX: u8 = 1;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct MeteoraDlmmMetadata {
+    /// pool
+    pub virtual_pool: Pubkey,
+    /// partner
+    pub partner: Pubkey,
+    /// dlmm lb pair, set after migration
+    pub lb_pair: Pubkey,
+    /// dlmm position of partner, default if partner has no liquidity
+    pub partner_position: Pubkey,
+    /// dlmm position of creator, default if creator has no liquidity
+    pub creator_position: Pubkey,
+    /// Reserve
+    pub _padding: [u8; 128],
+}
+
+const_assert_eq!(MeteoraDlmmMetadata::INIT_SPACE, 288);
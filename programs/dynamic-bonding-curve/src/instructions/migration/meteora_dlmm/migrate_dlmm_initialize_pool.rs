@@ -0,0 +1,586 @@
+use anchor_spl::{
+    token::{accessor, TokenAccount as SplTokenAccount},
+    token_interface::{TokenAccount, TokenInterface},
+};
+use dlmm::types::{
+    CustomizableParams, LiquidityParameterByStrategy, StrategyParameters, StrategyType,
+};
+use ruint::aliases::U256;
+
+use crate::{
+    const_pda,
+    constants::{account_space, dlmm::MIGRATION_BIN_RANGE, seeds::DLMM_POSITION_PREFIX},
+    math::bin_math::{
+        get_base_factor, get_bin_array_index, get_dlmm_migration_base_token, get_price_from_id,
+    },
+    rent::{fund_pool_authority, get_rent_exempt_lamports, refund_pool_authority},
+    safe_math::SafeMath,
+    state::{MigrationAmount, MigrationOption, PoolConfig, PoolConfigExtension, VirtualPool},
+    u128x128_math::Rounding,
+    utils_math::safe_mul_div_cast_u64,
+    *,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateDlmmCtx<'info> {
+    /// virtual pool
+    #[account(mut, has_one = base_vault, has_one = quote_vault, has_one = config)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    /// migration metadata
+    #[account(mut, has_one = virtual_pool)]
+    pub migration_metadata: AccountLoader<'info, MeteoraDlmmMetadata>,
+
+    /// virtual pool config key
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    /// CHECK: pool authority
+    #[account(
+        mut,
+        address = const_pda::pool_authority::ID,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: lb pair, validated by dlmm program
+    #[account(mut)]
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: lower bin array of migrated liquidity, initialized if empty
+    #[account(mut)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: upper bin array of migrated liquidity, initialized if empty
+    #[account(mut)]
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: dlmm position for partner
+    #[account(
+        mut,
+        seeds = [
+            DLMM_POSITION_PREFIX,
+            virtual_pool.key().as_ref(),
+            &[PARTNER_POSITION_INDEX],
+        ],
+        bump,
+    )]
+    pub partner_position: UncheckedAccount<'info>,
+
+    /// CHECK: dlmm position for creator
+    #[account(
+        mut,
+        seeds = [
+            DLMM_POSITION_PREFIX,
+            virtual_pool.key().as_ref(),
+            &[CREATOR_POSITION_INDEX],
+        ],
+        bump,
+    )]
+    pub creator_position: UncheckedAccount<'info>,
+
+    /// CHECK: dlmm reserve of base token
+    #[account(mut)]
+    pub reserve_x: UncheckedAccount<'info>,
+
+    /// CHECK: dlmm reserve of quote token
+    #[account(mut)]
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: dlmm oracle
+    #[account(mut)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK:
+    #[account(address = dlmm::ID)]
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: base token mint
+    #[account(mut)]
+    pub base_mint: UncheckedAccount<'info>,
+    /// CHECK: quote token mint
+    pub quote_mint: UncheckedAccount<'info>,
+    /// CHECK: base_vault
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::token_program = token_program
+    )]
+    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: quote vault
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::token_program = token_program
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: token_program, dlmm migration only supports spl token
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: dlmm event authority
+    pub dlmm_event_authority: UncheckedAccount<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+    /// CHECK: rent sysvar, required by dlmm position
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> MigrateDlmmCtx<'info> {
+    fn create_lb_pair(
+        &self,
+        active_id: i32,
+        bin_step: u16,
+        base_factor: u16,
+        activation_type: u8,
+        bump: u8,
+    ) -> Result<()> {
+        let pool_authority_seeds = pool_authority_seeds!(bump);
+
+        // pool authority funds lb pair, oracle and reserves, dlmm migration only supports spl token mints
+        let lamports = get_rent_exempt_lamports(&[
+            account_space::DLMM_LB_PAIR,
+            account_space::DLMM_ORACLE,
+            SplTokenAccount::LEN,
+            SplTokenAccount::LEN,
+        ])?;
+        let lamports_before = fund_pool_authority(
+            self.payer.to_account_info(),
+            self.pool_authority.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports,
+        )?;
+
+        dlmm::cpi::initialize_customizable_permissionless_lb_pair(
+            CpiContext::new_with_signer(
+                self.dlmm_program.to_account_info(),
+                dlmm::cpi::accounts::InitializeCustomizablePermissionlessLbPair {
+                    lb_pair: self.lb_pair.to_account_info(),
+                    bin_array_bitmap_extension: None,
+                    token_mint_x: self.base_mint.to_account_info(),
+                    token_mint_y: self.quote_mint.to_account_info(),
+                    reserve_x: self.reserve_x.to_account_info(),
+                    reserve_y: self.reserve_y.to_account_info(),
+                    oracle: self.oracle.to_account_info(),
+                    user_token_x: self.base_vault.to_account_info(),
+                    funder: self.pool_authority.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    user_token_y: self.quote_vault.to_account_info(),
+                    event_authority: self.dlmm_event_authority.to_account_info(),
+                    program: self.dlmm_program.to_account_info(),
+                },
+                &[&pool_authority_seeds[..]],
+            ),
+            CustomizableParams {
+                active_id,
+                bin_step,
+                base_factor,
+                activation_type,
+                has_alpha_vault: false,
+                activation_point: None,
+                creator_pool_on_off_control: false,
+                base_fee_power_factor: 0,
+                padding: [0; 62],
+            },
+        )?;
+
+        refund_pool_authority(
+            self.pool_authority.to_account_info(),
+            self.payer.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports_before,
+        )
+    }
+
+    fn initialize_bin_array_if_needed(
+        &self,
+        bin_array: &UncheckedAccount<'info>,
+        index: i64,
+    ) -> Result<()> {
+        if !bin_array.data_is_empty() {
+            return Ok(());
+        }
+        dlmm::cpi::initialize_bin_array(
+            CpiContext::new(
+                self.dlmm_program.to_account_info(),
+                dlmm::cpi::accounts::InitializeBinArray {
+                    lb_pair: self.lb_pair.to_account_info(),
+                    bin_array: bin_array.to_account_info(),
+                    funder: self.payer.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                },
+            ),
+            index,
+        )
+    }
+
+    /// Returns base and quote token taken by the position
+    #[allow(clippy::too_many_arguments)]
+    fn create_position_and_add_liquidity(
+        &self,
+        position: &UncheckedAccount<'info>,
+        position_index: u8,
+        position_bump: u8,
+        active_id: i32,
+        amount_x: u64,
+        amount_y: u64,
+        bump: u8,
+    ) -> Result<(u64, u64)> {
+        let pool_authority_seeds = pool_authority_seeds!(bump);
+        let virtual_pool = self.virtual_pool.key();
+        let position_seeds = &[
+            DLMM_POSITION_PREFIX,
+            virtual_pool.as_ref(),
+            &[position_index],
+            &[position_bump],
+        ];
+
+        let lower_bin_id = active_id.safe_sub(MIGRATION_BIN_RANGE)?;
+        let upper_bin_id = active_id.safe_add(MIGRATION_BIN_RANGE)?;
+
+        msg!("create position");
+        dlmm::cpi::initialize_position(
+            CpiContext::new_with_signer(
+                self.dlmm_program.to_account_info(),
+                dlmm::cpi::accounts::InitializePosition {
+                    payer: self.payer.to_account_info(),
+                    position: position.to_account_info(),
+                    lb_pair: self.lb_pair.to_account_info(),
+                    owner: self.pool_authority.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                    event_authority: self.dlmm_event_authority.to_account_info(),
+                    program: self.dlmm_program.to_account_info(),
+                },
+                &[&pool_authority_seeds[..], &position_seeds[..]],
+            ),
+            lower_bin_id,
+            upper_bin_id.safe_sub(lower_bin_id)?.safe_add(1)?,
+        )?;
+
+        let base_vault_amount_before = accessor::amount(&self.base_vault.to_account_info())?;
+        let quote_vault_amount_before = accessor::amount(&self.quote_vault.to_account_info())?;
+
+        msg!("add liquidity");
+        dlmm::cpi::add_liquidity_by_strategy(
+            CpiContext::new_with_signer(
+                self.dlmm_program.to_account_info(),
+                dlmm::cpi::accounts::AddLiquidityByStrategy {
+                    position: position.to_account_info(),
+                    lb_pair: self.lb_pair.to_account_info(),
+                    bin_array_bitmap_extension: None,
+                    user_token_x: self.base_vault.to_account_info(),
+                    user_token_y: self.quote_vault.to_account_info(),
+                    reserve_x: self.reserve_x.to_account_info(),
+                    reserve_y: self.reserve_y.to_account_info(),
+                    token_x_mint: self.base_mint.to_account_info(),
+                    token_y_mint: self.quote_mint.to_account_info(),
+                    bin_array_lower: self.bin_array_lower.to_account_info(),
+                    bin_array_upper: self.bin_array_upper.to_account_info(),
+                    sender: self.pool_authority.to_account_info(),
+                    token_x_program: self.token_program.to_account_info(),
+                    token_y_program: self.token_program.to_account_info(),
+                    event_authority: self.dlmm_event_authority.to_account_info(),
+                    program: self.dlmm_program.to_account_info(),
+                },
+                &[&pool_authority_seeds[..]],
+            ),
+            LiquidityParameterByStrategy {
+                amount_x,
+                amount_y,
+                active_id,
+                max_active_bin_slippage: 0,
+                strategy_parameters: StrategyParameters {
+                    min_bin_id: lower_bin_id,
+                    max_bin_id: upper_bin_id,
+                    strategy_type: StrategyType::SpotBalanced,
+                    parameteres: [0; 64],
+                },
+            },
+        )?;
+
+        let deposited_base_amount = base_vault_amount_before
+            .safe_sub(accessor::amount(&self.base_vault.to_account_info())?)?;
+        let deposited_quote_amount = quote_vault_amount_before
+            .safe_sub(accessor::amount(&self.quote_vault.to_account_info())?)?;
+        Ok((deposited_base_amount, deposited_quote_amount))
+    }
+}
+
+/// Token taken by partner and creator positions, quote token not taken by the positions stays in quote vault
+#[derive(Debug, Clone, Copy)]
+pub struct DlmmDistributedLiquidity {
+    pub partner_position_base_amount: u64,
+    pub partner_position_quote_amount: u64,
+    pub creator_position_base_amount: u64,
+    pub creator_position_quote_amount: u64,
+    pub leftover_quote_amount: u64,
+}
+
+/// Dlmm migration adapter, position accounts are signed with their bumps
+pub struct DlmmMigration<'a, 'info> {
+    accounts: &'a MigrateDlmmCtx<'info>,
+    bumps: &'a MigrateDlmmCtxBumps,
+}
+
+impl<'info> MigrationAdapter<'info> for DlmmMigration<'_, 'info> {
+    const MIGRATION_OPTION: MigrationOption = MigrationOption::Dlmm;
+
+    type CreatedPool = ();
+
+    type DistributedLiquidity = DlmmDistributedLiquidity;
+
+    fn virtual_pool(&self) -> &AccountLoader<'info, VirtualPool> {
+        &self.accounts.virtual_pool
+    }
+
+    fn config(&self) -> &AccountLoader<'info, PoolConfig> {
+        &self.accounts.config
+    }
+
+    fn pool_authority(&self) -> AccountInfo<'info> {
+        self.accounts.pool_authority.to_account_info()
+    }
+
+    fn base_mint(&self) -> AccountInfo<'info> {
+        self.accounts.base_mint.to_account_info()
+    }
+
+    fn base_vault(&self) -> AccountInfo<'info> {
+        self.accounts.base_vault.to_account_info()
+    }
+
+    fn token_base_program(&self) -> AccountInfo<'info> {
+        self.accounts.token_program.to_account_info()
+    }
+
+    fn compute_amounts(
+        &self,
+        config: &PoolConfig,
+        virtual_pool: &VirtualPool,
+    ) -> Result<MigrationDeposit> {
+        let config_extension = PoolConfigExtension::load(&self.accounts.config)?;
+        let MigrationAmount { quote_amount, .. } =
+            config.get_migration_quote_amount_for_config()?;
+        let excluded_fee_base_reserve = self
+            .accounts
+            .base_vault
+            .amount
+            .safe_sub(virtual_pool.get_protocol_and_trading_base_fee()?)?;
+        // base token worth the quote amount at the active bin
+        let base_amount = get_dlmm_migration_base_token(
+            quote_amount,
+            config_extension.dlmm_active_id,
+            config_extension.dlmm_bin_step,
+        )?
+        .min(excluded_fee_base_reserve);
+        Ok(MigrationDeposit {
+            base_amount,
+            quote_amount,
+        })
+    }
+
+    fn create_pool(
+        &self,
+        config: &PoolConfig,
+        virtual_pool: &VirtualPool,
+        _deposit: &MigrationDeposit,
+        _remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let config_extension = PoolConfigExtension::load(&self.accounts.config)?;
+        let active_id = config_extension.dlmm_active_id;
+        let bin_step = config_extension.dlmm_bin_step;
+        // lb pair opens at the active bin, price of the bin is in Q64.64
+        if let Some(migration_price_tolerance_bps) =
+            config_extension.get_migration_price_tolerance_bps()
+        {
+            validate_migration_price(
+                U256::from(get_price_from_id(active_id, bin_step)?).safe_shl(64)?,
+                virtual_pool.sqrt_price,
+                migration_price_tolerance_bps,
+            )?;
+        }
+        let base_factor = get_base_factor(
+            config_extension.get_migration_base_fee_bps(config.migration_fee_option)?,
+            bin_step,
+        )?;
+
+        msg!("create lb pair");
+        self.accounts.create_lb_pair(
+            active_id,
+            bin_step,
+            base_factor,
+            config.activation_type,
+            const_pda::pool_authority::BUMP,
+        )?;
+
+        msg!("initialize bin arrays");
+        let lower_bin_array_index = get_bin_array_index(active_id.safe_sub(MIGRATION_BIN_RANGE)?);
+        let upper_bin_array_index = get_bin_array_index(active_id.safe_add(MIGRATION_BIN_RANGE)?);
+        self.accounts.initialize_bin_array_if_needed(
+            &self.accounts.bin_array_lower,
+            lower_bin_array_index,
+        )?;
+        if upper_bin_array_index != lower_bin_array_index {
+            self.accounts.initialize_bin_array_if_needed(
+                &self.accounts.bin_array_upper,
+                upper_bin_array_index,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn distribute_liquidity(
+        &self,
+        config: &PoolConfig,
+        _virtual_pool: &VirtualPool,
+        deposit: &MigrationDeposit,
+        _created_pool: (),
+    ) -> Result<DlmmDistributedLiquidity> {
+        let config_extension = PoolConfigExtension::load(&self.accounts.config)?;
+        let active_id = config_extension.dlmm_active_id;
+        let migration_price_tolerance_bps = config_extension.get_migration_price_tolerance_bps();
+
+        // all migrated liquidity is locked in positions owned by pool authority, partner and creator can only claim fee
+        let partner_base_amount = safe_mul_div_cast_u64(
+            deposit.base_amount,
+            config.partner_locked_lp_percentage.into(),
+            100,
+            Rounding::Down,
+        )?;
+        let partner_quote_amount = safe_mul_div_cast_u64(
+            deposit.quote_amount,
+            config.partner_locked_lp_percentage.into(),
+            100,
+            Rounding::Down,
+        )?;
+
+        let mut migration_metadata = self.accounts.migration_metadata.load_mut()?;
+        migration_metadata.lb_pair = self.accounts.lb_pair.key();
+
+        let mut partner_deposited_base_amount = 0;
+        let mut partner_deposited_quote_amount = 0;
+        if config.partner_locked_lp_percentage > 0 {
+            msg!("create partner position");
+            (
+                partner_deposited_base_amount,
+                partner_deposited_quote_amount,
+            ) = self.accounts.create_position_and_add_liquidity(
+                &self.accounts.partner_position,
+                PARTNER_POSITION_INDEX,
+                self.bumps.partner_position,
+                active_id,
+                partner_base_amount,
+                partner_quote_amount,
+                const_pda::pool_authority::BUMP,
+            )?;
+            validate_migration_deposit_amount(
+                partner_deposited_base_amount,
+                partner_base_amount,
+                None,
+            )?;
+            validate_migration_deposit_amount(
+                partner_deposited_quote_amount,
+                partner_quote_amount,
+                migration_price_tolerance_bps,
+            )?;
+            migration_metadata.partner_position = self.accounts.partner_position.key();
+        }
+
+        // creator position takes all token not taken by partner position, so only its rounding is left over
+        let creator_base_amount = deposit
+            .base_amount
+            .safe_sub(partner_deposited_base_amount)?;
+        let creator_quote_amount = deposit
+            .quote_amount
+            .safe_sub(partner_deposited_quote_amount)?;
+
+        let mut creator_deposited_base_amount = 0;
+        let mut creator_deposited_quote_amount = 0;
+        if config.creator_locked_lp_percentage > 0 {
+            msg!("create creator position");
+            (
+                creator_deposited_base_amount,
+                creator_deposited_quote_amount,
+            ) = self.accounts.create_position_and_add_liquidity(
+                &self.accounts.creator_position,
+                CREATOR_POSITION_INDEX,
+                self.bumps.creator_position,
+                active_id,
+                creator_base_amount,
+                creator_quote_amount,
+                const_pda::pool_authority::BUMP,
+            )?;
+            validate_migration_deposit_amount(
+                creator_deposited_base_amount,
+                creator_base_amount,
+                None,
+            )?;
+            validate_migration_deposit_amount(
+                creator_deposited_quote_amount,
+                creator_quote_amount,
+                migration_price_tolerance_bps,
+            )?;
+            migration_metadata.creator_position = self.accounts.creator_position.key();
+        }
+
+        // base token left is burned
+        Ok(DlmmDistributedLiquidity {
+            partner_position_base_amount: partner_deposited_base_amount,
+            partner_position_quote_amount: partner_deposited_quote_amount,
+            creator_position_base_amount: creator_deposited_base_amount,
+            creator_position_quote_amount: creator_deposited_quote_amount,
+            leftover_quote_amount: deposit
+                .quote_amount
+                .safe_sub(partner_deposited_quote_amount)?
+                .safe_sub(creator_deposited_quote_amount)?,
+        })
+    }
+}
+
+pub fn handle_migrate_dlmm<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MigrateDlmmCtx<'info>>,
+) -> Result<()> {
+    let MigrationResult {
+        distributed_liquidity,
+        burned_base_amount,
+        ..
+    } = process_migration(
+        &DlmmMigration {
+            accounts: ctx.accounts,
+            bumps: &ctx.bumps,
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    let (partner_position, creator_position) = {
+        let migration_metadata = ctx.accounts.migration_metadata.load()?;
+        (
+            migration_metadata.partner_position,
+            migration_metadata.creator_position,
+        )
+    };
+    emit_cpi!(EvtMigrateDlmm {
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+        lb_pair: ctx.accounts.lb_pair.key(),
+        partner_position,
+        partner_position_base_amount: distributed_liquidity.partner_position_base_amount,
+        partner_position_quote_amount: distributed_liquidity.partner_position_quote_amount,
+        creator_position,
+        creator_position_base_amount: distributed_liquidity.creator_position_base_amount,
+        creator_position_quote_amount: distributed_liquidity.creator_position_quote_amount,
+        deposited_base_amount: distributed_liquidity
+            .partner_position_base_amount
+            .safe_add(distributed_liquidity.creator_position_base_amount)?,
+        deposited_quote_amount: distributed_liquidity
+            .partner_position_quote_amount
+            .safe_add(distributed_liquidity.creator_position_quote_amount)?,
+        leftover_quote_amount: distributed_liquidity.leftover_quote_amount,
+        burned_base_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::seeds::DLMM_METADATA_PREFIX;
+use crate::state::MigrationOption;
+use crate::state::PoolConfig;
+use crate::state::VirtualPool;
+use crate::EvtCreateDlmmMigrationMetadata;
+use crate::PoolError;
+
+use super::MeteoraDlmmMetadata;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrationDlmmCreateMetadataCtx<'info> {
+    #[account(has_one=config)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            DLMM_METADATA_PREFIX,
+            virtual_pool.key().as_ref(),
+        ],
+        bump,
+        space = 8 + MeteoraDlmmMetadata::INIT_SPACE
+    )]
+    pub migration_metadata: AccountLoader<'info, MeteoraDlmmMetadata>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_migration_dlmm_create_metadata(
+    ctx: Context<MigrationDlmmCreateMetadataCtx>,
+) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    let migration_option = MigrationOption::try_from(config.migration_option)
+        .map_err(|_| PoolError::InvalidMigrationOption)?;
+    require!(
+        migration_option == MigrationOption::Dlmm,
+        PoolError::InvalidMigrationOption
+    );
+    let mut migration_metadata = ctx.accounts.migration_metadata.load_init()?;
+    migration_metadata.virtual_pool = ctx.accounts.virtual_pool.key();
+    migration_metadata.partner = config.fee_claimer;
+
+    emit_cpi!(EvtCreateDlmmMigrationMetadata {
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+    });
+
+    Ok(())
+}
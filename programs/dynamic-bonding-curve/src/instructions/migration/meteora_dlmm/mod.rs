@@ -0,0 +1,8 @@
+pub mod dlmm_metadata_state;
+pub use dlmm_metadata_state::*;
+pub mod migration_dlmm_create_metadata;
+pub use migration_dlmm_create_metadata::*;
+pub mod migrate_dlmm_initialize_pool;
+pub use migrate_dlmm_initialize_pool::*;
+pub mod dlmm_claim_position_fee;
+pub use dlmm_claim_position_fee::*;
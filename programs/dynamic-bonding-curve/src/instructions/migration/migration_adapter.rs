@@ -0,0 +1,284 @@
+use anchor_spl::{
+    token::accessor,
+    token_interface::{self, Mint, TransferChecked},
+};
+use ruint::aliases::U256;
+
+use crate::{
+    const_pda,
+    constants::BASIS_POINT_MAX,
+    safe_math::SafeMath,
+    state::{
+        MigrationAmount, MigrationOption, MigrationProgress, PoolConfig, PoolConfigExtension,
+        VirtualPool,
+    },
+    u128x128_math::Rounding,
+    utils_math::safe_mul_div_cast_u64,
+    *,
+};
+
+/// Token amounts deposited to the target amm at migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationDeposit {
+    /// base token for liquidity, excluding protocol and trading base fee
+    pub base_amount: u64,
+    /// quote token for liquidity, excluding migration fee
+    pub quote_amount: u64,
+}
+
+/// Result of [process_migration], emitted by migrate instructions
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationResult<T> {
+    pub deposit: MigrationDeposit,
+    /// lp or liquidity distributed to partner and creator
+    pub distributed_liquidity: T,
+    /// base token burned after migration
+    pub burned_base_amount: u64,
+}
+
+/// A migration target amm.
+///
+/// Migrate instructions implement the adapter on their accounts and call [process_migration], which
+/// validates the virtual pool can migrate, runs the steps of the adapter in order, then burns leftover
+/// base token and marks the pool as migrated. Adding a new amm only requires a new adapter module.
+pub trait MigrationAdapter<'info> {
+    /// migration option of configs migrating to the amm
+    const MIGRATION_OPTION: MigrationOption;
+
+    /// state returned by [MigrationAdapter::create_pool] and consumed by [MigrationAdapter::distribute_liquidity]
+    type CreatedPool;
+
+    /// lp or liquidity returned by [MigrationAdapter::distribute_liquidity]
+    type DistributedLiquidity;
+
+    fn virtual_pool(&self) -> &AccountLoader<'info, VirtualPool>;
+
+    fn config(&self) -> &AccountLoader<'info, PoolConfig>;
+
+    fn pool_authority(&self) -> AccountInfo<'info>;
+
+    fn base_mint(&self) -> AccountInfo<'info>;
+
+    fn base_vault(&self) -> AccountInfo<'info>;
+
+    fn token_base_program(&self) -> AccountInfo<'info>;
+
+    /// Validate amm specific accounts
+    fn validate(
+        &self,
+        _config: &PoolConfig,
+        _remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Amounts deposited to the amm, by default the quote migration amount and all base token except fees
+    fn compute_amounts(
+        &self,
+        config: &PoolConfig,
+        virtual_pool: &VirtualPool,
+    ) -> Result<MigrationDeposit> {
+        let MigrationAmount { quote_amount, .. } =
+            config.get_migration_quote_amount_for_config()?;
+        let base_amount = accessor::amount(&self.base_vault())?
+            .safe_sub(virtual_pool.get_protocol_and_trading_base_fee()?)?;
+        Ok(MigrationDeposit {
+            base_amount,
+            quote_amount,
+        })
+    }
+
+    /// Create the amm pool, funded from the virtual pool vaults by pool authority
+    fn create_pool(
+        &self,
+        config: &PoolConfig,
+        virtual_pool: &VirtualPool,
+        deposit: &MigrationDeposit,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<Self::CreatedPool>;
+
+    /// Distribute lp or positions of the amm pool to partner and creator
+    fn distribute_liquidity(
+        &self,
+        config: &PoolConfig,
+        virtual_pool: &VirtualPool,
+        deposit: &MigrationDeposit,
+        created_pool: Self::CreatedPool,
+    ) -> Result<Self::DistributedLiquidity>;
+
+    /// Burn base token left in base vault after migration, excluding fees, returns the burned amount
+    fn finalize(&self, config: &PoolConfig, virtual_pool: &VirtualPool) -> Result<u64> {
+        let left_base_token = accessor::amount(&self.base_vault())?
+            .safe_sub(virtual_pool.get_protocol_and_trading_base_fee()?)?;
+
+        let burnable_amount = config.get_burnable_amount_post_migration(left_base_token)?;
+        if burnable_amount > 0 {
+            let seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+            anchor_spl::token_interface::burn(
+                CpiContext::new_with_signer(
+                    self.token_base_program(),
+                    anchor_spl::token_interface::Burn {
+                        mint: self.base_mint(),
+                        from: self.base_vault(),
+                        authority: self.pool_authority(),
+                    },
+                    &[&seeds[..]],
+                ),
+                burnable_amount,
+            )?;
+        }
+        Ok(burnable_amount)
+    }
+
+    /// Pay the migration crank reward to the account executing the migration, only damm and damm v2 support it
+    fn pay_crank_reward(&self, _amount: u64) -> Result<()> {
+        err!(PoolError::InvalidMigrationCrankReward)
+    }
+}
+
+/// Transfer the migration crank reward from quote vault, the receiver must be passed when config sets a reward
+pub fn transfer_crank_reward<'info>(
+    pool_authority: AccountInfo<'info>,
+    quote_mint: AccountInfo<'info>,
+    quote_vault: AccountInfo<'info>,
+    crank_reward_token_account: Option<AccountInfo<'info>>,
+    token_quote_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let crank_reward_token_account =
+        crank_reward_token_account.ok_or(PoolError::InvalidMigrationCrankReward)?;
+    let decimals = Mint::try_deserialize(&mut &quote_mint.try_borrow_data()?[..])?.decimals;
+    let seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_quote_program,
+            TransferChecked {
+                from: quote_vault,
+                mint: quote_mint,
+                to: crank_reward_token_account,
+                authority: pool_authority,
+            },
+            &[&seeds[..]],
+        ),
+        amount,
+        decimals,
+    )
+}
+
+/// Price in Q128.128 from sqrt price in Q64.64
+pub fn get_price_from_sqrt_price(sqrt_price: u128) -> Result<U256> {
+    Ok(U256::from(sqrt_price).safe_mul(U256::from(sqrt_price))?)
+}
+
+/// Price in Q128.128 of quote per base deposited to a constant product pool
+pub fn get_price_from_amounts(base_amount: u64, quote_amount: u64) -> Result<U256> {
+    require!(base_amount > 0, PoolError::ExceededMigrationSlippage);
+    Ok(U256::from(quote_amount)
+        .safe_shl(128)?
+        .safe_div(U256::from(base_amount))?)
+}
+
+/// Validate the price of the pool created at migration doesn't diverge from the final price of the curve by
+/// more than the tolerance
+pub fn validate_migration_price(
+    price: U256,
+    curve_sqrt_price: u128,
+    tolerance_bps: u16,
+) -> Result<()> {
+    let curve_price = get_price_from_sqrt_price(curve_sqrt_price)?;
+    let price_diff = if price > curve_price {
+        price.safe_sub(curve_price)?
+    } else {
+        curve_price.safe_sub(price)?
+    };
+    require!(
+        price_diff.safe_mul(U256::from(BASIS_POINT_MAX))?
+            <= curve_price.safe_mul(U256::from(tolerance_bps))?,
+        PoolError::ExceededMigrationSlippage
+    );
+    Ok(())
+}
+
+/// Validate token taken by the migrated pool is at most the deposit, and at least the deposit less the tolerance
+/// when migration price is checked
+pub fn validate_migration_deposit_amount(
+    deposited_amount: u64,
+    deposit_amount: u64,
+    tolerance_bps: Option<u16>,
+) -> Result<()> {
+    require!(
+        deposited_amount <= deposit_amount,
+        PoolError::ExceededMigrationSlippage
+    );
+    if let Some(tolerance_bps) = tolerance_bps {
+        let minimum_amount = safe_mul_div_cast_u64(
+            deposit_amount,
+            BASIS_POINT_MAX.safe_sub(tolerance_bps.into())?,
+            BASIS_POINT_MAX,
+            Rounding::Up,
+        )?;
+        require!(
+            deposited_amount >= minimum_amount,
+            PoolError::ExceededMigrationSlippage
+        );
+    }
+    Ok(())
+}
+
+/// Migrate a completed virtual pool with the adapter
+pub fn process_migration<'info, A: MigrationAdapter<'info>>(
+    adapter: &A,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<MigrationResult<A::DistributedLiquidity>> {
+    let config = adapter.config().load()?;
+    let mut virtual_pool = adapter.virtual_pool().load_mut()?;
+
+    require!(
+        virtual_pool.get_migration_progress()? == MigrationProgress::LockedVesting,
+        PoolError::NotPermitToDoThisAction
+    );
+
+    require!(
+        virtual_pool.is_curve_complete(config.migration_quote_threshold),
+        PoolError::PoolIsIncompleted
+    );
+
+    let migration_option = MigrationOption::try_from(config.migration_option)
+        .map_err(|_| PoolError::InvalidMigrationOption)?;
+    require!(
+        migration_option == A::MIGRATION_OPTION,
+        PoolError::InvalidMigrationOption
+    );
+
+    adapter.validate(&config, remaining_accounts)?;
+
+    let deposit = adapter.compute_amounts(&config, &virtual_pool)?;
+
+    msg!("create pool");
+    let created_pool = adapter.create_pool(&config, &virtual_pool, &deposit, remaining_accounts)?;
+
+    msg!("distribute liquidity");
+    let distributed_liquidity =
+        adapter.distribute_liquidity(&config, &virtual_pool, &deposit, created_pool)?;
+
+    virtual_pool.update_after_create_pool();
+
+    // burn the rest of token in pool authority after migrated amount and fee
+    let burned_base_amount = adapter.finalize(&config, &virtual_pool)?;
+
+    virtual_pool.set_migration_progress(MigrationProgress::CreatedPool.into());
+
+    // crank reward is reserved from migration fee, so it is left in quote vault after the pool is created
+    let migration_crank_reward =
+        PoolConfigExtension::load(adapter.config())?.migration_crank_reward;
+    if migration_crank_reward > 0 {
+        msg!("pay migration crank reward");
+        adapter.pay_crank_reward(migration_crank_reward)?;
+    }
+
+    Ok(MigrationResult {
+        deposit,
+        distributed_liquidity,
+        burned_base_amount,
+    })
+}
@@ -1,10 +1,18 @@
+pub mod migration_adapter;
+pub use migration_adapter::*;
 pub mod meteora_damm;
 pub use meteora_damm::*;
 pub mod dynamic_amm_v2;
 pub use dynamic_amm_v2::*;
+pub mod meteora_dlmm;
+pub use meteora_dlmm::*;
+pub mod external_program;
+pub use external_program::*;
 pub mod create_locker;
 pub use create_locker::*;
 pub mod withdraw_leftover;
 pub use withdraw_leftover::*;
+pub mod ix_thaw_base_token_account;
+pub use ix_thaw_base_token_account::*;
 pub mod ix_withdraw_migration_fee;
 pub use ix_withdraw_migration_fee::*;
@@ -10,3 +10,5 @@ pub mod migration;
 pub use migration::*;
 pub mod creator;
 pub use creator::*;
+pub mod refund;
+pub use refund::*;
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::PoolError;
+
+/// Failed check of config parameters
+#[derive(Debug)]
+pub struct ConfigParameterError {
+    /// path of the offending field in `ConfigParameters`, e.g. `curve[2].sqrt_price`
+    pub field: String,
+    pub error: Error,
+}
+
+/// Runs the checks of config parameters. On chain validation stops at the first failed check, off chain
+/// tooling collects every failed check with the offending field
+pub struct ConfigValidator {
+    errors: Option<Vec<ConfigParameterError>>,
+}
+
+impl ConfigValidator {
+    pub fn fail_fast() -> Self {
+        Self { errors: None }
+    }
+
+    pub fn collect_all() -> Self {
+        Self {
+            errors: Some(vec![]),
+        }
+    }
+
+    pub fn has_error(&self) -> bool {
+        self.errors
+            .as_ref()
+            .is_some_and(|errors| !errors.is_empty())
+    }
+
+    pub fn into_errors(self) -> Vec<ConfigParameterError> {
+        self.errors.unwrap_or_default()
+    }
+
+    /// Returns whether the check passed
+    pub fn check(&mut self, field: &str, result: Result<()>) -> Result<bool> {
+        self.check_field(|| field.to_string(), result)
+    }
+
+    /// Returns whether the condition holds
+    pub fn require(&mut self, field: &str, condition: bool, error: PoolError) -> Result<bool> {
+        if condition {
+            return Ok(true);
+        }
+        self.check(field, Err(error.into()))
+    }
+
+    /// Check of an item of a list field, reported as `field[index].sub_field`, or `field[index]` when sub field
+    /// is empty
+    pub fn require_at(
+        &mut self,
+        field: &str,
+        index: usize,
+        sub_field: &str,
+        condition: bool,
+        error: PoolError,
+    ) -> Result<bool> {
+        if condition {
+            return Ok(true);
+        }
+        self.check_at(field, index, sub_field, Err(error.into()))
+    }
+
+    /// Same as [Self::require_at] for a check returning a result
+    pub fn check_at(
+        &mut self,
+        field: &str,
+        index: usize,
+        sub_field: &str,
+        result: Result<()>,
+    ) -> Result<bool> {
+        self.check_field(
+            || {
+                if sub_field.is_empty() {
+                    format!("{}[{}]", field, index)
+                } else {
+                    format!("{}[{}].{}", field, index, sub_field)
+                }
+            },
+            result,
+        )
+    }
+
+    /// Field path is only built when the check fails and errors are collected
+    fn check_field(&mut self, field: impl FnOnce() -> String, result: Result<()>) -> Result<bool> {
+        match (result, self.errors.as_mut()) {
+            (Ok(()), _) => Ok(true),
+            (Err(error), None) => Err(error),
+            (Err(error), Some(errors)) => {
+                errors.push(ConfigParameterError {
+                    field: field(),
+                    error,
+                });
+                Ok(false)
+            }
+        }
+    }
+}
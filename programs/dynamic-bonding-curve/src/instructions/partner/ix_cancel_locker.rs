@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use locker::cpi::accounts::CancelVestingEscrow;
+
+use crate::{
+    const_pda, load_vesting_escrow,
+    safe_math::SafeMath,
+    state::{PoolConfig, VirtualPool},
+    token::transfer_from_pool,
+    EvtCancelLocker, PoolError,
+};
+
+/// Accounts for partner to cancel a locked vesting escrow of a virtual pool
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelLockerCtx<'info> {
+    #[account(has_one = base_mint, has_one = config)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    #[account(has_one = fee_claimer)]
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    /// CHECK: pool authority
+    #[account(
+        mut,
+        address = const_pda::pool_authority::ID,
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: escrow of locker, validated in handler
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: escrow token, validated by locker program
+    #[account(mut)]
+    pub escrow_token: UncheckedAccount<'info>,
+
+    /// base token account of pool authority receiving unvested token from locker, needs to be pre-created
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = pool_authority,
+        token::token_program = token_program
+    )]
+    pub pool_authority_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: recipient token account receiving claimable token, validated by locker program
+    #[account(mut)]
+    pub recipient_token: UncheckedAccount<'info>,
+
+    /// base token account of partner receiving unvested token
+    #[account(mut, token::mint = base_mint, token::token_program = token_program)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: receiver of rent of escrow token, the payer of `create_locker`, validated in handler
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// base token mint
+    #[account(mut)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub fee_claimer: Signer<'info>,
+
+    /// CHECK: memo program, validated by locker program
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// token program of base mint
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Locker program
+    #[account(address = locker::ID)]
+    pub locker_program: UncheckedAccount<'info>,
+
+    /// CHECK: Locker event authority
+    pub locker_event_authority: UncheckedAccount<'info>,
+}
+
+/// Partner cancels a locked vesting escrow, claimable token goes to the recipient and unvested token to partner
+pub fn handle_cancel_locker(ctx: Context<CancelLockerCtx>) -> Result<()> {
+    {
+        let config = ctx.accounts.config.load()?;
+        require!(
+            config
+                .locked_vesting_config
+                .get_cancel_mode()?
+                .is_partner_allowed(),
+            PoolError::NotPermitToDoThisAction
+        );
+        let virtual_pool = ctx.accounts.virtual_pool.load()?;
+        require!(
+            ctx.accounts.rent_receiver.key() == virtual_pool.locker_payer,
+            PoolError::InvalidAccount
+        );
+    }
+    let vesting_escrow = load_vesting_escrow(
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.base_mint.key(),
+    )?;
+
+    let amount_before = ctx.accounts.pool_authority_token.amount;
+
+    let pool_authority_seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+    locker::cpi::cancel_vesting_escrow(
+        CpiContext::new_with_signer(
+            ctx.accounts.locker_program.to_account_info(),
+            CancelVestingEscrow {
+                escrow: ctx.accounts.escrow.to_account_info(),
+                token_mint: ctx.accounts.base_mint.to_account_info(),
+                escrow_token: ctx.accounts.escrow_token.to_account_info(),
+                creator_token: ctx.accounts.pool_authority_token.to_account_info(),
+                recipient_token: ctx.accounts.recipient_token.to_account_info(),
+                rent_receiver: ctx.accounts.rent_receiver.to_account_info(),
+                signer: ctx.accounts.pool_authority.to_account_info(),
+                memo_program: ctx.accounts.memo_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                event_authority: ctx.accounts.locker_event_authority.to_account_info(),
+                program: ctx.accounts.locker_program.to_account_info(),
+            },
+            &[&pool_authority_seeds[..]],
+        ),
+        None,
+    )?;
+
+    ctx.accounts.pool_authority_token.reload()?;
+    let cancelled_amount = ctx
+        .accounts
+        .pool_authority_token
+        .amount
+        .safe_sub(amount_before)?;
+
+    transfer_from_pool(
+        ctx.accounts.pool_authority.to_account_info(),
+        &ctx.accounts.base_mint,
+        &ctx.accounts.pool_authority_token,
+        &ctx.accounts.token_account,
+        &ctx.accounts.token_program,
+        cancelled_amount,
+        const_pda::pool_authority::BUMP,
+    )?;
+
+    emit_cpi!(EvtCancelLocker {
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+        base_mint: ctx.accounts.base_mint.key(),
+        escrow: ctx.accounts.escrow.key(),
+        recipient: vesting_escrow.recipient,
+        cancelled_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    safe_math::SafeMath,
+    state::{PoolConfig, CLOSED_CONFIG_DISCRIMINATOR},
+    EvtCloseConfig, PoolError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseConfigCtx<'info> {
+    /// CHECK: config, read in handler. It isn't an `AccountLoader` so anchor doesn't write the config discriminator
+    /// back on exit
+    #[account(mut, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    pub close_authority: Signer<'info>,
+
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle_close_config(ctx: Context<CloseConfigCtx>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let pool_count = {
+        let data = config_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + PoolConfig::INIT_SPACE && data[..8] == *PoolConfig::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let config: PoolConfig = bytemuck::pod_read_unaligned(&data[8..8 + PoolConfig::INIT_SPACE]);
+        require_keys_eq!(
+            config.close_authority,
+            ctx.accounts.close_authority.key(),
+            ErrorCode::ConstraintHasOne
+        );
+        require!(config.is_closable(), PoolError::ConfigIsNotClosable);
+        config.pool_count
+    };
+
+    // keep the account as a closed config instead of returning it to system program, otherwise the config keypair
+    // could initialize a different config at the same address
+    config_info.realloc(CLOSED_CONFIG_DISCRIMINATOR.len(), false)?;
+    config_info
+        .try_borrow_mut_data()?
+        .copy_from_slice(&CLOSED_CONFIG_DISCRIMINATOR);
+    let closed_config_lamports = Rent::get()?.minimum_balance(CLOSED_CONFIG_DISCRIMINATOR.len());
+    let rent_amount = config_info.lamports().safe_sub(closed_config_lamports)?;
+    config_info.sub_lamports(rent_amount)?;
+    ctx.accounts.rent_receiver.add_lamports(rent_amount)?;
+
+    emit_cpi!(EvtCloseConfig {
+        config: ctx.accounts.config.key(),
+        close_authority: ctx.accounts.close_authority.key(),
+        pool_count,
+    });
+
+    Ok(())
+}
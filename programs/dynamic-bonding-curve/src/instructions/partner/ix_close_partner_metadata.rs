@@ -0,0 +1,34 @@
+use crate::constants::seeds::PARTNER_METADATA_PREFIX;
+use crate::state::PartnerMetadata;
+use crate::EvtClosePartnerMetadata;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePartnerMetadataCtx<'info> {
+    /// Partner metadata
+    #[account(
+        mut,
+        seeds = [
+            PARTNER_METADATA_PREFIX,
+            fee_claimer.key().as_ref()
+        ],
+        bump,
+        has_one = fee_claimer,
+        close = rent_receiver,
+    )]
+    pub partner_metadata: Box<Account<'info, PartnerMetadata>>,
+    /// Fee claimer for partner
+    pub fee_claimer: Signer<'info>,
+    /// CHECK: rent receiver
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn handle_close_partner_metadata(ctx: Context<ClosePartnerMetadataCtx>) -> Result<()> {
+    emit_cpi!(EvtClosePartnerMetadata {
+        partner_metadata: ctx.accounts.partner_metadata.key(),
+        fee_claimer: ctx.accounts.fee_claimer.key(),
+    });
+    Ok(())
+}
@@ -5,21 +5,29 @@ use static_assertions::const_assert_eq;
 
 use crate::{
     activation_handler::ActivationType,
-    constants::{MAX_CURVE_POINT, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    constants::{
+        dlmm::MIGRATION_BIN_RANGE, fee::MAX_FEE_BPS, MAX_CURVE_POINT, MAX_LOCKED_VESTING_TRANCHES,
+        MAX_MIGRATION_PRICE_TOLERANCE_BPS, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+    },
+    math::bin_math::{
+        get_base_factor, get_id_from_sqrt_price, is_overflow_default_bin_array_bitmap,
+    },
     params::{
-        fee_parameters::PoolFeeParameters,
+        fee_parameters::{MigratedPoolFeeParameters, PoolFeeParameters},
         liquidity_distribution::{
             get_base_token_for_swap, get_migration_base_token, get_migration_threshold_price,
-            LiquidityDistributionParameters,
+            LiquidityDistributionParameters, MigrationPriceRange,
         },
     },
     safe_math::SafeMath,
     state::{
-        CollectFeeMode, LockedVestingConfig, MigrationFeeOption, MigrationOption, PoolConfig,
-        TokenType, TokenUpdateAuthorityOption,
+        CollectFeeMode, LockedVestingCancelMode, LockedVestingConfig, LockedVestingStartAnchor,
+        LockedVestingTrancheConfig, LockedVestingUpdateRecipientMode, MigrationAmount,
+        MigrationFeeOption, MigrationOption, PoolConfig, PoolConfigExtension, PoolCreationFeeToken,
+        RefundFeeMode, TokenType, TokenUpdateAuthorityOption,
     },
     token::{get_token_program_flags, is_supported_quote_mint},
-    EvtCreateConfig, PoolError,
+    ConfigValidator, EvtCreateConfig, PoolError,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug)]
@@ -42,9 +50,65 @@ pub struct ConfigParameters {
     pub creator_trading_fee_percentage: u8, // percentage of trading fee creator can share with partner
     pub token_update_authority: u8,
     pub migration_fee: MigrationFee,
-    pub padding_0: [u8; 4],
+    /// locked vesting of base token to recipients other than pool creator, at most MAX_LOCKED_VESTING_TRANCHES
+    pub locked_vesting_tranches: Vec<LockedVestingTrancheParams>,
+    /// who can update the recipient of locked vesting escrows, recipient (0), none (1), partner (2) or either (3)
+    pub locked_vesting_update_recipient_mode: u8,
+    /// who can cancel locked vesting escrows for partner to claw back unvested token, none (0) or partner (1)
+    pub locked_vesting_cancel_mode: u8,
+    /// start of locked vesting escrows, migration (0), pool activation (1) or `locked_vesting_start_timestamp` (2)
+    pub locked_vesting_start_anchor: u8,
+    /// unix timestamp locked vesting starts at, only for timestamp start anchor
+    pub locked_vesting_start_timestamp: u64,
+    /// token of pool creation fee, SOL (0) or quote token (1)
+    pub pool_creation_fee_token: u8,
+    /// transfer fee basis points of token 2022 base mint, 0 means no transfer fee
+    pub base_transfer_fee_basis_points: u16,
+    /// list pool creator as verified creator in metaplex metadata of spl token base mint, 0 or 1
+    pub metadata_creator_flag: u8,
+    /// authority that can close the config, default pubkey means the config can't be closed
+    pub close_authority: Pubkey,
+    /// authority that must approve pool creation, default pubkey means anyone can create pool
+    pub pool_creator_authority: Pubkey,
+    /// fee charged from payer when a pool is created on the config
+    pub pool_creation_fee: u64,
+    /// address to receive pool creation fee
+    pub pool_creation_fee_recipient: Pubkey,
+    /// maximum transfer fee of token 2022 base mint
+    pub base_transfer_fee_maximum_fee: u64,
+    /// metaplex collection of spl token base mint, default pubkey means no collection
+    pub metadata_collection_mint: Pubkey,
+    /// program receiving migrated liquidity, only for external program migration option
+    pub migration_program: Pubkey,
+    /// quote token paid from migration fee to the account executing the migration, only for damm and damm v2
+    pub migration_crank_reward: u64,
+    /// fees of the damm v2 pool created at migration, only for damm v2 migration. When set, migration creates a
+    /// customizable damm v2 pool with these fees instead of using a damm v2 config key
+    pub migrated_pool_fee: Option<MigratedPoolFeeParameters>,
+    /// price range of the damm v2 pool created at migration around the migration price, only for damm v2
+    /// migration. Default is full price range
+    pub migration_price_range: Option<MigrationPriceRange>,
+    /// bin step of the dlmm pool created at migration, only for dlmm migration option
+    pub dlmm_bin_step: u16,
+    /// migrate to damm v2 in the swap completing the curve when migration accounts are given, 0 or 1
+    pub auto_migration_flag: u8,
+    /// base fee bps of the pool created at migration, only for customizable migration fee option
+    pub migration_base_fee_bps: u16,
+    /// max divergence in bps of the price of the pool created at migration from the final price of the curve,
+    /// checked at migration. 0 means migration price isn't checked
+    pub migration_price_tolerance_bps: u16,
+    /// duration after pool activation for the curve to complete, in slots or seconds by activation type. Past it,
+    /// swaps stop and base token holders redeem the quote reserve pro-rata. 0 means no refund
+    pub refund_deadline_duration: u64,
+    /// partner and creator trading fees of a refunding pool, kept (0) or refunded to holders (1)
+    pub refund_fee_mode: u8,
+    /// interest rate in bps of token 2022 base mint, 0 means no interest. Not supported by damm v2 migration
+    pub base_interest_rate: i16,
+    /// freeze token accounts of token 2022 base mint until migration, 0 or 1. Not supported by damm v2 migration,
+    /// and fees must be collected on quote token
+    pub base_non_transferable_flag: u8,
     /// padding for future use
-    pub padding_1: [u64; 7],
+    pub padding: [u64; 4],
     pub curve: Vec<LiquidityDistributionParameters>,
 }
 
@@ -106,21 +170,24 @@ impl LockedVestingParams {
         }
     }
 
+    /// Escrow parameters of the vesting, cliff duration is counted from the start time given by config
+    /// `locked_vesting_start_anchor`
     pub fn to_create_vesting_escrow_params(
         &self,
-        finish_curve_timestamp: u64,
+        vesting_start_time: u64,
+        update_recipient_mode: LockedVestingUpdateRecipientMode,
+        cancel_mode: LockedVestingCancelMode,
     ) -> Result<CreateVestingEscrowParameters> {
-        let cliff_time =
-            finish_curve_timestamp.safe_add(self.cliff_duration_from_migration_time)?;
+        let cliff_time = vesting_start_time.safe_add(self.cliff_duration_from_migration_time)?;
         Ok(CreateVestingEscrowParameters {
-            vesting_start_time: finish_curve_timestamp,
+            vesting_start_time,
             cliff_time,
             frequency: self.frequency,
             cliff_unlock_amount: self.cliff_unlock_amount,
             amount_per_period: self.amount_per_period,
             number_of_period: self.number_of_period,
-            update_recipient_mode: 2, // only recipient
-            cancel_mode: 1,           // only creator
+            update_recipient_mode: update_recipient_mode.to_escrow_mode(),
+            cancel_mode: cancel_mode.to_escrow_mode(),
         })
     }
 
@@ -146,7 +213,55 @@ impl LockedVestingParams {
     }
 }
 
+/// Total base token locked in vesting escrows
+pub fn get_total_locked_vesting_amount(locked_vestings: &[LockedVestingParams]) -> Result<u64> {
+    let mut total_amount: u64 = 0;
+    for locked_vesting in locked_vestings {
+        total_amount = total_amount.safe_add(locked_vesting.get_total_amount()?)?;
+    }
+    Ok(total_amount)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct LockedVestingTrancheParams {
+    pub recipient: Pubkey,
+    pub locked_vesting: LockedVestingParams,
+}
+
+impl LockedVestingTrancheParams {
+    pub fn to_locked_vesting_tranche_config(&self) -> LockedVestingTrancheConfig {
+        LockedVestingTrancheConfig {
+            recipient: self.recipient,
+            locked_vesting_config: self.locked_vesting.to_locked_vesting_config(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.recipient != Pubkey::default() && self.locked_vesting.has_vesting(),
+            PoolError::InvalidVestingParameters
+        );
+        self.locked_vesting.validate()
+    }
+}
+
 impl ConfigParameters {
+    /// Locked vesting of pool creator followed by the locked vesting tranches
+    pub fn get_locked_vestings(&self) -> Vec<LockedVestingParams> {
+        let mut locked_vestings = vec![];
+        if self.locked_vesting.has_vesting() {
+            locked_vestings.push(self.locked_vesting);
+        }
+        for tranche in self.locked_vesting_tranches.iter() {
+            locked_vestings.push(tranche.locked_vesting);
+        }
+        locked_vestings
+    }
+
+    pub fn has_locked_vesting(&self) -> bool {
+        self.locked_vesting.has_vesting() || !self.locked_vesting_tranches.is_empty()
+    }
+
     pub fn validate<'info>(&self, quote_mint: &InterfaceAccount<'info, Mint>) -> Result<()> {
         // validate quote mint
         require!(
@@ -154,113 +269,671 @@ impl ConfigParameters {
             PoolError::InvalidQuoteMint
         );
 
-        let activation_type = ActivationType::try_from(self.activation_type)
-            .map_err(|_| PoolError::TypeCastFailed)?;
+        self.validate_parameters(
+            quote_mint.to_account_info().owner,
+            &mut ConfigValidator::fail_fast(),
+        )
+    }
+
+    /// Checks of the parameters that don't need the quote mint account, `quote_token_program` is the owner of
+    /// quote mint
+    pub fn validate_parameters(
+        &self,
+        quote_token_program: &Pubkey,
+        validator: &mut ConfigValidator,
+    ) -> Result<()> {
+        let activation_type = ActivationType::try_from(self.activation_type).ok();
+        validator.require(
+            "activation_type",
+            activation_type.is_some(),
+            PoolError::TypeCastFailed,
+        )?;
+
+        // validate collect fee mode
+        let collect_fee_mode_valid = validator.require(
+            "collect_fee_mode",
+            CollectFeeMode::try_from(self.collect_fee_mode).is_ok(),
+            PoolError::InvalidCollectFeeMode,
+        )?;
 
         // validate fee
-        self.pool_fees
-            .validate(self.collect_fee_mode, activation_type)?;
+        if let (Some(activation_type), true) = (activation_type, collect_fee_mode_valid) {
+            validator.check(
+                "pool_fees",
+                self.pool_fees
+                    .validate(self.collect_fee_mode, activation_type),
+            )?;
+        }
 
         // validate creator trading fee percentage
-        require!(
+        validator.require(
+            "creator_trading_fee_percentage",
             self.creator_trading_fee_percentage <= 100,
-            PoolError::InvalidCreatorTradingFeePercentage
-        );
+            PoolError::InvalidCreatorTradingFeePercentage,
+        )?;
 
-        self.migration_fee.validate()?;
+        validator.check("migration_fee", self.migration_fee.validate())?;
+
+        // validate pool creation fee
+        validator.require(
+            "pool_creation_fee_token",
+            PoolCreationFeeToken::try_from(self.pool_creation_fee_token).is_ok(),
+            PoolError::InvalidPoolCreationFee,
+        )?;
+        if self.pool_creation_fee > 0 {
+            validator.require(
+                "pool_creation_fee_recipient",
+                self.pool_creation_fee_recipient != Pubkey::default(),
+                PoolError::InvalidPoolCreationFee,
+            )?;
+        }
 
-        // validate collect fee mode
-        require!(
-            CollectFeeMode::try_from(self.collect_fee_mode).is_ok(),
-            PoolError::InvalidCollectFeeMode
-        );
         // validate migration option and token type
-        let migration_option_value = MigrationOption::try_from(self.migration_option)
-            .map_err(|_| PoolError::InvalidMigrationOption)?;
-        let token_type_value =
-            TokenType::try_from(self.token_type).map_err(|_| PoolError::InvalidTokenType)?;
-
-        match migration_option_value {
-            MigrationOption::MeteoraDamm => {
-                require!(
-                    token_type_value == TokenType::SplToken,
-                    PoolError::InvalidTokenType
-                );
-                require!(
-                    *quote_mint.to_account_info().owner == anchor_spl::token::Token::id(),
-                    PoolError::InvalidQuoteMint
-                );
+        let migration_option = MigrationOption::try_from(self.migration_option).ok();
+        validator.require(
+            "migration_option",
+            migration_option.is_some(),
+            PoolError::InvalidMigrationOption,
+        )?;
+        let token_type = TokenType::try_from(self.token_type).ok();
+        validator.require(
+            "token_type",
+            token_type.is_some(),
+            PoolError::InvalidTokenType,
+        )?;
+
+        match migration_option {
+            Some(MigrationOption::MeteoraDamm) => {
+                validator.require(
+                    "token_type",
+                    token_type != Some(TokenType::Token2022),
+                    PoolError::InvalidTokenType,
+                )?;
+                validator.require(
+                    "quote_mint",
+                    *quote_token_program == anchor_spl::token::Token::id(),
+                    PoolError::InvalidQuoteMint,
+                )?;
+            }
+            Some(MigrationOption::Dlmm) => {
+                validator.require(
+                    "token_type",
+                    token_type != Some(TokenType::Token2022),
+                    PoolError::InvalidTokenType,
+                )?;
+                validator.require(
+                    "quote_mint",
+                    *quote_token_program == anchor_spl::token::Token::id(),
+                    PoolError::InvalidQuoteMint,
+                )?;
+                // migrated liquidity is held in dlmm positions owned by pool authority, so all lp is locked
+                validator.require(
+                    "partner_lp_percentage",
+                    self.partner_lp_percentage == 0 && self.creator_lp_percentage == 0,
+                    PoolError::InvalidDlmmConfig,
+                )?;
+                // dlmm base fee must match migration fee option, invalid option is reported below
+                if let Ok(migration_fee_option) =
+                    MigrationFeeOption::try_from(self.migration_fee_option)
+                {
+                    validator.check(
+                        "dlmm_bin_step",
+                        get_base_factor(
+                            migration_fee_option.get_base_fee_bps(self.migration_base_fee_bps),
+                            self.dlmm_bin_step,
+                        )
+                        .map(|_| ()),
+                    )?;
+                }
             }
-            MigrationOption::DammV2 => {
-                // nothing to check
+            Some(MigrationOption::ExternalProgram) => {
+                validator.require(
+                    "migration_program",
+                    self.migration_program != Pubkey::default()
+                        && self.migration_program != crate::ID,
+                    PoolError::InvalidMigrationProgram,
+                )?;
             }
+            Some(MigrationOption::DammV2) | None => {}
+        }
+        if migration_option != Some(MigrationOption::Dlmm) {
+            validator.require(
+                "dlmm_bin_step",
+                self.dlmm_bin_step == 0,
+                PoolError::InvalidDlmmConfig,
+            )?;
+        }
+        if migration_option != Some(MigrationOption::ExternalProgram) {
+            validator.require(
+                "migration_program",
+                self.migration_program == Pubkey::default(),
+                PoolError::InvalidMigrationProgram,
+            )?;
+        }
+        // validate auto migration, the completing swap can't create locker of locked vesting
+        validator.require(
+            "auto_migration_flag",
+            self.auto_migration_flag <= 1
+                && (self.auto_migration_flag == 0
+                    || (migration_option == Some(MigrationOption::DammV2)
+                        && !self.has_locked_vesting())),
+            PoolError::InvalidAutoMigrationConfig,
+        )?;
+        // validate fees of the migrated damm v2 pool
+        if let Some(migrated_pool_fee) = self.migrated_pool_fee {
+            validator.require(
+                "migrated_pool_fee",
+                migration_option == Some(MigrationOption::DammV2),
+                PoolError::InvalidMigratedPoolFee,
+            )?;
+            if let Some(activation_type) = activation_type {
+                validator.check(
+                    "migrated_pool_fee",
+                    migrated_pool_fee.validate(activation_type),
+                )?;
+            }
+        }
+        // validate price range of the migrated damm v2 pool
+        if let Some(migration_price_range) = self.migration_price_range {
+            validator.require(
+                "migration_price_range",
+                migration_option == Some(MigrationOption::DammV2),
+                PoolError::InvalidMigrationPriceRange,
+            )?;
+            validator.check("migration_price_range", migration_price_range.validate())?;
+        }
+        // validate price tolerance of the migrated pool, dlmm pool is opened at the bin below migration price
+        if self.migration_price_tolerance_bps > 0 {
+            validator.require(
+                "migration_price_tolerance_bps",
+                migration_option != Some(MigrationOption::ExternalProgram)
+                    && self.migration_price_tolerance_bps <= MAX_MIGRATION_PRICE_TOLERANCE_BPS
+                    && self.migration_price_tolerance_bps >= self.dlmm_bin_step,
+                PoolError::InvalidMigrationPriceTolerance,
+            )?;
+        }
+        // validate refund of pools not completed before the deadline
+        validator.require(
+            "refund_fee_mode",
+            RefundFeeMode::try_from(self.refund_fee_mode).is_ok()
+                && (self.refund_deadline_duration > 0 || self.refund_fee_mode == 0),
+            PoolError::InvalidRefundConfig,
+        )?;
+        // base vault would receive less than the base token redeemed, which is burnt from holders net of transfer fee
+        validator.require(
+            "refund_deadline_duration",
+            self.refund_deadline_duration == 0 || self.base_transfer_fee_basis_points == 0,
+            PoolError::InvalidRefundConfig,
+        )?;
+        // validate migration crank reward, it is reserved from migration fee
+        if self.migration_crank_reward > 0 {
+            let max_migration_crank_reward = PoolConfig::get_migration_quote_amount(
+                self.migration_quote_threshold,
+                self.migration_fee.fee_percentage,
+            )
+            .map_or(0, |MigrationAmount { fee, .. }| fee);
+            validator.require(
+                "migration_crank_reward",
+                (migration_option == Some(MigrationOption::MeteoraDamm)
+                    || migration_option == Some(MigrationOption::DammV2))
+                    && self.migration_crank_reward <= max_migration_crank_reward,
+                PoolError::InvalidMigrationCrankReward,
+            )?;
+        }
+
+        // validate base token transfer fee, only token 2022 base mint can charge transfer fee
+        if self.base_transfer_fee_basis_points > 0 {
+            validator.require(
+                "base_transfer_fee_basis_points",
+                token_type == Some(TokenType::Token2022)
+                    && u64::from(self.base_transfer_fee_basis_points) <= MAX_FEE_BPS,
+                PoolError::InvalidBaseTransferFee,
+            )?;
+            validator.require(
+                "base_transfer_fee_maximum_fee",
+                self.base_transfer_fee_maximum_fee > 0,
+                PoolError::InvalidBaseTransferFee,
+            )?;
+        }
+
+        // validate interest bearing and non-transferable base mint, damm v2 doesn't accept these extensions
+        if self.base_interest_rate != 0 {
+            validator.require(
+                "base_interest_rate",
+                token_type == Some(TokenType::Token2022)
+                    && migration_option != Some(MigrationOption::DammV2),
+                PoolError::InvalidBaseTokenExtension,
+            )?;
+        }
+        if self.base_non_transferable_flag != 0 {
+            validator.require(
+                "base_non_transferable_flag",
+                self.base_non_transferable_flag == 1
+                    && token_type == Some(TokenType::Token2022)
+                    && migration_option != Some(MigrationOption::DammV2),
+                PoolError::InvalidBaseTokenExtension,
+            )?;
+            // base token fees would be sent to frozen token accounts
+            validator.require(
+                "collect_fee_mode",
+                self.collect_fee_mode == u8::from(CollectFeeMode::QuoteToken),
+                PoolError::InvalidBaseTokenExtension,
+            )?;
+        }
+
+        // validate metaplex creator and collection, only spl token base mint has metaplex metadata
+        validator.require(
+            "metadata_creator_flag",
+            self.metadata_creator_flag <= 1,
+            PoolError::InvalidTokenMetadataConfig,
+        )?;
+        if self.metadata_creator_flag == 1 || self.metadata_collection_mint != Pubkey::default() {
+            validator.require(
+                "metadata_collection_mint",
+                token_type == Some(TokenType::SplToken),
+                PoolError::InvalidTokenMetadataConfig,
+            )?;
         }
 
         // validate token update authority
-        require!(
+        validator.require(
+            "token_update_authority",
             TokenUpdateAuthorityOption::try_from(self.token_update_authority).is_ok(),
-            PoolError::InvalidTokenUpdateAuthorityOption
-        );
+            PoolError::InvalidTokenUpdateAuthorityOption,
+        )?;
 
         // validate token decimals
-        require!(
+        validator.require(
+            "token_decimal",
             self.token_decimal >= 6 && self.token_decimal <= 9,
-            PoolError::InvalidTokenDecimals
-        );
+            PoolError::InvalidTokenDecimals,
+        )?;
 
-        let sum_lp_percentage = self
-            .partner_lp_percentage
-            .safe_add(self.partner_locked_lp_percentage)?
-            .safe_add(self.creator_lp_percentage)?
-            .safe_add(self.creator_locked_lp_percentage)?;
-        require!(sum_lp_percentage == 100, PoolError::InvalidFeePercentage);
+        let sum_lp_percentage = u16::from(self.partner_lp_percentage)
+            + u16::from(self.partner_locked_lp_percentage)
+            + u16::from(self.creator_lp_percentage)
+            + u16::from(self.creator_locked_lp_percentage);
+        validator.require(
+            "partner_lp_percentage",
+            sum_lp_percentage == 100,
+            PoolError::InvalidFeePercentage,
+        )?;
 
-        require!(
+        validator.require(
+            "migration_quote_threshold",
             self.migration_quote_threshold > 0,
-            PoolError::InvalidQuoteThreshold
-        );
+            PoolError::InvalidQuoteThreshold,
+        )?;
 
         // validate vesting params
-        self.locked_vesting.validate()?;
+        validator.check("locked_vesting", self.locked_vesting.validate())?;
+        validator.require(
+            "locked_vesting_tranches",
+            self.locked_vesting_tranches.len() <= MAX_LOCKED_VESTING_TRANCHES,
+            PoolError::InvalidVestingParameters,
+        )?;
+        for (i, tranche) in self.locked_vesting_tranches.iter().enumerate() {
+            validator.check_at("locked_vesting_tranches", i, "", tranche.validate())?;
+        }
+        validator.require(
+            "locked_vesting_update_recipient_mode",
+            LockedVestingUpdateRecipientMode::try_from(self.locked_vesting_update_recipient_mode)
+                .is_ok(),
+            PoolError::InvalidVestingParameters,
+        )?;
+        validator.require(
+            "locked_vesting_cancel_mode",
+            LockedVestingCancelMode::try_from(self.locked_vesting_cancel_mode).is_ok(),
+            PoolError::InvalidVestingParameters,
+        )?;
+        match LockedVestingStartAnchor::try_from(self.locked_vesting_start_anchor) {
+            Ok(locked_vesting_start_anchor) => {
+                validator.require(
+                    "locked_vesting_start_timestamp",
+                    (locked_vesting_start_anchor == LockedVestingStartAnchor::Timestamp)
+                        == (self.locked_vesting_start_timestamp > 0),
+                    PoolError::InvalidVestingParameters,
+                )?;
+            }
+            Err(_) => {
+                validator.check(
+                    "locked_vesting_start_anchor",
+                    Err(PoolError::InvalidVestingParameters.into()),
+                )?;
+            }
+        }
 
         // validate migrate fee option
-        require!(
-            MigrationFeeOption::try_from(self.migration_fee_option).is_ok(),
-            PoolError::InvalidMigrationFeeOption
-        );
+        match MigrationFeeOption::try_from(self.migration_fee_option) {
+            Ok(migration_fee_option) => {
+                validator.check(
+                    "migration_base_fee_bps",
+                    migration_fee_option
+                        .validate_migration_base_fee_bps(self.migration_base_fee_bps),
+                )?;
+            }
+            Err(_) => {
+                validator.check(
+                    "migration_fee_option",
+                    Err(PoolError::InvalidMigrationFeeOption.into()),
+                )?;
+            }
+        }
 
         // validate price and liquidity
-        require!(
+        self.validate_curve(validator)
+    }
+
+    fn validate_curve(&self, validator: &mut ConfigValidator) -> Result<()> {
+        validator.require(
+            "sqrt_start_price",
             self.sqrt_start_price >= MIN_SQRT_PRICE && self.sqrt_start_price < MAX_SQRT_PRICE,
-            PoolError::InvalidCurve
-        );
+            PoolError::InvalidCurve,
+        )?;
         let curve_length = self.curve.len();
-        require!(
+        if !validator.require(
+            "curve",
             curve_length > 0 && curve_length <= MAX_CURVE_POINT,
-            PoolError::InvalidCurve
-        );
-        require!(
-            self.curve[0].sqrt_price > self.sqrt_start_price
-                && self.curve[0].liquidity > 0
-                && self.curve[0].sqrt_price <= MAX_SQRT_PRICE,
-            PoolError::InvalidCurve
-        );
+            PoolError::InvalidCurve,
+        )? {
+            return Ok(());
+        }
+
+        for (i, point) in self.curve.iter().enumerate() {
+            let lower_sqrt_price = if i == 0 {
+                self.sqrt_start_price
+            } else {
+                self.curve[i - 1].sqrt_price
+            };
+            // the last price in curve must be smaller than or equal max price
+            validator.require_at(
+                "curve",
+                i,
+                "sqrt_price",
+                point.sqrt_price > lower_sqrt_price && point.sqrt_price <= MAX_SQRT_PRICE,
+                PoolError::InvalidCurve,
+            )?;
+            validator.require_at(
+                "curve",
+                i,
+                "liquidity",
+                point.liquidity > 0,
+                PoolError::InvalidCurve,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Config extension with the fields set by the parameters, derived migration fields are set by
+    /// [Self::get_derived_values]
+    pub fn to_config_extension(&self) -> PoolConfigExtension {
+        let mut config_extension = PoolConfigExtension {
+            pool_creator_authority: self.pool_creator_authority,
+            pool_creation_fee_recipient: self.pool_creation_fee_recipient,
+            pool_creation_fee: self.pool_creation_fee,
+            pool_creation_fee_token: self.pool_creation_fee_token,
+            base_transfer_fee_basis_points: self.base_transfer_fee_basis_points,
+            base_transfer_fee_maximum_fee: self.base_transfer_fee_maximum_fee,
+            metadata_creator_flag: self.metadata_creator_flag,
+            metadata_collection_mint: self.metadata_collection_mint,
+            migration_program: self.migration_program,
+            migration_crank_reward: self.migration_crank_reward,
+            migrated_pool_fee: self
+                .migrated_pool_fee
+                .map(|migrated_pool_fee| migrated_pool_fee.to_migrated_pool_fee_config())
+                .unwrap_or_default(),
+            auto_migration_flag: self.auto_migration_flag,
+            migration_base_fee_bps: self.migration_base_fee_bps,
+            migration_price_tolerance_bps: self.migration_price_tolerance_bps,
+            locked_vesting_start_anchor: self.locked_vesting_start_anchor,
+            locked_vesting_start_timestamp: self.locked_vesting_start_timestamp,
+            refund_deadline_duration: self.refund_deadline_duration,
+            refund_fee_mode: self.refund_fee_mode,
+            base_interest_rate: self.base_interest_rate,
+            base_non_transferable_flag: self.base_non_transferable_flag,
+            ..Default::default()
+        };
+        for (i, tranche) in self.locked_vesting_tranches.iter().enumerate() {
+            config_extension.locked_vesting_tranches[i] =
+                tranche.to_locked_vesting_tranche_config();
+            config_extension.locked_vesting_tranches[i]
+                .locked_vesting_config
+                .set_escrow_modes(
+                    self.locked_vesting_update_recipient_mode,
+                    self.locked_vesting_cancel_mode,
+                );
+        }
+        config_extension
+    }
+
+    /// Migration price and base token amounts derived from the curve, sets the derived migration fields of config
+    /// extension. Parameters must pass [Self::validate_parameters], returns none when a check fails and validator
+    /// collects errors
+    pub fn get_derived_values(
+        &self,
+        leftover_receiver: &Pubkey,
+        config_extension: &mut PoolConfigExtension,
+        validator: &mut ConfigValidator,
+    ) -> Result<Option<ConfigDerivedValues>> {
+        let ConfigParameters {
+            migration_quote_threshold,
+            sqrt_start_price,
+            ref curve,
+            migration_fee,
+            migration_option,
+            dlmm_bin_step,
+            token_supply,
+            ..
+        } = *self;
+        let locked_vestings = self.get_locked_vestings();
 
-        for i in 1..curve_length {
+        let sqrt_migration_price =
+            get_migration_threshold_price(migration_quote_threshold, sqrt_start_price, curve);
+        // migration price must be smaller than max sqrt price
+        let sqrt_migration_price = sqrt_migration_price.and_then(|sqrt_migration_price| {
             require!(
-                self.curve[i].sqrt_price > self.curve[i - 1].sqrt_price
-                    && self.curve[i].liquidity > 0,
+                sqrt_migration_price < MAX_SQRT_PRICE,
                 PoolError::InvalidCurve
             );
-        }
+            Ok(sqrt_migration_price)
+        });
+        let Some(sqrt_migration_price) = check_value(validator, "curve", sqrt_migration_price)?
+        else {
+            return Ok(None);
+        };
 
-        // the last price in curve must be smaller than or equal max price
-        require!(
-            self.curve[curve_length - 1].sqrt_price <= MAX_SQRT_PRICE,
-            PoolError::InvalidCurve
+        let swap_base_amount =
+            get_base_token_for_swap(sqrt_start_price, sqrt_migration_price, curve).and_then(
+                |swap_base_amount| {
+                    u64::try_from(swap_base_amount).map_err(|_| PoolError::TypeCastFailed.into())
+                },
+            );
+        let Some(swap_base_amount) = check_value(validator, "curve", swap_base_amount)? else {
+            return Ok(None);
+        };
+
+        let migration_option_value = MigrationOption::try_from(migration_option)
+            .map_err(|_| PoolError::InvalidMigrationOption)?;
+        if let Some(migration_price_range) = self.migration_price_range {
+            // damm v2 pool is concentrated around migration price
+            let Some((migration_sqrt_min_price, migration_sqrt_max_price)) = check_value(
+                validator,
+                "migration_price_range",
+                migration_price_range.get_sqrt_price_range(sqrt_migration_price),
+            )?
+            else {
+                return Ok(None);
+            };
+            config_extension.migration_sqrt_min_price = migration_sqrt_min_price;
+            config_extension.migration_sqrt_max_price = migration_sqrt_max_price;
+        }
+        let (migration_sqrt_min_price, migration_sqrt_max_price) =
+            config_extension.get_migration_sqrt_price_range();
+        let migration_base_amount = get_migration_base_token(
+            migration_quote_threshold,
+            migration_fee.fee_percentage,
+            sqrt_migration_price,
+            migration_option_value,
+            dlmm_bin_step,
+            migration_sqrt_min_price,
+            migration_sqrt_max_price,
         );
+        let Some(migration_base_amount) = check_value(
+            validator,
+            "migration_quote_threshold",
+            migration_base_amount,
+        )?
+        else {
+            return Ok(None);
+        };
+        if migration_option_value == MigrationOption::Dlmm {
+            // dlmm pool is opened at the bin of migration price, migration doesn't initialize bin array bitmap
+            // extension
+            let dlmm_active_id = get_id_from_sqrt_price(sqrt_migration_price, dlmm_bin_step)
+                .and_then(|dlmm_active_id| {
+                    require!(
+                        !is_overflow_default_bin_array_bitmap(
+                            dlmm_active_id.safe_sub(MIGRATION_BIN_RANGE)?
+                        ) && !is_overflow_default_bin_array_bitmap(
+                            dlmm_active_id.safe_add(MIGRATION_BIN_RANGE)?
+                        ),
+                        PoolError::InvalidDlmmConfig
+                    );
+                    Ok(dlmm_active_id)
+                });
+            let Some(dlmm_active_id) = check_value(validator, "dlmm_bin_step", dlmm_active_id)?
+            else {
+                return Ok(None);
+            };
+            config_extension.dlmm_bin_step = dlmm_bin_step;
+            config_extension.dlmm_active_id = dlmm_active_id;
+        }
+        let migration_base_amount = PoolConfig::get_migration_base_threshold_with_transfer_fee(
+            migration_base_amount,
+            &locked_vestings,
+            config_extension.get_base_transfer_fee().as_ref(),
+        );
+        let Some(migration_base_amount) = check_value(
+            validator,
+            "base_transfer_fee_basis_points",
+            migration_base_amount,
+        )?
+        else {
+            return Ok(None);
+        };
 
-        Ok(())
+        // this is fine to add redundant check
+        let swap_base_amount_valid =
+            validator.require("curve", swap_base_amount > 0, PoolError::InvalidCurve)?;
+        let migration_base_amount_valid = validator.require(
+            "migration_quote_threshold",
+            migration_base_amount > 0,
+            PoolError::InvalidCurve,
+        )?;
+        if !swap_base_amount_valid || !migration_base_amount_valid {
+            return Ok(None);
+        }
+
+        let (fixed_token_supply_flag, pre_migration_token_supply, post_migration_token_supply) =
+            if let Some(TokenSupplyParams {
+                pre_migration_token_supply,
+                post_migration_token_supply,
+            }) = token_supply
+            {
+                let minimum_base_supply_with_buffer = PoolConfig::get_swap_amount_with_buffer(
+                    swap_base_amount,
+                    sqrt_start_price,
+                    curve,
+                )
+                .and_then(|swap_base_amount_buffer| {
+                    PoolConfig::get_total_token_supply(
+                        swap_base_amount_buffer,
+                        migration_base_amount,
+                        &locked_vestings,
+                    )
+                });
+                let Some(minimum_base_supply_with_buffer) =
+                    check_value(validator, "token_supply", minimum_base_supply_with_buffer)?
+                else {
+                    return Ok(None);
+                };
+
+                let minimum_base_supply_without_buffer = PoolConfig::get_total_token_supply(
+                    swap_base_amount,
+                    migration_base_amount,
+                    &locked_vestings,
+                );
+                let Some(minimum_base_supply_without_buffer) = check_value(
+                    validator,
+                    "token_supply",
+                    minimum_base_supply_without_buffer,
+                )?
+                else {
+                    return Ok(None);
+                };
+
+                let leftover_receiver_valid = validator.require(
+                    "leftover_receiver",
+                    *leftover_receiver != Pubkey::default(),
+                    PoolError::InvalidLeftoverAddress,
+                )?;
+                let post_migration_token_supply_valid = validator.require(
+                    "token_supply.post_migration_token_supply",
+                    minimum_base_supply_without_buffer <= post_migration_token_supply
+                        && post_migration_token_supply <= pre_migration_token_supply,
+                    PoolError::InvalidTokenSupply,
+                )?;
+                let pre_migration_token_supply_valid = validator.require(
+                    "token_supply.pre_migration_token_supply",
+                    minimum_base_supply_with_buffer <= pre_migration_token_supply,
+                    PoolError::InvalidTokenSupply,
+                )?;
+                if !leftover_receiver_valid
+                    || !post_migration_token_supply_valid
+                    || !pre_migration_token_supply_valid
+                {
+                    return Ok(None);
+                }
+                (1, pre_migration_token_supply, post_migration_token_supply)
+            } else {
+                (0, 0, 0)
+            };
+
+        Ok(Some(ConfigDerivedValues {
+            sqrt_migration_price,
+            swap_base_amount,
+            migration_base_amount,
+            fixed_token_supply_flag,
+            pre_migration_token_supply,
+            post_migration_token_supply,
+        }))
+    }
+}
+
+/// Values derived from config parameters and stored in the config
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfigDerivedValues {
+    pub sqrt_migration_price: u128,
+    pub swap_base_amount: u64,
+    pub migration_base_amount: u64,
+    pub fixed_token_supply_flag: u8,
+    pub pre_migration_token_supply: u64,
+    pub post_migration_token_supply: u64,
+}
+
+/// Value of a derived computation, none when it fails and validator collects errors
+fn check_value<T>(
+    validator: &mut ConfigValidator,
+    field: &str,
+    result: Result<T>,
+) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => {
+            validator.check(field, Err(error))?;
+            Ok(None)
+        }
     }
 }
 
@@ -271,7 +944,7 @@ pub struct CreateConfigCtx<'info> {
         init,
         signer,
         payer = payer,
-        space = 8 + PoolConfig::INIT_SPACE
+        space = 8 + PoolConfig::INIT_SPACE + PoolConfigExtension::INIT_SPACE
     )]
     pub config: AccountLoader<'info, PoolConfig>,
 
@@ -293,6 +966,21 @@ pub fn handle_create_config(
     config_parameters: ConfigParameters,
 ) -> Result<()> {
     config_parameters.validate(&ctx.accounts.quote_mint)?;
+    let mut config_extension = config_parameters.to_config_extension();
+    let ConfigDerivedValues {
+        sqrt_migration_price,
+        swap_base_amount,
+        migration_base_amount,
+        fixed_token_supply_flag,
+        pre_migration_token_supply,
+        post_migration_token_supply,
+    } = config_parameters
+        .get_derived_values(
+            ctx.accounts.leftover_receiver.key,
+            &mut config_extension,
+            &mut ConfigValidator::fail_fast(),
+        )?
+        .ok_or(PoolError::InvalidCurve)?;
 
     let ConfigParameters {
         pool_fees,
@@ -308,82 +996,17 @@ pub fn handle_create_config(
         migration_quote_threshold,
         sqrt_start_price,
         locked_vesting,
+        locked_vesting_update_recipient_mode,
+        locked_vesting_cancel_mode,
         migration_fee_option,
-        token_supply,
         curve,
         creator_trading_fee_percentage,
         token_update_authority,
         migration_fee,
+        close_authority,
         ..
     } = config_parameters;
 
-    let sqrt_migration_price =
-        get_migration_threshold_price(migration_quote_threshold, sqrt_start_price, &curve)?;
-    // migration price must be smaller than max sqrt price
-    require!(
-        sqrt_migration_price < MAX_SQRT_PRICE,
-        PoolError::InvalidCurve
-    );
-
-    let swap_base_amount_256 =
-        get_base_token_for_swap(sqrt_start_price, sqrt_migration_price, &curve)?;
-    let swap_base_amount: u64 = swap_base_amount_256
-        .try_into()
-        .map_err(|_| PoolError::TypeCastFailed)?;
-
-    let migration_base_amount = get_migration_base_token(
-        migration_quote_threshold,
-        migration_fee.fee_percentage,
-        sqrt_migration_price,
-        MigrationOption::try_from(migration_option)
-            .map_err(|_| PoolError::InvalidMigrationOption)?,
-    )?;
-
-    require!(
-        // this is fine to add redundant check
-        migration_base_amount > 0 && swap_base_amount > 0,
-        PoolError::InvalidCurve
-    );
-
-    let (fixed_token_supply_flag, pre_migration_token_supply, post_migration_token_supply) =
-        if let Some(TokenSupplyParams {
-            pre_migration_token_supply,
-            post_migration_token_supply,
-        }) = token_supply
-        {
-            let swap_base_amount_buffer = PoolConfig::get_swap_amount_with_buffer(
-                swap_base_amount,
-                sqrt_start_price,
-                &curve,
-            )?;
-
-            let minimum_base_supply_with_buffer = PoolConfig::get_total_token_supply(
-                swap_base_amount_buffer,
-                migration_base_amount,
-                &locked_vesting,
-            )?;
-
-            let minimum_base_supply_without_buffer = PoolConfig::get_total_token_supply(
-                swap_base_amount,
-                migration_base_amount,
-                &locked_vesting,
-            )?;
-
-            require!(
-                ctx.accounts.leftover_receiver.key() != Pubkey::default(),
-                PoolError::InvalidLeftoverAddress
-            );
-            require!(
-                minimum_base_supply_without_buffer <= post_migration_token_supply
-                    && post_migration_token_supply <= pre_migration_token_supply
-                    && minimum_base_supply_with_buffer <= pre_migration_token_supply,
-                PoolError::InvalidTokenSupply
-            );
-            (1, pre_migration_token_supply, post_migration_token_supply)
-        } else {
-            (0, 0, 0)
-        };
-
     let mut config = ctx.accounts.config.load_init()?;
     config.init(
         &ctx.accounts.quote_mint.key(),
@@ -414,7 +1037,15 @@ pub fn handle_create_config(
         pre_migration_token_supply,
         post_migration_token_supply,
         &curve,
+        &close_authority,
     );
+    config.locked_vesting_config.set_escrow_modes(
+        locked_vesting_update_recipient_mode,
+        locked_vesting_cancel_mode,
+    );
+    drop(config);
+
+    config_extension.store(&ctx.accounts.config)?;
 
     emit_cpi!(EvtCreateConfig {
         config: ctx.accounts.config.key(),
@@ -25,7 +25,7 @@ pub struct CreatePartnerMetadataCtx<'info> {
         ],
         bump,
         payer = payer,
-        space = 8 + PartnerMetadata::space(&metadata)
+        space = 8 + PartnerMetadata::space(&metadata.name, &metadata.website, &metadata.logo)
     )]
     pub partner_metadata: Box<Account<'info, PartnerMetadata>>,
     /// Payer of the partner metadata.
@@ -41,6 +41,8 @@ pub fn handle_create_partner_metadata(
     ctx: Context<CreatePartnerMetadataCtx>,
     metadata: CreatePartnerMetadataParameters,
 ) -> Result<()> {
+    PartnerMetadata::validate(&metadata.name, &metadata.website, &metadata.logo)?;
+
     let partner_metadata = &mut ctx.accounts.partner_metadata;
     partner_metadata.fee_claimer = ctx.accounts.fee_claimer.key();
     partner_metadata.name = metadata.name;
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use locker::cpi::accounts::UpdateVestingEscrowRecipient;
+
+use crate::{
+    const_pda, load_vesting_escrow,
+    state::{PoolConfig, VirtualPool},
+    EvtUpdateLockerRecipient, PoolError,
+};
+
+/// Accounts for partner to update the recipient of a locked vesting escrow of a virtual pool
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateLockerRecipientCtx<'info> {
+    #[account(has_one = base_mint, has_one = config)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    #[account(has_one = fee_claimer)]
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    /// CHECK: pool authority
+    #[account(
+        mut,
+        address = const_pda::pool_authority::ID,
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: escrow of locker, validated in handler
+    #[account(mut)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: base token mint
+    pub base_mint: UncheckedAccount<'info>,
+
+    pub fee_claimer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Locker program
+    #[account(address = locker::ID)]
+    pub locker_program: UncheckedAccount<'info>,
+
+    /// CHECK: Locker event authority
+    pub locker_event_authority: UncheckedAccount<'info>,
+}
+
+/// Partner updates the recipient of a locked vesting escrow, escrow metadata is left unchanged
+pub fn handle_update_locker_recipient(
+    ctx: Context<UpdateLockerRecipientCtx>,
+    new_recipient: Pubkey,
+) -> Result<()> {
+    {
+        let config = ctx.accounts.config.load()?;
+        require!(
+            config
+                .locked_vesting_config
+                .get_update_recipient_mode()?
+                .is_partner_allowed(),
+            PoolError::NotPermitToDoThisAction
+        );
+    }
+    let vesting_escrow = load_vesting_escrow(
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.base_mint.key(),
+    )?;
+
+    let pool_authority_seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+    locker::cpi::update_vesting_escrow_recipient(
+        CpiContext::new_with_signer(
+            ctx.accounts.locker_program.to_account_info(),
+            UpdateVestingEscrowRecipient {
+                escrow: ctx.accounts.escrow.to_account_info(),
+                escrow_metadata: None,
+                signer: ctx.accounts.pool_authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                event_authority: ctx.accounts.locker_event_authority.to_account_info(),
+                program: ctx.accounts.locker_program.to_account_info(),
+            },
+            &[&pool_authority_seeds[..]],
+        ),
+        new_recipient,
+        None,
+    )?;
+
+    emit_cpi!(EvtUpdateLockerRecipient {
+        virtual_pool: ctx.accounts.virtual_pool.key(),
+        base_mint: ctx.accounts.base_mint.key(),
+        escrow: ctx.accounts.escrow.key(),
+        old_recipient: vesting_escrow.recipient,
+        new_recipient,
+    });
+
+    Ok(())
+}
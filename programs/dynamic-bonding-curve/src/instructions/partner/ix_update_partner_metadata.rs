@@ -0,0 +1,55 @@
+use crate::constants::seeds::PARTNER_METADATA_PREFIX;
+use crate::state::PartnerMetadata;
+use crate::EvtUpdatePartnerMetadata;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdatePartnerMetadataParameters {
+    pub name: String,
+    pub website: String,
+    pub logo: String,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(metadata: UpdatePartnerMetadataParameters)]
+pub struct UpdatePartnerMetadataCtx<'info> {
+    /// Partner metadata, reallocated to the new string lengths
+    #[account(
+        mut,
+        seeds = [
+            PARTNER_METADATA_PREFIX,
+            fee_claimer.key().as_ref()
+        ],
+        bump,
+        has_one = fee_claimer,
+        realloc = 8 + PartnerMetadata::space(&metadata.name, &metadata.website, &metadata.logo),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub partner_metadata: Box<Account<'info, PartnerMetadata>>,
+    /// Payer of the extra rent, or receiver of the refunded rent when the metadata shrinks
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// Fee claimer for partner
+    pub fee_claimer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_update_partner_metadata(
+    ctx: Context<UpdatePartnerMetadataCtx>,
+    metadata: UpdatePartnerMetadataParameters,
+) -> Result<()> {
+    PartnerMetadata::validate(&metadata.name, &metadata.website, &metadata.logo)?;
+
+    let partner_metadata = &mut ctx.accounts.partner_metadata;
+    partner_metadata.name = metadata.name;
+    partner_metadata.website = metadata.website;
+    partner_metadata.logo = metadata.logo;
+    emit_cpi!(EvtUpdatePartnerMetadata {
+        partner_metadata: ctx.accounts.partner_metadata.key(),
+        fee_claimer: ctx.accounts.fee_claimer.key(),
+    });
+    Ok(())
+}
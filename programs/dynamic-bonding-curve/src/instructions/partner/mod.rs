@@ -2,7 +2,19 @@ pub mod ix_claim_partner_trading_fee;
 pub use ix_claim_partner_trading_fee::*;
 pub mod ix_create_config;
 pub use ix_create_config::*;
+pub mod config_validator;
+pub use config_validator::*;
 pub mod ix_withdraw_partner_surplus;
 pub use ix_withdraw_partner_surplus::*;
 pub mod ix_create_partner_metadata;
 pub use ix_create_partner_metadata::*;
+pub mod ix_close_config;
+pub use ix_close_config::*;
+pub mod ix_update_partner_metadata;
+pub use ix_update_partner_metadata::*;
+pub mod ix_close_partner_metadata;
+pub use ix_close_partner_metadata::*;
+pub mod ix_cancel_locker;
+pub use ix_cancel_locker::*;
+pub mod ix_update_locker_recipient;
+pub use ix_update_locker_recipient::*;
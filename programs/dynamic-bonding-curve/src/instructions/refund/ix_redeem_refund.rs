@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    const_pda,
+    state::{MigrationProgress, PoolConfig, VirtualPool},
+    token::{freeze_base_token_account, thaw_restricted_base_token_account, transfer_from_pool},
+    EvtRedeemRefund, PoolError,
+};
+
+/// Accounts for a base token holder to redeem base token of a refunding pool
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RedeemRefundCtx<'info> {
+    /// CHECK: pool authority
+    #[account(
+        address = const_pda::pool_authority::ID
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(has_one = quote_mint)]
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = base_mint,
+        has_one = quote_vault,
+        has_one = config,
+    )]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+
+    /// The owner base token account, redeemed base token is burned
+    #[account(mut, token::mint = base_mint, token::token_program = token_base_program)]
+    pub base_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The receiver quote token account
+    #[account(mut)]
+    pub quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault token account for quote token
+    #[account(mut, token::token_program = token_quote_program, token::mint = quote_mint)]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of base token
+    #[account(mut)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of quote token
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub owner: Signer<'info>,
+
+    /// Token base program
+    pub token_base_program: Interface<'info, TokenInterface>,
+
+    /// Token quote program
+    pub token_quote_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_redeem_refund(ctx: Context<RedeemRefundCtx>, base_amount: u64) -> Result<()> {
+    let mut virtual_pool = ctx.accounts.virtual_pool.load_mut()?;
+
+    require!(
+        virtual_pool.get_migration_progress()? == MigrationProgress::Refunding,
+        PoolError::NotPermitToDoThisAction
+    );
+
+    let quote_amount = virtual_pool.redeem_refund(base_amount)?;
+
+    // base token of a non-transferable base mint stays frozen, the pool never migrates
+    let refreeze_base_token_account = thaw_restricted_base_token_account(
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.base_token_account.to_account_info(),
+        ctx.accounts.token_base_program.to_account_info(),
+    )?;
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_base_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.base_mint.to_account_info(),
+                from: ctx.accounts.base_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        base_amount,
+    )?;
+    if refreeze_base_token_account {
+        freeze_base_token_account(
+            ctx.accounts.pool_authority.to_account_info(),
+            ctx.accounts.base_mint.to_account_info(),
+            ctx.accounts.base_token_account.to_account_info(),
+            ctx.accounts.token_base_program.to_account_info(),
+        )?;
+    }
+
+    transfer_from_pool(
+        ctx.accounts.pool_authority.to_account_info(),
+        &ctx.accounts.quote_mint,
+        &ctx.accounts.quote_vault,
+        &ctx.accounts.quote_token_account,
+        &ctx.accounts.token_quote_program,
+        quote_amount,
+        const_pda::pool_authority::BUMP,
+    )?;
+
+    emit_cpi!(EvtRedeemRefund {
+        pool: ctx.accounts.virtual_pool.key(),
+        owner: ctx.accounts.owner.key(),
+        base_amount,
+        quote_amount,
+    });
+
+    Ok(())
+}
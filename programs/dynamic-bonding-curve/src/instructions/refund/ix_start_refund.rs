@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    activation_handler::get_current_point,
+    state::{MigrationProgress, PoolConfig, PoolConfigExtension, VirtualPool},
+    EvtStartRefund, PoolError,
+};
+
+/// Accounts to start the refund of a pool that didn't complete the curve before the refund deadline
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StartRefundCtx<'info> {
+    pub config: AccountLoader<'info, PoolConfig>,
+
+    #[account(mut, has_one = config)]
+    pub virtual_pool: AccountLoader<'info, VirtualPool>,
+}
+
+pub fn handle_start_refund(ctx: Context<StartRefundCtx>) -> Result<()> {
+    let config = ctx.accounts.config.load()?;
+    let config_extension = PoolConfigExtension::load(&ctx.accounts.config)?;
+    let mut virtual_pool = ctx.accounts.virtual_pool.load_mut()?;
+
+    require!(
+        virtual_pool.get_migration_progress()? == MigrationProgress::PreBondingCurve
+            && !virtual_pool.is_curve_complete(config.migration_quote_threshold),
+        PoolError::NotPermitToDoThisAction
+    );
+
+    let current_point = get_current_point(config.activation_type)?;
+    require!(
+        config_extension.is_refund_deadline_passed(virtual_pool.activation_point, current_point)?,
+        PoolError::NotPermitToDoThisAction
+    );
+
+    virtual_pool.start_refund(
+        config.get_initial_base_supply(&config_extension)?,
+        config_extension.get_refund_fee_mode()?,
+    )?;
+
+    emit_cpi!(EvtStartRefund {
+        pool: ctx.accounts.virtual_pool.key(),
+        config: ctx.accounts.config.key(),
+        refund_base_amount: virtual_pool.refund_base_amount,
+        refund_quote_amount: virtual_pool.refund_quote_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod ix_start_refund;
+pub use ix_start_refund::*;
+pub mod ix_redeem_refund;
+pub use ix_redeem_refund::*;
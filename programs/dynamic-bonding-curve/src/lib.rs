@@ -47,6 +47,10 @@ pub mod dynamic_bonding_curve {
         instructions::handle_protocol_withdraw_surplus(ctx)
     }
 
+    pub fn deprecate_config(ctx: Context<DeprecateConfigCtx>) -> Result<()> {
+        instructions::handle_deprecate_config(ctx)
+    }
+
     /// PARTNER FUNCTIONS ////
     pub fn create_partner_metadata(
         ctx: Context<CreatePartnerMetadataCtx>,
@@ -54,12 +58,28 @@ pub mod dynamic_bonding_curve {
     ) -> Result<()> {
         instructions::handle_create_partner_metadata(ctx, metadata)
     }
+
+    pub fn update_partner_metadata(
+        ctx: Context<UpdatePartnerMetadataCtx>,
+        metadata: UpdatePartnerMetadataParameters,
+    ) -> Result<()> {
+        instructions::handle_update_partner_metadata(ctx, metadata)
+    }
+
+    pub fn close_partner_metadata(ctx: Context<ClosePartnerMetadataCtx>) -> Result<()> {
+        instructions::handle_close_partner_metadata(ctx)
+    }
+
     pub fn create_config(
         ctx: Context<CreateConfigCtx>,
         config_parameters: ConfigParameters,
     ) -> Result<()> {
         instructions::handle_create_config(ctx, config_parameters)
     }
+
+    pub fn close_config(ctx: Context<CloseConfigCtx>) -> Result<()> {
+        instructions::handle_close_config(ctx)
+    }
     pub fn claim_trading_fee(
         ctx: Context<ClaimTradingFeesCtx>,
         max_amount_a: u64,
@@ -73,6 +93,18 @@ pub mod dynamic_bonding_curve {
         instructions::handle_partner_withdraw_surplus(ctx)
     }
 
+    /// cancel a locked vesting escrow and claw back unvested base token
+    pub fn cancel_locker(ctx: Context<CancelLockerCtx>) -> Result<()> {
+        instructions::handle_cancel_locker(ctx)
+    }
+
+    pub fn update_locker_recipient(
+        ctx: Context<UpdateLockerRecipientCtx>,
+        new_recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::handle_update_locker_recipient(ctx, new_recipient)
+    }
+
     /// POOL CREATOR FUNCTIONS ////
     pub fn initialize_virtual_pool_with_spl_token<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithSplTokenCtx<'info>>,
@@ -84,8 +116,19 @@ pub mod dynamic_bonding_curve {
     pub fn initialize_virtual_pool_with_token2022<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithToken2022Ctx<'info>>,
         params: InitializePoolParameters,
+        additional_metadata: Vec<TokenMetadataField>,
+    ) -> Result<()> {
+        instructions::handle_initialize_virtual_pool_with_token2022(
+            ctx,
+            params,
+            additional_metadata,
+        )
+    }
+
+    pub fn initialize_virtual_pool_with_existing_token<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeVirtualPoolWithExistingTokenCtx<'info>>,
     ) -> Result<()> {
-        instructions::handle_initialize_virtual_pool_with_token2022(ctx, params)
+        instructions::handle_initialize_virtual_pool_with_existing_token(ctx)
     }
 
     pub fn create_virtual_pool_metadata(
@@ -95,6 +138,17 @@ pub mod dynamic_bonding_curve {
         instructions::handle_create_virtual_pool_metadata(ctx, metadata)
     }
 
+    pub fn update_virtual_pool_metadata(
+        ctx: Context<UpdateVirtualPoolMetadataCtx>,
+        metadata: UpdateVirtualPoolMetadataParameters,
+    ) -> Result<()> {
+        instructions::handle_update_virtual_pool_metadata(ctx, metadata)
+    }
+
+    pub fn close_virtual_pool_metadata(ctx: Context<CloseVirtualPoolMetadataCtx>) -> Result<()> {
+        instructions::handle_close_virtual_pool_metadata(ctx)
+    }
+
     pub fn claim_creator_trading_fee(
         ctx: Context<ClaimCreatorTradingFeesCtx>,
         max_base_amount: u64,
@@ -114,27 +168,54 @@ pub mod dynamic_bonding_curve {
         instructions::handle_transfer_pool_creator(ctx)
     }
 
+    pub fn update_token_metadata_fields(
+        ctx: Context<UpdateTokenMetadataFieldsCtx>,
+        fields: Vec<TokenMetadataField>,
+    ) -> Result<()> {
+        instructions::handle_update_token_metadata_fields(ctx, fields)
+    }
+
     /// BOTH partner and creator FUNCTIONS ///
     pub fn withdraw_migration_fee(ctx: Context<WithdrawMigrationFeeCtx>, flag: u8) -> Result<()> {
         instructions::handle_withdraw_migration_fee(ctx, flag)
     }
 
     /// TRADING BOTS FUNCTIONS ////
-    pub fn swap(ctx: Context<SwapCtx>, params: SwapParameters) -> Result<()> {
+    pub fn swap<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapCtx<'info>>,
+        params: SwapParameters,
+    ) -> Result<()> {
         instructions::handle_swap(ctx, params)
     }
 
     /// PERMISSIONLESS FUNCTIONS ///
     /// create locker
-    pub fn create_locker(ctx: Context<CreateLockerCtx>) -> Result<()> {
+    pub fn create_locker<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CreateLockerCtx<'info>>,
+    ) -> Result<()> {
         instructions::handle_create_locker(ctx)
     }
 
+    /// start refund of a pool that didn't complete the curve before the refund deadline
+    pub fn start_refund(ctx: Context<StartRefundCtx>) -> Result<()> {
+        instructions::handle_start_refund(ctx)
+    }
+
+    /// redeem base token for a pro-rata share of the quote token of a refunding pool
+    pub fn redeem_refund(ctx: Context<RedeemRefundCtx>, base_amount: u64) -> Result<()> {
+        instructions::handle_redeem_refund(ctx, base_amount)
+    }
+
     // withdraw leftover on base token, can only call after pool is initialized
     pub fn withdraw_leftover(ctx: Context<WithdrawLeftoverCtx>) -> Result<()> {
         instructions::handle_withdraw_leftover(ctx)
     }
 
+    /// thaw a base token account created while the base mint was non-transferable, after migration
+    pub fn thaw_base_token_account(ctx: Context<ThawBaseTokenAccountCtx>) -> Result<()> {
+        instructions::handle_thaw_base_token_account(ctx)
+    }
+
     /// migrate damm v1
     pub fn migration_meteora_damm_create_metadata<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, MigrationMeteoraDammCreateMetadataCtx<'info>>,
@@ -172,4 +253,30 @@ pub mod dynamic_bonding_curve {
     ) -> Result<()> {
         instructions::handle_migrate_damm_v2(ctx)
     }
+
+    // migrate dlmm
+    pub fn migration_dlmm_create_metadata<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrationDlmmCreateMetadataCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_migration_dlmm_create_metadata(ctx)
+    }
+
+    pub fn migration_dlmm<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrateDlmmCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_migrate_dlmm(ctx)
+    }
+
+    pub fn dlmm_claim_position_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, DlmmClaimPositionFeeCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_dlmm_claim_position_fee(ctx)
+    }
+
+    // migrate to external program
+    pub fn migrate_external_program<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrateExternalProgramCtx<'info>>,
+    ) -> Result<()> {
+        instructions::handle_migrate_external_program(ctx)
+    }
 }
@@ -10,3 +10,30 @@ macro_rules! base_locker_seeds {
         &[b"base_locker".as_ref(), $virtual_pool.as_ref(), &[$bump]]
     };
 }
+
+macro_rules! collection_authority_seeds {
+    ($config:expr, $bump:expr) => {
+        &[b"collection_authority".as_ref(), $config.as_ref(), &[$bump]]
+    };
+}
+
+macro_rules! external_migration_signer_seeds {
+    ($virtual_pool:expr, $bump:expr) => {
+        &[
+            b"migration_signer".as_ref(),
+            $virtual_pool.as_ref(),
+            &[$bump],
+        ]
+    };
+}
+
+macro_rules! base_locker_tranche_seeds {
+    ($virtual_pool:expr, $tranche_index:expr, $bump:expr) => {
+        &[
+            b"base_locker".as_ref(),
+            $virtual_pool.as_ref(),
+            &[$tranche_index],
+            &[$bump],
+        ]
+    };
+}
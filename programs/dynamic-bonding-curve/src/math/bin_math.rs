@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use ruint::aliases::U256;
+
+use crate::{
+    constants::{
+        dlmm::{BIN_ARRAY_BITMAP_SIZE, MAX_BIN_ID, MAX_BIN_PER_ARRAY, MAX_BIN_STEP, MIN_BIN_ID},
+        BASIS_POINT_MAX, ONE_Q64,
+    },
+    safe_math::SafeMath,
+    u128x128_math::{mul_shr, shl_div, Rounding},
+    PoolError,
+};
+
+// fractional bits of log2 estimation, enough to find the bin of the smallest bin step up to a few bins
+const LOG2_FRACTION_BITS: u32 = 40;
+
+/// 1 + bin_step / BASIS_POINT_MAX in Q64.64
+fn get_bin_base(bin_step: u16) -> Result<u128> {
+    require!(
+        bin_step > 0 && bin_step <= MAX_BIN_STEP,
+        PoolError::InvalidDlmmConfig
+    );
+    let bin_step_in_u128 = (u128::from(bin_step) << 64).safe_div(BASIS_POINT_MAX.into())?;
+    Ok(ONE_Q64.safe_add(bin_step_in_u128)?)
+}
+
+/// Price of a dlmm bin in Q64.64, (1 + bin_step / BASIS_POINT_MAX) ^ bin_id
+pub fn get_price_from_id(bin_id: i32, bin_step: u16) -> Result<u128> {
+    let base = get_bin_base(bin_step)?;
+
+    let mut result = ONE_Q64;
+    let mut square = base;
+    let mut exponent = bin_id.unsigned_abs();
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_shr(result, square, 64).ok_or(PoolError::MathOverflow)?;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            square = mul_shr(square, square, 64).ok_or(PoolError::MathOverflow)?;
+        }
+    }
+
+    if bin_id < 0 {
+        result = shl_div(ONE_Q64, result, 64, Rounding::Down).ok_or(PoolError::MathOverflow)?;
+    }
+    require!(result > 0, PoolError::MathOverflow);
+    Ok(result)
+}
+
+/// log2 of a Q64.64 value, in Q64.64, truncated to LOG2_FRACTION_BITS fractional bits
+fn log2_q64(value: U256) -> Result<i128> {
+    require!(value > U256::ZERO, PoolError::MathOverflow);
+    let msb = value.bit_len() - 1;
+    let integer_part = i128::try_from(msb).map_err(|_| PoolError::TypeCastFailed)? - 64;
+
+    // normalize value to [1, 2) in Q1.63
+    let normalized = if msb >= 63 {
+        // truncating lower bits
+        value >> (msb - 63)
+    } else {
+        value.safe_shl(63 - msb)?
+    };
+    let mut y: u128 = normalized
+        .try_into()
+        .map_err(|_| PoolError::TypeCastFailed)?;
+
+    let mut fraction: i128 = 0;
+    for i in 1..=LOG2_FRACTION_BITS {
+        y = y.safe_mul(y)? >> 63;
+        if y >= 1 << 64 {
+            y >>= 1;
+            fraction |= 1 << (64 - i);
+        }
+    }
+    Ok((integer_part << 64) + fraction)
+}
+
+/// Largest dlmm bin id whose price is smaller than or equal to the price of sqrt_price
+pub fn get_id_from_sqrt_price(sqrt_price: u128, bin_step: u16) -> Result<i32> {
+    let price = U256::from(sqrt_price).safe_mul(U256::from(sqrt_price))? >> 64;
+
+    // estimate with log, then adjust the estimation as log2 is truncated
+    let log2_price = log2_q64(price)?;
+    let log2_base = log2_q64(U256::from(get_bin_base(bin_step)?))?;
+    let mut bin_id: i32 = log2_price
+        .div_euclid(log2_base)
+        .clamp(MIN_BIN_ID.into(), MAX_BIN_ID.into())
+        .try_into()
+        .map_err(|_| PoolError::TypeCastFailed)?;
+
+    while U256::from(get_price_from_id(bin_id, bin_step)?) > price {
+        bin_id = bin_id.safe_sub(1)?;
+    }
+    while bin_id < MAX_BIN_ID
+        && get_price_from_id(bin_id.safe_add(1)?, bin_step)
+            .is_ok_and(|next_price| U256::from(next_price) <= price)
+    {
+        bin_id = bin_id.safe_add(1)?;
+    }
+
+    require!(
+        (MIN_BIN_ID..=MAX_BIN_ID).contains(&bin_id),
+        PoolError::InvalidDlmmConfig
+    );
+    Ok(bin_id)
+}
+
+/// Index of the dlmm bin array containing the bin
+pub fn get_bin_array_index(bin_id: i32) -> i64 {
+    i64::from(bin_id.div_euclid(MAX_BIN_PER_ARRAY))
+}
+
+/// Whether the bin is out of the bin array bitmap of the lb pair, so a bitmap extension is required
+pub fn is_overflow_default_bin_array_bitmap(bin_id: i32) -> bool {
+    let bin_array_index = get_bin_array_index(bin_id);
+    !(-BIN_ARRAY_BITMAP_SIZE..BIN_ARRAY_BITMAP_SIZE).contains(&bin_array_index)
+}
+
+/// Dlmm base factor for the base fee, dlmm base fee in bps is bin_step * base_factor / BASIS_POINT_MAX
+pub fn get_base_factor(base_fee_bps: u64, bin_step: u16) -> Result<u16> {
+    require!(bin_step > 0, PoolError::InvalidDlmmConfig);
+    let numerator = base_fee_bps.safe_mul(BASIS_POINT_MAX)?;
+    let bin_step = u64::from(bin_step);
+    require!(
+        numerator.safe_rem(bin_step)? == 0,
+        PoolError::InvalidDlmmConfig
+    );
+    u16::try_from(numerator.safe_div(bin_step)?).map_err(|_| PoolError::InvalidDlmmConfig.into())
+}
+
+/// Base token amount worth the quote amount at the price of the active bin, rounded up
+pub fn get_dlmm_migration_base_token(
+    quote_amount: u64,
+    active_id: i32,
+    bin_step: u16,
+) -> Result<u64> {
+    let price = get_price_from_id(active_id, bin_step)?;
+    let base_amount =
+        shl_div(quote_amount.into(), price, 64, Rounding::Up).ok_or(PoolError::MathOverflow)?;
+    u64::try_from(base_amount).map_err(|_| PoolError::MathOverflow.into())
+}
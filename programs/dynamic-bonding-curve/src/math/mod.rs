@@ -1,5 +1,7 @@
 //! Math module
 
+/// dlmm bin math
+pub mod bin_math;
 /// fee math
 pub mod fee_math;
 /// safe math
@@ -1,11 +1,13 @@
 //! Fees module includes information about fee charges
 use crate::activation_handler::ActivationType;
-use crate::base_fee::get_base_fee_handler;
+use crate::base_fee::{get_base_fee_handler, BaseFeeHandler, FeeScheduler};
 use crate::constants::fee::{HOST_FEE_PERCENT, MAX_BASIS_POINT, PROTOCOL_FEE_PERCENT};
 use crate::constants::{BASIS_POINT_MAX, BIN_STEP_BPS_DEFAULT, BIN_STEP_BPS_U128_DEFAULT, U24_MAX};
 use crate::error::PoolError;
 use crate::safe_math::SafeMath;
-use crate::state::{BaseFeeConfig, DynamicFeeConfig, PoolFeesConfig};
+use crate::state::{
+    BaseFeeConfig, BaseFeeMode, DynamicFeeConfig, MigratedPoolFeeConfig, PoolFeesConfig,
+};
 use anchor_lang::prelude::*;
 
 /// Information regarding fee charges
@@ -88,7 +90,7 @@ pub struct DynamicFeeParameters {
 }
 
 impl DynamicFeeParameters {
-    fn to_dynamic_fee_config(&self) -> DynamicFeeConfig {
+    pub fn to_dynamic_fee_config(&self) -> DynamicFeeConfig {
         DynamicFeeConfig {
             initialized: 1,
             bin_step: self.bin_step,
@@ -194,3 +196,72 @@ impl PoolFeeParameters {
         Ok(())
     }
 }
+
+/// Fees of the damm v2 pool created at migration, used to create a customizable pool instead of a damm v2 config key
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Default)]
+pub struct MigratedPoolFeeParameters {
+    pub cliff_fee_numerator: u64,
+    pub number_of_period: u16,
+    pub period_frequency: u64,
+    pub reduction_factor: u64,
+    /// fee scheduler mode of damm v2, linear (0) or exponential (1)
+    pub fee_scheduler_mode: u8,
+    /// collect fee mode of damm v2, both token (0) or only quote token (1)
+    pub collect_fee_mode: u8,
+    /// percent of protocol fee of damm v2, net of referral fee, going to partner, up to 100
+    pub partner_fee_percent: u8,
+    pub dynamic_fee: Option<DynamicFeeParameters>,
+}
+
+impl MigratedPoolFeeParameters {
+    pub fn validate(&self, activation_type: ActivationType) -> Result<()> {
+        let fee_scheduler_mode = BaseFeeMode::try_from(self.fee_scheduler_mode)
+            .map_err(|_| PoolError::InvalidMigratedPoolFee)?;
+        require!(
+            fee_scheduler_mode == BaseFeeMode::FeeSchedulerLinear
+                || fee_scheduler_mode == BaseFeeMode::FeeSchedulerExponential,
+            PoolError::InvalidMigratedPoolFee
+        );
+        require!(
+            self.collect_fee_mode <= 1,
+            PoolError::InvalidMigratedPoolFee
+        );
+        require!(
+            self.partner_fee_percent <= 100,
+            PoolError::InvalidMigratedPoolFee
+        );
+
+        let fee_scheduler = FeeScheduler {
+            cliff_fee_numerator: self.cliff_fee_numerator,
+            number_of_period: self.number_of_period,
+            period_frequency: self.period_frequency,
+            reduction_factor: self.reduction_factor,
+            fee_scheduler_mode: self.fee_scheduler_mode,
+        };
+        fee_scheduler.validate(self.collect_fee_mode, activation_type)?;
+
+        if let Some(dynamic_fee) = self.dynamic_fee {
+            dynamic_fee.validate()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_migrated_pool_fee_config(&self) -> MigratedPoolFeeConfig {
+        MigratedPoolFeeConfig {
+            cliff_fee_numerator: self.cliff_fee_numerator,
+            period_frequency: self.period_frequency,
+            reduction_factor: self.reduction_factor,
+            number_of_period: self.number_of_period,
+            fee_scheduler_mode: self.fee_scheduler_mode,
+            collect_fee_mode: self.collect_fee_mode,
+            initialized: 1,
+            partner_fee_percent: self.partner_fee_percent,
+            dynamic_fee: self
+                .dynamic_fee
+                .map(|dynamic_fee| dynamic_fee.to_dynamic_fee_config())
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+}
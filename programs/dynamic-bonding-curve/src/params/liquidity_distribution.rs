@@ -4,7 +4,8 @@ use anchor_lang::prelude::*;
 use ruint::aliases::U256;
 
 use crate::{
-    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    bin_math::{get_dlmm_migration_base_token, get_id_from_sqrt_price},
+    constants::{BASIS_POINT_MAX, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
     curve::{
         get_delta_amount_base_unsigned_256, get_delta_amount_quote_unsigned_256,
         get_initial_liquidity_from_delta_quote, get_next_sqrt_price_from_input,
@@ -67,16 +68,67 @@ pub fn get_base_token_for_swap(
     Ok(total_amount)
 }
 
+/// Price range of the damm v2 pool created at migration, in bps of the migration price
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Default)]
+pub struct MigrationPriceRange {
+    /// min price in bps of migration price, below 10_000
+    pub min_price_bps: u32,
+    /// max price in bps of migration price, above 10_000
+    pub max_price_bps: u32,
+}
+
+impl MigrationPriceRange {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.min_price_bps > 0
+                && u64::from(self.min_price_bps) < BASIS_POINT_MAX
+                && u64::from(self.max_price_bps) > BASIS_POINT_MAX,
+            PoolError::InvalidMigrationPriceRange
+        );
+        Ok(())
+    }
+
+    /// Sqrt min and max price of the range, which must be within damm v2 price bounds
+    pub fn get_sqrt_price_range(&self, sqrt_migration_price: u128) -> Result<(u128, u128)> {
+        let sqrt_min_price = get_sqrt_price_from_bps(sqrt_migration_price, self.min_price_bps)?;
+        let sqrt_max_price = get_sqrt_price_from_bps(sqrt_migration_price, self.max_price_bps)?;
+        require!(
+            sqrt_min_price >= MIN_SQRT_PRICE
+                && sqrt_max_price <= MAX_SQRT_PRICE
+                && sqrt_min_price < sqrt_migration_price
+                && sqrt_migration_price < sqrt_max_price,
+            PoolError::InvalidMigrationPriceRange
+        );
+        Ok((sqrt_min_price, sqrt_max_price))
+    }
+}
+
+/// sqrt(price * bps / 10_000), rounded down
+fn get_sqrt_price_from_bps(sqrt_price: u128, bps: u32) -> Result<u128> {
+    let sqrt_price = U256::from(sqrt_price);
+    let price = sqrt_price
+        .safe_mul(sqrt_price)?
+        .safe_mul(U256::from(bps))?
+        .safe_div(U256::from(BASIS_POINT_MAX))?;
+    Ok(price
+        .root(2)
+        .try_into()
+        .map_err(|_| PoolError::TypeCastFailed)?)
+}
+
 pub fn get_migration_base_token(
     migration_threshold: u64,
     migration_fee_percentage: u8,
     sqrt_migration_price: u128,
     migration_option: MigrationOption,
+    dlmm_bin_step: u16,
+    migration_sqrt_min_price: u128,
+    migration_sqrt_max_price: u128,
 ) -> Result<u64> {
     let MigrationAmount { quote_amount, .. } =
         PoolConfig::get_migration_quote_amount(migration_threshold, migration_fee_percentage)?;
     match migration_option {
-        MigrationOption::MeteoraDamm => {
+        MigrationOption::MeteoraDamm | MigrationOption::ExternalProgram => {
             // constant product
             let sqrt_migration_price = U256::from(sqrt_migration_price);
             // price = quote / base for constant-product
@@ -95,13 +147,13 @@ pub fn get_migration_base_token(
             // calculate to L firsty
             let liquidity = get_initial_liquidity_from_delta_quote(
                 quote_amount,
-                MIN_SQRT_PRICE,
+                migration_sqrt_min_price,
                 sqrt_migration_price,
             )?;
             // calculate base threshold
             let base_amount = get_delta_amount_base_unsigned_256(
                 sqrt_migration_price,
-                MAX_SQRT_PRICE,
+                migration_sqrt_max_price,
                 liquidity,
                 Rounding::Up,
             )?;
@@ -114,8 +166,8 @@ pub fn get_migration_base_token(
             #[cfg(feature = "local")]
             {
                 let (_initial_base_amount, initial_quote_amount) = get_initialize_amounts(
-                    MIN_SQRT_PRICE,
-                    MAX_SQRT_PRICE,
+                    migration_sqrt_min_price,
+                    migration_sqrt_max_price,
                     sqrt_migration_price,
                     liquidity,
                 )?;
@@ -128,6 +180,11 @@ pub fn get_migration_base_token(
             }
             Ok(base_amount)
         }
+        MigrationOption::Dlmm => {
+            // quote is placed in bins below and base in bins above the active bin, both worth the same at the active bin price
+            let active_id = get_id_from_sqrt_price(sqrt_migration_price, dlmm_bin_step)?;
+            get_dlmm_migration_base_token(quote_amount, active_id, dlmm_bin_step)
+        }
     }
 }
 
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
+use locker::types::CreateVestingEscrowParameters;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use ruint::aliases::U256;
 use static_assertions::const_assert_eq;
@@ -6,15 +8,18 @@ use static_assertions::const_assert_eq;
 use crate::{
     base_fee::{get_base_fee_handler, FeeRateLimiter},
     constants::{
-        fee::{FEE_DENOMINATOR, MAX_FEE_NUMERATOR},
+        fee::{FEE_DENOMINATOR, MAX_FEE_NUMERATOR, MAX_MIGRATION_BASE_FEE_BPS, MIN_FEE_BPS},
         MAX_CURVE_POINT_CONFIG, MAX_SQRT_PRICE, MAX_SWALLOW_PERCENTAGE, SWAP_BUFFER_PERCENTAGE,
     },
+    get_total_locked_vesting_amount,
     params::{
         fee_parameters::PoolFeeParameters,
         liquidity_distribution::{get_base_token_for_swap, LiquidityDistributionParameters},
         swap::TradeDirection,
     },
     safe_math::SafeMath,
+    state::PoolConfigExtension,
+    token::get_transfer_fee_included_amount,
     u128x128_math::Rounding,
     utils_math::{safe_mul_div_cast_u128, safe_mul_div_cast_u64},
     LockedVestingParams, MigrationFee, PoolError,
@@ -256,11 +261,25 @@ pub struct LockedVestingConfig {
     pub frequency: u64,
     pub number_of_period: u64,
     pub cliff_unlock_amount: u64,
-    pub _padding: u64,
+    /// LockedVestingUpdateRecipientMode of the escrow
+    pub update_recipient_mode: u8,
+    /// LockedVestingCancelMode of the escrow
+    pub cancel_mode: u8,
+    pub _padding: [u8; 6],
 }
 
 const_assert_eq!(LockedVestingConfig::INIT_SPACE, 48);
 
+/// Locked vesting of base token to a recipient other than pool creator, default recipient means no tranche
+#[zero_copy]
+#[derive(Debug, InitSpace, Default)]
+pub struct LockedVestingTrancheConfig {
+    pub recipient: Pubkey,
+    pub locked_vesting_config: LockedVestingConfig,
+}
+
+const_assert_eq!(LockedVestingTrancheConfig::INIT_SPACE, 80);
+
 impl LockedVestingConfig {
     pub fn to_locked_vesting_params(&self) -> LockedVestingParams {
         LockedVestingParams {
@@ -271,6 +290,133 @@ impl LockedVestingConfig {
             cliff_unlock_amount: self.cliff_unlock_amount,
         }
     }
+
+    pub fn set_escrow_modes(&mut self, update_recipient_mode: u8, cancel_mode: u8) {
+        self.update_recipient_mode = update_recipient_mode;
+        self.cancel_mode = cancel_mode;
+    }
+
+    pub fn get_update_recipient_mode(&self) -> Result<LockedVestingUpdateRecipientMode> {
+        let update_recipient_mode =
+            LockedVestingUpdateRecipientMode::try_from(self.update_recipient_mode)
+                .map_err(|_| PoolError::TypeCastFailed)?;
+        Ok(update_recipient_mode)
+    }
+
+    pub fn get_cancel_mode(&self) -> Result<LockedVestingCancelMode> {
+        let cancel_mode = LockedVestingCancelMode::try_from(self.cancel_mode)
+            .map_err(|_| PoolError::TypeCastFailed)?;
+        Ok(cancel_mode)
+    }
+
+    pub fn to_create_vesting_escrow_params(
+        &self,
+        vesting_start_time: u64,
+    ) -> Result<CreateVestingEscrowParameters> {
+        self.to_locked_vesting_params()
+            .to_create_vesting_escrow_params(
+                vesting_start_time,
+                self.get_update_recipient_mode()?,
+                self.get_cancel_mode()?,
+            )
+    }
+}
+
+/// Who can update the recipient of locked vesting escrows. Pool authority is the escrow creator, so partner acts
+/// as the creator through `update_locker_recipient`. Default keeps the recipient only mode of previous configs
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+    Default,
+)]
+pub enum LockedVestingUpdateRecipientMode {
+    #[default]
+    Recipient,
+    None,
+    Partner,
+    PartnerOrRecipient,
+}
+
+impl LockedVestingUpdateRecipientMode {
+    /// update recipient mode of locker escrow, 1 creator, 2 recipient, 3 either
+    pub fn to_escrow_mode(&self) -> u8 {
+        match *self {
+            LockedVestingUpdateRecipientMode::None => 0,
+            LockedVestingUpdateRecipientMode::Partner => 1,
+            LockedVestingUpdateRecipientMode::Recipient => 2,
+            LockedVestingUpdateRecipientMode::PartnerOrRecipient => 3,
+        }
+    }
+
+    pub fn is_partner_allowed(&self) -> bool {
+        matches!(
+            *self,
+            LockedVestingUpdateRecipientMode::Partner
+                | LockedVestingUpdateRecipientMode::PartnerOrRecipient
+        )
+    }
+}
+
+/// Start of locked vesting escrows, cliff duration is counted from it. Default keeps the migration start of
+/// previous configs, which is the time the curve is completed
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+    Default,
+)]
+pub enum LockedVestingStartAnchor {
+    #[default]
+    Migration,
+    Activation,
+    Timestamp,
+}
+
+/// Who can cancel locked vesting escrows, the unvested token goes to partner. Pool authority is the escrow
+/// creator, so partner acts as the creator through `cancel_locker`
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+    Default,
+)]
+pub enum LockedVestingCancelMode {
+    #[default]
+    None,
+    Partner,
+}
+
+impl LockedVestingCancelMode {
+    /// cancel mode of locker escrow, 0 none, 1 creator
+    pub fn to_escrow_mode(&self) -> u8 {
+        match *self {
+            LockedVestingCancelMode::None => 0,
+            LockedVestingCancelMode::Partner => 1,
+        }
+    }
+
+    pub fn is_partner_allowed(&self) -> bool {
+        *self == LockedVestingCancelMode::Partner
+    }
 }
 
 #[repr(u8)]
@@ -305,6 +451,8 @@ pub enum TokenUpdateAuthorityOption {
 pub enum MigrationOption {
     MeteoraDamm,
     DammV2,
+    Dlmm,
+    ExternalProgram,
 }
 
 #[repr(u8)]
@@ -323,6 +471,22 @@ pub enum TokenType {
     Token2022,
 }
 
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum PoolCreationFeeToken {
+    Sol,
+    QuoteToken,
+}
+
 #[repr(u8)]
 #[derive(
     Clone,
@@ -335,38 +499,75 @@ pub enum TokenType {
     AnchorSerialize,
 )]
 pub enum MigrationFeeOption {
-    FixedBps25,  // 0.25%
-    FixedBps30,  // 0.3%
-    FixedBps100, // 1%
-    FixedBps200, // 2%
-    FixedBps400, // 4%
-    FixedBps600, // 6%
+    FixedBps25,   // 0.25%
+    FixedBps30,   // 0.3%
+    FixedBps100,  // 1%
+    FixedBps200,  // 2%
+    FixedBps400,  // 4%
+    FixedBps600,  // 6%
+    Customizable, // migration_base_fee_bps of config
 }
 
 impl MigrationFeeOption {
-    pub fn validate_base_fee(&self, base_fee_bps: u64) -> Result<()> {
+    /// Base fee bps of the migrated pool, `migration_base_fee_bps` is only used by customizable option
+    pub fn get_base_fee_bps(&self, migration_base_fee_bps: u16) -> u64 {
         match *self {
-            MigrationFeeOption::FixedBps25 => {
-                require!(base_fee_bps == 25, PoolError::InvalidMigrationFeeOption);
-            }
-            MigrationFeeOption::FixedBps30 => {
-                require!(base_fee_bps == 30, PoolError::InvalidMigrationFeeOption);
-            }
-            MigrationFeeOption::FixedBps100 => {
-                require!(base_fee_bps == 100, PoolError::InvalidMigrationFeeOption);
-            }
-            MigrationFeeOption::FixedBps200 => {
-                require!(base_fee_bps == 200, PoolError::InvalidMigrationFeeOption);
-            }
-            MigrationFeeOption::FixedBps400 => {
-                require!(base_fee_bps == 400, PoolError::InvalidMigrationFeeOption);
-            }
-            MigrationFeeOption::FixedBps600 => {
-                require!(base_fee_bps == 600, PoolError::InvalidMigrationFeeOption);
-            }
+            MigrationFeeOption::FixedBps25 => 25,
+            MigrationFeeOption::FixedBps30 => 30,
+            MigrationFeeOption::FixedBps100 => 100,
+            MigrationFeeOption::FixedBps200 => 200,
+            MigrationFeeOption::FixedBps400 => 400,
+            MigrationFeeOption::FixedBps600 => 600,
+            MigrationFeeOption::Customizable => migration_base_fee_bps.into(),
         }
+    }
+
+    pub fn validate_base_fee(&self, base_fee_bps: u64, migration_base_fee_bps: u16) -> Result<()> {
+        require!(
+            base_fee_bps == self.get_base_fee_bps(migration_base_fee_bps),
+            PoolError::InvalidMigrationFeeOption
+        );
         Ok(())
     }
+
+    /// Customizable option takes base fee bps within damm supported bounds, fixed options take 0
+    pub fn validate_migration_base_fee_bps(&self, migration_base_fee_bps: u16) -> Result<()> {
+        if *self == MigrationFeeOption::Customizable {
+            require!(
+                (MIN_FEE_BPS..=MAX_MIGRATION_BASE_FEE_BPS)
+                    .contains(&u64::from(migration_base_fee_bps)),
+                PoolError::InvalidMigrationFeeOption
+            );
+        } else {
+            require!(
+                migration_base_fee_bps == 0,
+                PoolError::InvalidMigrationFeeOption
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Partner and creator trading fees of a pool refunding base token holders
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+    Default,
+)]
+pub enum RefundFeeMode {
+    /// partner and creator keep their unclaimed trading fees
+    #[default]
+    KeepTradingFee,
+    /// unclaimed partner and creator quote trading fees are refunded to holders, unclaimed base trading fees are
+    /// forfeited
+    RefundTradingFee,
 }
 
 #[account(zero_copy)]
@@ -414,8 +615,12 @@ pub struct PoolConfig {
     pub migration_fee_percentage: u8,
     /// creator migration fee percentage
     pub creator_migration_fee_percentage: u8,
+    /// flag to indicate whether admin has deprecated the config, no pool can be created on a deprecated config
+    pub is_deprecated: u8,
     /// padding 1
-    pub _padding_1: [u8; 7],
+    pub _padding_1: [u8; 2],
+    /// number of pools created on the config
+    pub pool_count: u32,
     /// swap base amount
     pub swap_base_amount: u64,
     /// migration quote threshold (in quote token)
@@ -430,8 +635,8 @@ pub struct PoolConfig {
     pub pre_migration_token_supply: u64,
     /// post migration token supply
     pub post_migration_token_supply: u64,
-    /// padding 2
-    pub _padding_2: [u128; 2],
+    /// authority that can close the config, default pubkey means the config can't be closed
+    pub close_authority: Pubkey,
     /// minimum price
     pub sqrt_start_price: u128,
     /// curve, only use 20 point firstly, we can extend that latter
@@ -442,6 +647,10 @@ pub struct PoolConfig {
 
 const_assert_eq!(PoolConfig::INIT_SPACE, 1040);
 
+/// Data of a closed config account. The account stays owned by the program with this discriminator, so the config
+/// address can't be initialized again
+pub const CLOSED_CONFIG_DISCRIMINATOR: [u8; 8] = [255; 8];
+
 #[zero_copy]
 #[derive(InitSpace, Debug, Default)]
 pub struct LiquidityDistributionConfig {
@@ -489,11 +698,13 @@ impl PoolConfig {
         pre_migration_token_supply: u64,
         post_migration_token_supply: u64,
         curve: &Vec<LiquidityDistributionParameters>,
+        close_authority: &Pubkey,
     ) {
         self.version = 0;
         self.quote_mint = *quote_mint;
         self.fee_claimer = *fee_claimer;
         self.leftover_receiver = *leftover_receiver;
+        self.close_authority = *close_authority;
         self.pool_fees = pool_fees.to_pool_fees_config();
         self.creator_trading_fee_percentage = creator_trading_fee_percentage;
         self.token_update_authority = token_update_authority;
@@ -555,8 +766,13 @@ impl PoolConfig {
         Ok(MigrationAmount { quote_amount, fee })
     }
 
-    pub fn get_migration_fee_distribution(&self) -> Result<MigrationFeeDistribution> {
+    /// Migration fee of partner and creator, after the migration crank reward reserved from migration fee
+    pub fn get_migration_fee_distribution(
+        &self,
+        migration_crank_reward: u64,
+    ) -> Result<MigrationFeeDistribution> {
         let MigrationAmount { fee, .. } = self.get_migration_quote_amount_for_config()?;
+        let fee = fee.safe_sub(migration_crank_reward)?;
 
         let creator_migration_fee = safe_mul_div_cast_u64(
             fee,
@@ -594,16 +810,55 @@ impl PoolConfig {
     pub fn get_total_token_supply(
         swap_base_amount: u64,
         migration_base_threshold: u64,
-        locked_vesting_params: &LockedVestingParams,
+        locked_vestings: &[LockedVestingParams],
     ) -> Result<u64> {
         let total_circulating_amount =
             swap_base_amount.safe_add(migration_base_threshold.into())?;
-        let total_locked_vesting_amount = locked_vesting_params.get_total_amount()?;
+        let total_locked_vesting_amount = get_total_locked_vesting_amount(locked_vestings)?;
         let total_amount = total_circulating_amount.safe_add(total_locked_vesting_amount.into())?;
         Ok(u64::try_from(total_amount).map_err(|_| PoolError::MathOverflow)?)
     }
 
-    pub fn get_initial_base_supply(&self) -> Result<u64> {
+    /// When base token charges transfer fee, migration base threshold also covers the transfer fee
+    /// when depositing migration base amount to the migrated pool and each locked vesting amount to locker
+    pub fn get_migration_base_threshold_with_transfer_fee(
+        migration_base_amount: u64,
+        locked_vestings: &[LockedVestingParams],
+        base_transfer_fee: Option<&TransferFee>,
+    ) -> Result<u64> {
+        let mut migration_base_threshold = migration_base_amount.safe_add(
+            get_transfer_fee_included_amount(base_transfer_fee, migration_base_amount)?
+                .transfer_fee,
+        )?;
+        for locked_vesting in locked_vestings {
+            let locked_vesting_transfer_fee = get_transfer_fee_included_amount(
+                base_transfer_fee,
+                locked_vesting.get_total_amount()?,
+            )?
+            .transfer_fee;
+            migration_base_threshold =
+                migration_base_threshold.safe_add(locked_vesting_transfer_fee)?;
+        }
+        Ok(migration_base_threshold)
+    }
+
+    /// Locked vesting of pool creator followed by the locked vesting tranches, each creates a locker escrow
+    pub fn get_locked_vestings(
+        &self,
+        config_extension: &PoolConfigExtension,
+    ) -> Vec<LockedVestingParams> {
+        let mut locked_vestings = vec![];
+        let locked_vesting = self.locked_vesting_config.to_locked_vesting_params();
+        if locked_vesting.has_vesting() {
+            locked_vestings.push(locked_vesting);
+        }
+        for tranche in config_extension.get_locked_vesting_tranches() {
+            locked_vestings.push(tranche.locked_vesting_config.to_locked_vesting_params());
+        }
+        locked_vestings
+    }
+
+    pub fn get_initial_base_supply(&self, config_extension: &PoolConfigExtension) -> Result<u64> {
         if self.is_fixed_token_supply() {
             Ok(self.pre_migration_token_supply)
         } else {
@@ -622,7 +877,7 @@ impl PoolConfig {
             PoolConfig::get_total_token_supply(
                 swap_amount_with_buffer,
                 self.migration_base_threshold,
-                &self.locked_vesting_config.to_locked_vesting_params(),
+                &self.get_locked_vestings(config_extension),
             )
         }
     }
@@ -647,6 +902,26 @@ impl PoolConfig {
         self.fixed_token_supply_flag == 1
     }
 
+    pub fn is_deprecated(&self) -> bool {
+        self.is_deprecated == 1
+    }
+
+    pub fn deprecate(&mut self) {
+        self.is_deprecated = 1;
+    }
+
+    pub fn increase_pool_count(&mut self) -> Result<()> {
+        self.pool_count = self.pool_count.safe_add(1)?;
+        Ok(())
+    }
+
+    /// config can only be closed by close authority when no pool has been created on it, deprecated or not. Pools
+    /// are never closed and load their config even after migration (e.g. to claim dlmm position fee or cancel
+    /// locker), so a deprecated config with pools must stay open
+    pub fn is_closable(&self) -> bool {
+        self.close_authority != Pubkey::default() && self.pool_count == 0
+    }
+
     pub fn get_lp_distribution(&self, lp_amount: u64) -> Result<LiquidityDistributionU64> {
         let partner_locked_lp = safe_mul_div_cast_u64(
             lp_amount,
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
+use static_assertions::const_assert_eq;
+
+use crate::{
+    activation_handler::get_activation_timestamp,
+    constants::{
+        fee::{HOST_FEE_PERCENT, PROTOCOL_FEE_PERCENT},
+        MAX_LOCKED_VESTING_TRANCHES, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+    },
+    safe_math::SafeMath,
+    state::{
+        DynamicFeeConfig, LockedVestingStartAnchor, LockedVestingTrancheConfig, MigrationFeeOption,
+        PoolConfig, PoolCreationFeeToken, RefundFeeMode, VirtualPool,
+    },
+    PoolError,
+};
+
+/// Offset of the extension in config account data, right after discriminator and PoolConfig
+pub const CONFIG_EXTENSION_OFFSET: usize = 8 + PoolConfig::INIT_SPACE;
+
+/// Extra config fields stored after PoolConfig in the config account.
+/// Configs created before the extension was introduced don't have it, and are read as default
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct PoolConfigExtension {
+    /// authority that must approve pool creation, default pubkey means anyone can create pool
+    pub pool_creator_authority: Pubkey,
+    /// address to receive pool creation fee
+    pub pool_creation_fee_recipient: Pubkey,
+    /// fee charged from payer when a pool is created on the config
+    pub pool_creation_fee: u64,
+    /// token of pool creation fee, SOL (0) or quote token (1)
+    pub pool_creation_fee_token: u8,
+    /// migrate to damm v2 in the swap completing the curve, 0 or 1
+    pub auto_migration_flag: u8,
+    /// transfer fee basis points of token 2022 base mint, 0 means base mint doesn't have transfer fee extension
+    pub base_transfer_fee_basis_points: u16,
+    /// list pool creator as verified creator in metaplex metadata of spl token base mint, 0 or 1
+    pub metadata_creator_flag: u8,
+    /// token accounts of token 2022 base mint are frozen until migration, 0 or 1
+    pub base_non_transferable_flag: u8,
+    /// padding 1
+    pub _padding_1: [u8; 2],
+    /// maximum transfer fee of token 2022 base mint
+    pub base_transfer_fee_maximum_fee: u64,
+    /// active bin of the dlmm pool created at migration, derived from migration price
+    pub dlmm_active_id: i32,
+    /// bin step of the dlmm pool created at migration
+    pub dlmm_bin_step: u16,
+    /// base fee bps of the pool created at migration, only for customizable migration fee option
+    pub migration_base_fee_bps: u16,
+    /// metaplex collection of spl token base mint, default pubkey means no collection
+    pub metadata_collection_mint: Pubkey,
+    /// program receiving migrated liquidity of external program migration
+    pub migration_program: Pubkey,
+    /// quote token paid from migration fee to the account executing the migration
+    pub migration_crank_reward: u64,
+    /// max divergence in bps of the price of the pool created at migration from the final price of the curve, 0
+    /// means migration price isn't checked
+    pub migration_price_tolerance_bps: u16,
+    /// start of locked vesting escrows, LockedVestingStartAnchor
+    pub locked_vesting_start_anchor: u8,
+    /// handling of partner and creator trading fees when the pool refunds, RefundFeeMode
+    pub refund_fee_mode: u8,
+    /// interest rate in bps of token 2022 base mint, 0 means base mint doesn't have interest bearing extension
+    pub base_interest_rate: i16,
+    /// padding 3
+    pub _padding_3: [u8; 2],
+    /// fees of the damm v2 pool created at migration, when initialized
+    pub migrated_pool_fee: MigratedPoolFeeConfig,
+    /// sqrt min price of the damm v2 pool created at migration, 0 means full price range
+    pub migration_sqrt_min_price: u128,
+    /// sqrt max price of the damm v2 pool created at migration, 0 means full price range
+    pub migration_sqrt_max_price: u128,
+    /// locked vesting of base token to recipients other than pool creator, each creates its own locker escrow
+    pub locked_vesting_tranches: [LockedVestingTrancheConfig; MAX_LOCKED_VESTING_TRANCHES],
+    /// unix timestamp locked vesting starts at, only for timestamp start anchor
+    pub locked_vesting_start_timestamp: u64,
+    /// duration after pool activation for the curve to complete, in slots or seconds by activation type. Past
+    /// it, the pool refunds quote token to base token holders. 0 means no refund
+    pub refund_deadline_duration: u64,
+    /// padding 5
+    pub _padding_5: [u128; 4],
+}
+
+const_assert_eq!(PoolConfigExtension::INIT_SPACE, 688);
+
+/// Fees of the damm v2 pool created at migration
+#[zero_copy]
+#[derive(InitSpace, Debug, Default)]
+pub struct MigratedPoolFeeConfig {
+    pub cliff_fee_numerator: u64,
+    pub period_frequency: u64,
+    pub reduction_factor: u64,
+    pub number_of_period: u16,
+    /// fee scheduler mode of damm v2, linear (0) or exponential (1)
+    pub fee_scheduler_mode: u8,
+    /// collect fee mode of damm v2, both token (0) or only quote token (1)
+    pub collect_fee_mode: u8,
+    /// 1 when migration creates a customizable damm v2 pool with these fees
+    pub initialized: u8,
+    /// percent of protocol fee of damm v2, net of referral fee, going to partner
+    pub partner_fee_percent: u8,
+    pub _padding: [u8; 2],
+    pub dynamic_fee: DynamicFeeConfig,
+}
+
+const_assert_eq!(MigratedPoolFeeConfig::INIT_SPACE, 80);
+
+impl MigratedPoolFeeConfig {
+    pub fn is_initialized(&self) -> bool {
+        self.initialized != 0
+    }
+
+    pub fn to_damm_v2_pool_fee_parameters(&self) -> damm_v2::types::PoolFeeParameters {
+        let dynamic_fee = if self.dynamic_fee.is_dynamic_fee_enable() {
+            Some(damm_v2::types::DynamicFeeParameters {
+                bin_step: self.dynamic_fee.bin_step,
+                bin_step_u128: self.dynamic_fee.bin_step_u128,
+                filter_period: self.dynamic_fee.filter_period,
+                decay_period: self.dynamic_fee.decay_period,
+                reduction_factor: self.dynamic_fee.reduction_factor,
+                max_volatility_accumulator: self.dynamic_fee.max_volatility_accumulator,
+                variable_fee_control: self.dynamic_fee.variable_fee_control,
+            })
+        } else {
+            None
+        };
+        damm_v2::types::PoolFeeParameters {
+            base_fee: damm_v2::types::BaseFeeParameters {
+                cliff_fee_numerator: self.cliff_fee_numerator,
+                number_of_period: self.number_of_period,
+                period_frequency: self.period_frequency,
+                reduction_factor: self.reduction_factor,
+                fee_scheduler_mode: self.fee_scheduler_mode,
+            },
+            protocol_fee_percent: PROTOCOL_FEE_PERCENT,
+            partner_fee_percent: self.partner_fee_percent,
+            referral_fee_percent: HOST_FEE_PERCENT,
+            dynamic_fee,
+        }
+    }
+}
+
+impl PoolConfigExtension {
+    pub fn load(config: &AccountLoader<PoolConfig>) -> Result<Self> {
+        let data = config.as_ref().try_borrow_data()?;
+        let end = CONFIG_EXTENSION_OFFSET + Self::INIT_SPACE;
+        if data.len() < end {
+            return Ok(Self::default());
+        }
+        Ok(bytemuck::pod_read_unaligned(
+            &data[CONFIG_EXTENSION_OFFSET..end],
+        ))
+    }
+
+    pub fn store(&self, config: &AccountLoader<PoolConfig>) -> Result<()> {
+        let mut data = config.as_ref().try_borrow_mut_data()?;
+        let end = CONFIG_EXTENSION_OFFSET + Self::INIT_SPACE;
+        require!(data.len() >= end, PoolError::InvalidAccount);
+        data[CONFIG_EXTENSION_OFFSET..end].copy_from_slice(bytemuck::bytes_of(self));
+        Ok(())
+    }
+
+    /// Base fee bps of the pool created at migration
+    pub fn get_migration_base_fee_bps(&self, migration_fee_option: u8) -> Result<u64> {
+        let migration_fee_option = MigrationFeeOption::try_from(migration_fee_option)
+            .map_err(|_| PoolError::InvalidMigrationFeeOption)?;
+        Ok(migration_fee_option.get_base_fee_bps(self.migration_base_fee_bps))
+    }
+
+    /// Sqrt price range of the damm v2 pool created at migration
+    pub fn get_migration_sqrt_price_range(&self) -> (u128, u128) {
+        if self.migration_sqrt_min_price == 0 {
+            (MIN_SQRT_PRICE, MAX_SQRT_PRICE)
+        } else {
+            (self.migration_sqrt_min_price, self.migration_sqrt_max_price)
+        }
+    }
+
+    /// Price tolerance of the pool created at migration, none when it isn't checked
+    pub fn get_migration_price_tolerance_bps(&self) -> Option<u16> {
+        if self.migration_price_tolerance_bps == 0 {
+            None
+        } else {
+            Some(self.migration_price_tolerance_bps)
+        }
+    }
+
+    /// Locked vesting tranches set by config
+    pub fn get_locked_vesting_tranches(&self) -> impl Iterator<Item = &LockedVestingTrancheConfig> {
+        self.locked_vesting_tranches
+            .iter()
+            .filter(|tranche| tranche.recipient != Pubkey::default())
+    }
+
+    /// Start time of locked vesting escrows, slot activation point is converted to a timestamp with
+    /// [get_activation_timestamp]
+    pub fn get_locked_vesting_start_time(
+        &self,
+        virtual_pool: &VirtualPool,
+        activation_type: u8,
+        clock: &Clock,
+    ) -> Result<u64> {
+        let locked_vesting_start_anchor =
+            LockedVestingStartAnchor::try_from(self.locked_vesting_start_anchor)
+                .map_err(|_| PoolError::TypeCastFailed)?;
+        let vesting_start_time = match locked_vesting_start_anchor {
+            LockedVestingStartAnchor::Migration => virtual_pool.finish_curve_timestamp,
+            LockedVestingStartAnchor::Activation => {
+                get_activation_timestamp(activation_type, virtual_pool.activation_point, clock)?
+            }
+            LockedVestingStartAnchor::Timestamp => self.locked_vesting_start_timestamp,
+        };
+        Ok(vesting_start_time)
+    }
+
+    pub fn has_refund_deadline(&self) -> bool {
+        self.refund_deadline_duration > 0
+    }
+
+    /// Whether the refund deadline of a pool activated at activation point has passed
+    pub fn is_refund_deadline_passed(
+        &self,
+        activation_point: u64,
+        current_point: u64,
+    ) -> Result<bool> {
+        if !self.has_refund_deadline() {
+            return Ok(false);
+        }
+        Ok(current_point >= activation_point.safe_add(self.refund_deadline_duration)?)
+    }
+
+    pub fn get_refund_fee_mode(&self) -> Result<RefundFeeMode> {
+        let refund_fee_mode =
+            RefundFeeMode::try_from(self.refund_fee_mode).map_err(|_| PoolError::TypeCastFailed)?;
+        Ok(refund_fee_mode)
+    }
+
+    pub fn has_pool_creator_authority(&self) -> bool {
+        self.pool_creator_authority != Pubkey::default()
+    }
+
+    pub fn is_auto_migration_enabled(&self) -> bool {
+        self.auto_migration_flag == 1
+    }
+
+    pub fn has_metadata_creator(&self) -> bool {
+        self.metadata_creator_flag == 1
+    }
+
+    pub fn get_metadata_collection_mint(&self) -> Option<Pubkey> {
+        if self.metadata_collection_mint == Pubkey::default() {
+            None
+        } else {
+            Some(self.metadata_collection_mint)
+        }
+    }
+
+    pub fn has_base_transfer_fee(&self) -> bool {
+        self.base_transfer_fee_basis_points > 0
+    }
+
+    /// Transfer fee of base mint, it is set at mint creation without transfer fee config authority so it never changes
+    pub fn get_base_transfer_fee(&self) -> Option<TransferFee> {
+        if !self.has_base_transfer_fee() {
+            return None;
+        }
+        Some(TransferFee {
+            epoch: 0.into(),
+            maximum_fee: self.base_transfer_fee_maximum_fee.into(),
+            transfer_fee_basis_points: self.base_transfer_fee_basis_points.into(),
+        })
+    }
+
+    pub fn has_base_interest_rate(&self) -> bool {
+        self.base_interest_rate != 0
+    }
+
+    pub fn is_base_non_transferable(&self) -> bool {
+        self.base_non_transferable_flag == 1
+    }
+
+    pub fn get_pool_creation_fee_token(&self) -> Result<PoolCreationFeeToken> {
+        Ok(PoolCreationFeeToken::try_from(self.pool_creation_fee_token)
+            .map_err(|_| PoolError::InvalidPoolCreationFee)?)
+    }
+}
@@ -1,5 +1,7 @@
 pub mod config;
 pub use config::*;
+pub mod config_extension;
+pub use config_extension::*;
 pub mod virtual_pool;
 pub use virtual_pool::*;
 pub mod claim_fee_operator;
@@ -1,4 +1,9 @@
-use crate::*;
+use crate::{
+    constants::{
+        MAX_POOL_METADATA_LINK_LENGTH, MAX_POOL_METADATA_LOGO_LENGTH, MAX_POOL_METADATA_NAME_LENGTH,
+    },
+    *,
+};
 
 /// Metadata for a partner.
 #[account]
@@ -18,14 +23,17 @@ pub struct PartnerMetadata {
 
 impl PartnerMetadata {
     /// Space that a [PartnerMetadata] takes up.
-    pub fn space(metadata: &CreatePartnerMetadataParameters) -> usize {
-        std::mem::size_of::<Pubkey>()
-            + 16 * 6
-            + 4
-            + metadata.name.as_bytes().len()
-            + 4
-            + metadata.website.as_bytes().len()
-            + 4
-            + metadata.logo.as_bytes().len()
+    pub fn space(name: &str, website: &str, logo: &str) -> usize {
+        std::mem::size_of::<Pubkey>() + 16 * 6 + 4 + name.len() + 4 + website.len() + 4 + logo.len()
+    }
+
+    pub fn validate(name: &str, website: &str, logo: &str) -> Result<()> {
+        require!(
+            name.len() <= MAX_POOL_METADATA_NAME_LENGTH
+                && website.len() <= MAX_POOL_METADATA_LINK_LENGTH
+                && logo.len() <= MAX_POOL_METADATA_LOGO_LENGTH,
+            PoolError::InvalidPartnerMetadata
+        );
+        Ok(())
     }
 }
@@ -16,7 +16,7 @@ use crate::{
     safe_math::SafeMath,
     state::{
         fee::{FeeMode, FeeOnAmountResult, VolatilityTracker},
-        PoolConfig,
+        PoolConfig, RefundFeeMode,
     },
     u128x128_math::Rounding,
     utils_math::safe_mul_div_cast_u64,
@@ -66,6 +66,9 @@ pub enum PoolType {
 //
 // 2. With jup lock
 //    PreBonding -> PostBonding -> LockedVesting -> CreatedPool
+//
+// 3. Curve not completed before the refund deadline of config
+//    PreBonding -> Refunding
 #[repr(u8)]
 #[derive(
     Clone,
@@ -82,6 +85,7 @@ pub enum MigrationProgress {
     PostBondingCurve,
     LockedVesting,
     CreatedPool,
+    Refunding,
 }
 
 #[account(zero_copy)]
@@ -139,8 +143,14 @@ pub struct VirtualPool {
     pub creator_base_fee: u64,
     /// creator quote fee
     pub creator_quote_fee: u64,
+    /// base token left to redeem when refunding
+    pub refund_base_amount: u64,
+    /// quote token left to pay base token redeemed when refunding
+    pub refund_quote_amount: u64,
+    /// payer of `create_locker`, receives the rent of escrow token accounts closed by `cancel_locker`
+    pub locker_payer: Pubkey,
     /// Padding for further use
-    pub _padding_1: [u64; 7],
+    pub _padding_1: [u64; 1],
 }
 
 const_assert_eq!(VirtualPool::INIT_SPACE, 416);
@@ -639,6 +649,52 @@ impl VirtualPool {
         self.migration_fee_withdraw_status = self.migration_fee_withdraw_status.bitxor(mask)
     }
 
+    /// Refund quote reserve to holders of base token out of base reserve, after the curve didn't complete before
+    /// the refund deadline
+    pub fn start_refund(
+        &mut self,
+        initial_base_supply: u64,
+        refund_fee_mode: RefundFeeMode,
+    ) -> Result<()> {
+        let mut refund_base_amount = initial_base_supply.safe_sub(self.base_reserve)?;
+        let mut refund_quote_amount = self.quote_reserve;
+        if refund_fee_mode == RefundFeeMode::RefundTradingFee {
+            // forfeited base fee stays in base vault, so it isn't redeemable
+            refund_base_amount = refund_base_amount
+                .safe_sub(self.partner_base_fee)?
+                .safe_sub(self.creator_base_fee)?;
+            refund_quote_amount = refund_quote_amount
+                .safe_add(self.partner_quote_fee)?
+                .safe_add(self.creator_quote_fee)?;
+            self.partner_base_fee = 0;
+            self.partner_quote_fee = 0;
+            self.creator_base_fee = 0;
+            self.creator_quote_fee = 0;
+        }
+        self.refund_base_amount = refund_base_amount;
+        self.refund_quote_amount = refund_quote_amount;
+        self.set_migration_progress(MigrationProgress::Refunding.into());
+        Ok(())
+    }
+
+    /// Redeem base token for its pro-rata share of the quote token left, returns the quote amount
+    pub fn redeem_refund(&mut self, base_amount: u64) -> Result<u64> {
+        require!(base_amount > 0, PoolError::AmountIsZero);
+        require!(
+            base_amount <= self.refund_base_amount,
+            PoolError::InvalidInput
+        );
+        let quote_amount = safe_mul_div_cast_u64(
+            base_amount,
+            self.refund_quote_amount,
+            self.refund_base_amount,
+            Rounding::Down,
+        )?;
+        self.refund_base_amount = self.refund_base_amount.safe_sub(base_amount)?;
+        self.refund_quote_amount = self.refund_quote_amount.safe_sub(quote_amount)?;
+        Ok(quote_amount)
+    }
+
     pub fn get_migration_progress(&self) -> Result<MigrationProgress> {
         let migration_progress = MigrationProgress::try_from(self.migration_progress)
             .map_err(|_| PoolError::TypeCastFailed)?;
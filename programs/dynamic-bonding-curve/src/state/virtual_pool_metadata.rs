@@ -1,4 +1,102 @@
-use crate::*;
+use std::io::Read;
+
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::{
+    constants::{
+        MAX_POOL_METADATA_DESCRIPTION_LENGTH, MAX_POOL_METADATA_LINK_LENGTH,
+        MAX_POOL_METADATA_LOGO_LENGTH, MAX_POOL_METADATA_NAME_LENGTH, MAX_POOL_METADATA_TAGS,
+        MAX_POOL_METADATA_TAG_LENGTH,
+    },
+    *,
+};
+
+#[repr(u8)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    IntoPrimitive,
+    TryFromPrimitive,
+    AnchorDeserialize,
+    AnchorSerialize,
+)]
+pub enum VirtualPoolMetadataVersion {
+    /// name, website and logo only
+    V0,
+    /// name, website and logo with socials
+    V1,
+}
+
+/// Social fields of a virtual pool, added in [VirtualPoolMetadataVersion::V1]
+#[derive(AnchorSerialize, Clone, Debug, Default, PartialEq)]
+pub struct VirtualPoolSocials {
+    /// Description of project.
+    pub description: String,
+    /// Twitter of project.
+    pub twitter: String,
+    /// Telegram of project.
+    pub telegram: String,
+    /// Discord of project.
+    pub discord: String,
+    /// Tags of project.
+    pub tags: Vec<String>,
+}
+
+// accounts created with the V0 layout end right after logo, socials are read as default for them
+impl AnchorDeserialize for VirtualPoolSocials {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut description_length = [0u8; 4];
+        let read = reader.read(&mut description_length)?;
+        if read == 0 {
+            return Ok(Self::default());
+        }
+        reader.read_exact(&mut description_length[read..])?;
+
+        let mut reader = (&description_length[..]).chain(reader);
+        Ok(Self {
+            description: String::deserialize_reader(&mut reader)?,
+            twitter: String::deserialize_reader(&mut reader)?,
+            telegram: String::deserialize_reader(&mut reader)?,
+            discord: String::deserialize_reader(&mut reader)?,
+            tags: Vec::<String>::deserialize_reader(&mut reader)?,
+        })
+    }
+}
+
+impl VirtualPoolSocials {
+    pub fn space(&self) -> usize {
+        4 + self.description.len()
+            + 4
+            + self.twitter.len()
+            + 4
+            + self.telegram.len()
+            + 4
+            + self.discord.len()
+            + 4
+            + self.tags.iter().map(|tag| 4 + tag.len()).sum::<usize>()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.description.len() <= MAX_POOL_METADATA_DESCRIPTION_LENGTH
+                && self.twitter.len() <= MAX_POOL_METADATA_LINK_LENGTH
+                && self.telegram.len() <= MAX_POOL_METADATA_LINK_LENGTH
+                && self.discord.len() <= MAX_POOL_METADATA_LINK_LENGTH,
+            PoolError::InvalidVirtualPoolMetadata
+        );
+        require!(
+            self.tags.len() <= MAX_POOL_METADATA_TAGS
+                && self
+                    .tags
+                    .iter()
+                    .all(|tag| !tag.is_empty() && tag.len() <= MAX_POOL_METADATA_TAG_LENGTH),
+            PoolError::InvalidVirtualPoolMetadata
+        );
+        Ok(())
+    }
+}
 
 /// Metadata for a virtual pool.
 #[account]
@@ -6,26 +104,48 @@ use crate::*;
 pub struct VirtualPoolMetadata {
     /// virtual pool
     pub virtual_pool: Pubkey,
+    /// layout version, see [VirtualPoolMetadataVersion]
+    pub version: u8,
+    /// padding 0
+    pub padding_0: [u8; 15],
     /// padding for future use
-    pub padding: [u128; 6],
+    pub padding: [u128; 5],
     /// Name of project.
     pub name: String,
     /// Website of project.
     pub website: String,
     /// Logo of project
     pub logo: String,
+    /// Socials of project, default for V0 accounts
+    pub socials: VirtualPoolSocials,
 }
 
 impl VirtualPoolMetadata {
-    /// Space that a [PartnerMetadata] takes up.
-    pub fn space(metadata: &CreateVirtualPoolMetadataParameters) -> usize {
+    /// Space that a [VirtualPoolMetadata] takes up.
+    pub fn space(name: &str, website: &str, logo: &str, socials: &VirtualPoolSocials) -> usize {
         std::mem::size_of::<Pubkey>()
             + 16 * 6
             + 4
-            + metadata.name.as_bytes().len()
+            + name.len()
             + 4
-            + metadata.website.as_bytes().len()
+            + website.len()
             + 4
-            + metadata.logo.as_bytes().len()
+            + logo.len()
+            + socials.space()
+    }
+
+    pub fn validate(
+        name: &str,
+        website: &str,
+        logo: &str,
+        socials: &VirtualPoolSocials,
+    ) -> Result<()> {
+        require!(
+            name.len() <= MAX_POOL_METADATA_NAME_LENGTH
+                && website.len() <= MAX_POOL_METADATA_LINK_LENGTH
+                && logo.len() <= MAX_POOL_METADATA_LOGO_LENGTH,
+            PoolError::InvalidVirtualPoolMetadata
+        );
+        socials.validate()
     }
 }
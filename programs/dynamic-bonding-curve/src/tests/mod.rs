@@ -18,3 +18,60 @@ mod test_migration_fee_status;
 
 #[cfg(test)]
 mod test_rate_limiter;
+
+#[cfg(test)]
+mod test_close_config;
+
+#[cfg(test)]
+mod test_pool_creator_authority;
+
+#[cfg(test)]
+mod test_transfer_fee;
+
+#[cfg(test)]
+mod test_existing_token;
+
+#[cfg(test)]
+mod test_token_metadata_fields;
+
+#[cfg(test)]
+mod test_virtual_pool_metadata;
+
+#[cfg(test)]
+mod test_dlmm_bin_math;
+
+#[cfg(test)]
+mod test_external_migration;
+
+#[cfg(test)]
+mod test_migrated_pool_fee;
+
+#[cfg(test)]
+mod test_migration_fee_option;
+
+#[cfg(test)]
+mod test_migration_price_range;
+
+#[cfg(test)]
+mod test_migration_rent;
+
+#[cfg(test)]
+mod test_migration_slippage;
+
+#[cfg(test)]
+mod test_locked_vesting_tranches;
+
+#[cfg(test)]
+mod test_locked_vesting_modes;
+
+#[cfg(test)]
+mod test_locked_vesting_start;
+
+#[cfg(test)]
+mod test_refund;
+
+#[cfg(test)]
+mod test_base_mint_extensions;
+
+#[cfg(test)]
+mod test_partner_metadata;
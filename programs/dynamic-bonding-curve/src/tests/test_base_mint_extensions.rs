@@ -0,0 +1,35 @@
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+
+use crate::{get_base_mint_extensions, state::PoolConfigExtension};
+
+#[test]
+fn test_base_mint_extensions() {
+    let config_extension = PoolConfigExtension::default();
+    assert_eq!(
+        get_base_mint_extensions(&config_extension),
+        vec![ExtensionType::MetadataPointer]
+    );
+
+    let config_extension = PoolConfigExtension {
+        base_transfer_fee_basis_points: 100,
+        base_transfer_fee_maximum_fee: 1_000,
+        base_interest_rate: -500,
+        base_non_transferable_flag: 1,
+        ..Default::default()
+    };
+    let extensions = get_base_mint_extensions(&config_extension);
+    assert_eq!(
+        extensions,
+        vec![
+            ExtensionType::MetadataPointer,
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::InterestBearingConfig,
+            ExtensionType::DefaultAccountState,
+        ]
+    );
+    // base vault only needs the account extension of transfer fee
+    assert_eq!(
+        ExtensionType::get_required_init_account_extensions(&extensions),
+        vec![ExtensionType::TransferFeeAmount]
+    );
+}
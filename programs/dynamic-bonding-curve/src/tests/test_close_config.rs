@@ -0,0 +1,30 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::state::PoolConfig;
+
+#[test]
+fn test_config_without_close_authority_is_not_closable() {
+    let mut config = PoolConfig::default();
+    assert!(!config.is_closable());
+
+    config.deprecate();
+    assert!(!config.is_closable());
+}
+
+#[test]
+fn test_config_is_closable_without_pool() {
+    let mut config = PoolConfig {
+        close_authority: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    assert!(config.is_closable());
+
+    config.increase_pool_count().unwrap();
+    assert_eq!(config.pool_count, 1);
+    assert!(!config.is_closable());
+
+    // pools load their config, so deprecation doesn't allow close authority to close config with pools
+    config.deprecate();
+    assert!(config.is_deprecated());
+    assert!(!config.is_closable());
+}
@@ -1,5 +1,5 @@
 use crate::{
-    constants::MAX_SQRT_PRICE,
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
     params::liquidity_distribution::{
         get_base_token_for_swap, get_migration_base_token, get_migration_threshold_price,
         LiquidityDistributionParameters,
@@ -31,6 +31,9 @@ fn test_create_config() {
         0,
         sqrt_migration_price,
         MigrationOption::MeteoraDamm,
+        0,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
     )
     .unwrap();
 
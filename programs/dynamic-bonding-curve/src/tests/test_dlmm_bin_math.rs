@@ -0,0 +1,151 @@
+use ruint::aliases::U256;
+
+use crate::{
+    constants::{dlmm::MIGRATION_BIN_RANGE, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    math::bin_math::{
+        get_base_factor, get_bin_array_index, get_dlmm_migration_base_token,
+        get_id_from_sqrt_price, get_price_from_id, is_overflow_default_bin_array_bitmap,
+    },
+    params::liquidity_distribution::get_migration_base_token,
+    state::{MigrationFeeOption, MigrationOption},
+};
+
+// smallest sqrt price whose price is not lower than the bin price
+fn get_sqrt_price_from_id(bin_id: i32, bin_step: u16) -> u128 {
+    let price = U256::from(get_price_from_id(bin_id, bin_step).unwrap());
+    let price_shifted: U256 = price << 64;
+    let sqrt_price: u128 = price_shifted.root(2).try_into().unwrap();
+    sqrt_price + 1
+}
+
+#[test]
+fn test_get_id_from_sqrt_price() {
+    for bin_step in [1u16, 10, 25, 80, 100, 400] {
+        for bin_id in [-600, -100, -1, 0, 1, 100, 600] {
+            let sqrt_price = get_sqrt_price_from_id(bin_id, bin_step);
+            assert_eq!(
+                get_id_from_sqrt_price(sqrt_price, bin_step).unwrap(),
+                bin_id,
+                "bin_step {} bin_id {}",
+                bin_step,
+                bin_id
+            );
+            // just below the bin price falls into the previous bin
+            assert_eq!(
+                get_id_from_sqrt_price(sqrt_price - 2, bin_step).unwrap(),
+                bin_id - 1,
+                "bin_step {} bin_id {}",
+                bin_step,
+                bin_id
+            );
+        }
+    }
+}
+
+#[test]
+fn test_get_id_from_sqrt_price_invalid_bin_step() {
+    let sqrt_price = get_sqrt_price_from_id(0, 1);
+    assert!(get_id_from_sqrt_price(sqrt_price, 0).is_err());
+    assert!(get_id_from_sqrt_price(sqrt_price, 401).is_err());
+}
+
+#[test]
+fn test_get_base_factor() {
+    assert_eq!(get_base_factor(25, 25).unwrap(), 10_000);
+    assert_eq!(get_base_factor(200, 80).unwrap(), 25_000);
+    assert_eq!(get_base_factor(25, 80).unwrap(), 3_125);
+    // base fee can't be expressed with the bin step
+    assert!(get_base_factor(25, 7).is_err());
+    // base factor overflows u16
+    assert!(get_base_factor(600, 1).is_err());
+
+    for option in [
+        MigrationFeeOption::FixedBps25,
+        MigrationFeeOption::FixedBps30,
+        MigrationFeeOption::FixedBps100,
+        MigrationFeeOption::FixedBps200,
+        MigrationFeeOption::FixedBps400,
+        MigrationFeeOption::FixedBps600,
+    ] {
+        let base_fee_bps = option.get_base_fee_bps(0);
+        let base_factor = get_base_factor(base_fee_bps, 100).unwrap();
+        assert_eq!(u64::from(base_factor) * 100 / 10_000, base_fee_bps);
+    }
+}
+
+#[test]
+fn test_get_bin_array_index() {
+    assert_eq!(get_bin_array_index(0), 0);
+    assert_eq!(get_bin_array_index(69), 0);
+    assert_eq!(get_bin_array_index(70), 1);
+    assert_eq!(get_bin_array_index(-1), -1);
+    assert_eq!(get_bin_array_index(-70), -1);
+    assert_eq!(get_bin_array_index(-71), -2);
+
+    assert!(!is_overflow_default_bin_array_bitmap(512 * 70 - 1));
+    assert!(is_overflow_default_bin_array_bitmap(512 * 70));
+    assert!(!is_overflow_default_bin_array_bitmap(-512 * 70));
+    assert!(is_overflow_default_bin_array_bitmap(-512 * 70 - 1));
+
+    // migration range spans at most 2 bin arrays
+    for active_id in [-1000, -35, -34, 0, 35, 36, 1000] {
+        let lower = get_bin_array_index(active_id - MIGRATION_BIN_RANGE);
+        let upper = get_bin_array_index(active_id + MIGRATION_BIN_RANGE);
+        assert!(upper == lower || upper == lower + 1);
+    }
+}
+
+#[test]
+fn test_dlmm_migration_base_token() {
+    let quote_amount = 50_000_000_000;
+    // price 1
+    assert_eq!(
+        get_dlmm_migration_base_token(quote_amount, 0, 100).unwrap(),
+        quote_amount
+    );
+
+    for (bin_id, bin_step) in [(-500, 25), (-100, 80), (100, 80), (50, 100)] {
+        let base_amount = get_dlmm_migration_base_token(quote_amount, bin_id, bin_step).unwrap();
+        let price = U256::from(get_price_from_id(bin_id, bin_step).unwrap());
+        // base amount is worth at least the quote amount, but not more than one extra base token
+        let base_value = U256::from(base_amount) * price;
+        let quote_value = U256::from(quote_amount) << 64;
+        assert!(base_value >= quote_value);
+        assert!(base_value - quote_value <= price);
+    }
+}
+
+#[test]
+fn test_dlmm_migration_base_token_from_config() {
+    let migration_quote_threshold = 50_000_000_000;
+    let bin_step = 80;
+    let bin_id = -100;
+    let sqrt_migration_price = get_sqrt_price_from_id(bin_id, bin_step);
+
+    let migration_base_amount = get_migration_base_token(
+        migration_quote_threshold,
+        0,
+        sqrt_migration_price,
+        MigrationOption::Dlmm,
+        bin_step,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
+    )
+    .unwrap();
+    assert_eq!(
+        migration_base_amount,
+        get_dlmm_migration_base_token(migration_quote_threshold, bin_id, bin_step).unwrap()
+    );
+
+    // bin step is required for dlmm migration
+    assert!(get_migration_base_token(
+        migration_quote_threshold,
+        0,
+        sqrt_migration_price,
+        MigrationOption::Dlmm,
+        0,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
+    )
+    .is_err());
+}
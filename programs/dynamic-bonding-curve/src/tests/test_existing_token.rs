@@ -0,0 +1,20 @@
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+
+use crate::token::is_supported_base_mint_extensions;
+
+#[test]
+fn test_existing_base_mint_extensions() {
+    assert!(is_supported_base_mint_extensions(&[]));
+    assert!(is_supported_base_mint_extensions(&[
+        ExtensionType::MetadataPointer,
+        ExtensionType::TokenMetadata,
+    ]));
+    // transfer fee of an existing mint can be updated, so it is not supported
+    assert!(!is_supported_base_mint_extensions(&[
+        ExtensionType::TransferFeeConfig
+    ]));
+    assert!(!is_supported_base_mint_extensions(&[
+        ExtensionType::MetadataPointer,
+        ExtensionType::PermanentDelegate,
+    ]));
+}
@@ -0,0 +1,95 @@
+use anchor_lang::{prelude::Pubkey, solana_program::hash::hash, AnchorSerialize};
+
+use crate::{
+    constants::{seeds::EXTERNAL_MIGRATION_SIGNER_PREFIX, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    params::liquidity_distribution::{
+        get_migration_base_token, get_migration_threshold_price, LiquidityDistributionParameters,
+    },
+    state::MigrationOption,
+    ExternalMigrationParameters, EXTERNAL_MIGRATION_DISCRIMINATOR,
+};
+
+use super::price_math::get_price_from_id;
+
+#[test]
+fn test_external_migration_discriminator() {
+    assert_eq!(
+        EXTERNAL_MIGRATION_DISCRIMINATOR,
+        hash(b"global:on_dbc_migration").to_bytes()[..8]
+    );
+}
+
+#[test]
+fn test_external_migration_parameters_layout() {
+    let params = ExternalMigrationParameters {
+        virtual_pool: Pubkey::new_unique(),
+        config: Pubkey::new_unique(),
+        base_amount: 1,
+        quote_amount: 2,
+        sqrt_price: 3,
+        partner: Pubkey::new_unique(),
+        creator: Pubkey::new_unique(),
+        partner_lp_percentage: 10,
+        partner_locked_lp_percentage: 20,
+        creator_lp_percentage: 30,
+        creator_locked_lp_percentage: 40,
+    };
+    let data = params.try_to_vec().unwrap();
+    assert_eq!(data.len(), 32 * 2 + 8 * 2 + 16 + 32 * 2 + 4);
+    assert_eq!(data[32 * 2..32 * 2 + 8], 1u64.to_le_bytes());
+    assert_eq!(data[data.len() - 4..], [10, 20, 30, 40]);
+}
+
+#[test]
+fn test_external_migration_signer_seeds() {
+    let virtual_pool = Pubkey::new_unique();
+    let (migration_signer, bump) = Pubkey::find_program_address(
+        &[EXTERNAL_MIGRATION_SIGNER_PREFIX, virtual_pool.as_ref()],
+        &crate::ID,
+    );
+    assert_eq!(
+        Pubkey::create_program_address(
+            external_migration_signer_seeds!(virtual_pool, bump),
+            &crate::ID
+        )
+        .unwrap(),
+        migration_signer
+    );
+}
+
+#[test]
+fn test_external_migration_base_token() {
+    // external program receives base token worth the quote token at migration price, same as meteora damm
+    let migration_quote_threshold = 50_000_000_000;
+    let sqrt_start_price = get_price_from_id(-100, 80).unwrap();
+    let curve = vec![LiquidityDistributionParameters {
+        sqrt_price: MAX_SQRT_PRICE,
+        liquidity: 1_000_000_000_000_000_000_000_000u128
+            .checked_shl(64)
+            .unwrap(),
+    }];
+    let sqrt_migration_price =
+        get_migration_threshold_price(migration_quote_threshold, sqrt_start_price, &curve).unwrap();
+
+    let external_base_amount = get_migration_base_token(
+        migration_quote_threshold,
+        0,
+        sqrt_migration_price,
+        MigrationOption::ExternalProgram,
+        0,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
+    )
+    .unwrap();
+    let damm_base_amount = get_migration_base_token(
+        migration_quote_threshold,
+        0,
+        sqrt_migration_price,
+        MigrationOption::MeteoraDamm,
+        0,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
+    )
+    .unwrap();
+    assert_eq!(external_base_amount, damm_base_amount);
+}
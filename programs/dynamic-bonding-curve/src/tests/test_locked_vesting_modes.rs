@@ -0,0 +1,72 @@
+use crate::{
+    state::{LockedVestingCancelMode, LockedVestingConfig, LockedVestingUpdateRecipientMode},
+    LockedVestingParams,
+};
+
+fn locked_vesting_config(update_recipient_mode: u8, cancel_mode: u8) -> LockedVestingConfig {
+    let mut locked_vesting_config = LockedVestingParams {
+        amount_per_period: 100,
+        cliff_duration_from_migration_time: 86_400,
+        frequency: 3_600,
+        number_of_period: 10,
+        cliff_unlock_amount: 1_000,
+    }
+    .to_locked_vesting_config();
+    locked_vesting_config.set_escrow_modes(update_recipient_mode, cancel_mode);
+    locked_vesting_config
+}
+
+#[test]
+fn test_default_escrow_modes() {
+    // configs created before the modes were configurable keep recipient only update and no cancel
+    let params = locked_vesting_config(0, 0)
+        .to_create_vesting_escrow_params(1_000)
+        .unwrap();
+    assert_eq!(params.update_recipient_mode, 2);
+    assert_eq!(params.cancel_mode, 0);
+    assert_eq!(params.vesting_start_time, 1_000);
+    assert_eq!(params.cliff_time, 87_400);
+}
+
+#[test]
+fn test_escrow_modes() {
+    let params = locked_vesting_config(
+        LockedVestingUpdateRecipientMode::PartnerOrRecipient.into(),
+        LockedVestingCancelMode::Partner.into(),
+    )
+    .to_create_vesting_escrow_params(0)
+    .unwrap();
+    assert_eq!(params.update_recipient_mode, 3);
+    assert_eq!(params.cancel_mode, 1);
+
+    let params = locked_vesting_config(
+        LockedVestingUpdateRecipientMode::None.into(),
+        LockedVestingCancelMode::None.into(),
+    )
+    .to_create_vesting_escrow_params(0)
+    .unwrap();
+    assert_eq!(params.update_recipient_mode, 0);
+    assert_eq!(params.cancel_mode, 0);
+
+    assert!(locked_vesting_config(4, 0)
+        .to_create_vesting_escrow_params(0)
+        .is_err());
+    // recipient cancel would leave unvested token with pool authority
+    assert!(locked_vesting_config(0, 2)
+        .to_create_vesting_escrow_params(0)
+        .is_err());
+    assert!(locked_vesting_config(0, 3)
+        .to_create_vesting_escrow_params(0)
+        .is_err());
+}
+
+#[test]
+fn test_partner_allowed_modes() {
+    assert!(!LockedVestingCancelMode::None.is_partner_allowed());
+    assert!(LockedVestingCancelMode::Partner.is_partner_allowed());
+
+    assert!(!LockedVestingUpdateRecipientMode::Recipient.is_partner_allowed());
+    assert!(!LockedVestingUpdateRecipientMode::None.is_partner_allowed());
+    assert!(LockedVestingUpdateRecipientMode::Partner.is_partner_allowed());
+    assert!(LockedVestingUpdateRecipientMode::PartnerOrRecipient.is_partner_allowed());
+}
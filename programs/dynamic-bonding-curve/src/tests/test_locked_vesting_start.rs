@@ -0,0 +1,78 @@
+use anchor_lang::prelude::Clock;
+
+use crate::{
+    activation_handler::ActivationType,
+    state::{LockedVestingStartAnchor, PoolConfigExtension, VirtualPool},
+};
+
+fn virtual_pool() -> VirtualPool {
+    VirtualPool {
+        activation_point: 1_000,
+        finish_curve_timestamp: 50_000,
+        ..Default::default()
+    }
+}
+
+fn clock() -> Clock {
+    Clock {
+        slot: 101_000,
+        unix_timestamp: 90_000,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_locked_vesting_start_from_migration() {
+    // configs created before the start anchor start vesting at curve completion
+    let config_extension = PoolConfigExtension::default();
+    assert_eq!(
+        config_extension
+            .get_locked_vesting_start_time(
+                &virtual_pool(),
+                ActivationType::Timestamp.into(),
+                &clock()
+            )
+            .unwrap(),
+        50_000
+    );
+}
+
+#[test]
+fn test_locked_vesting_start_from_activation() {
+    let config_extension = PoolConfigExtension {
+        locked_vesting_start_anchor: LockedVestingStartAnchor::Activation.into(),
+        ..Default::default()
+    };
+    assert_eq!(
+        config_extension
+            .get_locked_vesting_start_time(
+                &virtual_pool(),
+                ActivationType::Timestamp.into(),
+                &clock()
+            )
+            .unwrap(),
+        1_000
+    );
+    // activated 100_000 slots ago, 40_000 seconds at 400ms per slot
+    assert_eq!(
+        config_extension
+            .get_locked_vesting_start_time(&virtual_pool(), ActivationType::Slot.into(), &clock())
+            .unwrap(),
+        50_000
+    );
+}
+
+#[test]
+fn test_locked_vesting_start_from_timestamp() {
+    let config_extension = PoolConfigExtension {
+        locked_vesting_start_anchor: LockedVestingStartAnchor::Timestamp.into(),
+        locked_vesting_start_timestamp: 20_000,
+        ..Default::default()
+    };
+    assert_eq!(
+        config_extension
+            .get_locked_vesting_start_time(&virtual_pool(), ActivationType::Slot.into(), &clock())
+            .unwrap(),
+        20_000
+    );
+}
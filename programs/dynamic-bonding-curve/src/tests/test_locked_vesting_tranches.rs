@@ -0,0 +1,89 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::{
+    get_total_locked_vesting_amount,
+    state::{PoolConfig, PoolConfigExtension},
+    LockedVestingParams, LockedVestingTrancheParams,
+};
+
+fn locked_vesting(amount_per_period: u64) -> LockedVestingParams {
+    LockedVestingParams {
+        amount_per_period,
+        cliff_duration_from_migration_time: 86_400,
+        frequency: 3_600,
+        number_of_period: 10,
+        cliff_unlock_amount: 1_000,
+    }
+}
+
+#[test]
+fn test_locked_vesting_tranche_validate() {
+    let tranche = LockedVestingTrancheParams {
+        recipient: Pubkey::new_unique(),
+        locked_vesting: locked_vesting(100),
+    };
+    assert!(tranche.validate().is_ok());
+
+    let without_recipient = LockedVestingTrancheParams {
+        recipient: Pubkey::default(),
+        ..tranche
+    };
+    assert!(without_recipient.validate().is_err());
+
+    let without_vesting = LockedVestingTrancheParams {
+        locked_vesting: LockedVestingParams::default(),
+        ..tranche
+    };
+    assert!(without_vesting.validate().is_err());
+}
+
+#[test]
+fn test_locked_vestings_of_config() {
+    let mut config = PoolConfig::default();
+    let mut config_extension = PoolConfigExtension::default();
+    assert!(config.get_locked_vestings(&config_extension).is_empty());
+
+    // tranches without creator vesting
+    let team = LockedVestingTrancheParams {
+        recipient: Pubkey::new_unique(),
+        locked_vesting: locked_vesting(100),
+    };
+    let advisors = LockedVestingTrancheParams {
+        recipient: Pubkey::new_unique(),
+        locked_vesting: locked_vesting(200),
+    };
+    config_extension.locked_vesting_tranches[0] = team.to_locked_vesting_tranche_config();
+    config_extension.locked_vesting_tranches[1] = advisors.to_locked_vesting_tranche_config();
+    assert_eq!(config_extension.get_locked_vesting_tranches().count(), 2);
+    assert_eq!(
+        config.get_locked_vestings(&config_extension),
+        vec![team.locked_vesting, advisors.locked_vesting]
+    );
+
+    // creator vesting comes first
+    config.locked_vesting_config = locked_vesting(300).to_locked_vesting_config();
+    let locked_vestings = config.get_locked_vestings(&config_extension);
+    assert_eq!(
+        locked_vestings,
+        vec![
+            locked_vesting(300),
+            team.locked_vesting,
+            advisors.locked_vesting
+        ]
+    );
+    assert_eq!(
+        get_total_locked_vesting_amount(&locked_vestings).unwrap(),
+        3_000 + 1_000 + 2_000 + 1_000 + 1_000 + 1_000
+    );
+}
+
+#[test]
+fn test_total_token_supply_includes_tranches() {
+    let total_supply = PoolConfig::get_total_token_supply(
+        1_000_000,
+        500_000,
+        &[locked_vesting(100), locked_vesting(200)],
+    )
+    .unwrap();
+    assert_eq!(total_supply, 1_000_000 + 500_000 + 2_000 + 3_000);
+}
@@ -0,0 +1,95 @@
+use crate::{
+    activation_handler::ActivationType,
+    constants::{BIN_STEP_BPS_DEFAULT, BIN_STEP_BPS_U128_DEFAULT},
+    params::fee_parameters::{DynamicFeeParameters, MigratedPoolFeeParameters},
+    state::MigratedPoolFeeConfig,
+};
+
+#[test]
+fn test_migrated_pool_fee_to_damm_v2_fee_parameters() {
+    let params = MigratedPoolFeeParameters {
+        cliff_fee_numerator: 500_000_000,
+        number_of_period: 60,
+        period_frequency: 60,
+        reduction_factor: 8_000_000,
+        fee_scheduler_mode: 0,
+        collect_fee_mode: 1,
+        partner_fee_percent: 20,
+        dynamic_fee: Some(DynamicFeeParameters {
+            bin_step: BIN_STEP_BPS_DEFAULT,
+            bin_step_u128: BIN_STEP_BPS_U128_DEFAULT,
+            filter_period: 10,
+            decay_period: 120,
+            reduction_factor: 5000,
+            max_volatility_accumulator: 14460000,
+            variable_fee_control: 2000,
+        }),
+    };
+    params.validate(ActivationType::Timestamp).unwrap();
+
+    let config = params.to_migrated_pool_fee_config();
+    assert!(config.is_initialized());
+    assert_eq!(config.collect_fee_mode, 1);
+
+    let pool_fees = config.to_damm_v2_pool_fee_parameters();
+    assert_eq!(pool_fees.base_fee.cliff_fee_numerator, 500_000_000);
+    assert_eq!(pool_fees.base_fee.number_of_period, 60);
+    assert_eq!(pool_fees.base_fee.period_frequency, 60);
+    assert_eq!(pool_fees.base_fee.reduction_factor, 8_000_000);
+    assert_eq!(pool_fees.partner_fee_percent, 20);
+    let dynamic_fee = pool_fees.dynamic_fee.unwrap();
+    assert_eq!(dynamic_fee.bin_step_u128, BIN_STEP_BPS_U128_DEFAULT);
+    assert_eq!(dynamic_fee.decay_period, 120);
+    assert_eq!(dynamic_fee.variable_fee_control, 2000);
+}
+
+#[test]
+fn test_migrated_pool_fee_without_dynamic_fee() {
+    let params = MigratedPoolFeeParameters {
+        cliff_fee_numerator: 2_500_000,
+        ..Default::default()
+    };
+    params.validate(ActivationType::Slot).unwrap();
+    let pool_fees = params
+        .to_migrated_pool_fee_config()
+        .to_damm_v2_pool_fee_parameters();
+    assert!(pool_fees.dynamic_fee.is_none());
+
+    assert!(!MigratedPoolFeeConfig::default().is_initialized());
+}
+
+#[test]
+fn test_migrated_pool_fee_validation() {
+    // rate limiter is not supported by damm v2
+    let params = MigratedPoolFeeParameters {
+        cliff_fee_numerator: 2_500_000,
+        fee_scheduler_mode: 2,
+        ..Default::default()
+    };
+    assert!(params.validate(ActivationType::Slot).is_err());
+
+    let params = MigratedPoolFeeParameters {
+        cliff_fee_numerator: 2_500_000,
+        collect_fee_mode: 2,
+        ..Default::default()
+    };
+    assert!(params.validate(ActivationType::Slot).is_err());
+
+    let params = MigratedPoolFeeParameters {
+        cliff_fee_numerator: 2_500_000,
+        partner_fee_percent: 101,
+        ..Default::default()
+    };
+    assert!(params.validate(ActivationType::Slot).is_err());
+
+    // fee scheduler requires all of its fields
+    let params = MigratedPoolFeeParameters {
+        cliff_fee_numerator: 2_500_000,
+        number_of_period: 10,
+        ..Default::default()
+    };
+    assert!(params.validate(ActivationType::Slot).is_err());
+
+    let params = MigratedPoolFeeParameters::default();
+    assert!(params.validate(ActivationType::Slot).is_err());
+}
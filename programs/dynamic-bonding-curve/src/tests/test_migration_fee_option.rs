@@ -0,0 +1,45 @@
+use crate::state::{MigrationFeeOption, PoolConfigExtension};
+
+#[test]
+fn test_customizable_migration_fee_option() {
+    let option = MigrationFeeOption::Customizable;
+    assert_eq!(u8::from(option), 6);
+    assert_eq!(option.get_base_fee_bps(150), 150);
+    assert!(option.validate_base_fee(150, 150).is_ok());
+    assert!(option.validate_base_fee(100, 150).is_err());
+
+    assert!(option.validate_migration_base_fee_bps(1).is_ok());
+    assert!(option.validate_migration_base_fee_bps(5000).is_ok());
+    assert!(option.validate_migration_base_fee_bps(0).is_err());
+    assert!(option.validate_migration_base_fee_bps(5001).is_err());
+}
+
+#[test]
+fn test_fixed_migration_fee_option_ignores_base_fee_bps() {
+    let option = MigrationFeeOption::FixedBps100;
+    assert_eq!(option.get_base_fee_bps(150), 100);
+    assert!(option.validate_base_fee(100, 0).is_ok());
+    assert!(option.validate_migration_base_fee_bps(0).is_ok());
+    assert!(option.validate_migration_base_fee_bps(100).is_err());
+}
+
+#[test]
+fn test_migration_base_fee_bps_of_config_extension() {
+    let config_extension = PoolConfigExtension {
+        migration_base_fee_bps: 75,
+        ..Default::default()
+    };
+    assert_eq!(
+        config_extension
+            .get_migration_base_fee_bps(MigrationFeeOption::Customizable.into())
+            .unwrap(),
+        75
+    );
+    assert_eq!(
+        config_extension
+            .get_migration_base_fee_bps(MigrationFeeOption::FixedBps25.into())
+            .unwrap(),
+        25
+    );
+    assert!(config_extension.get_migration_base_fee_bps(7).is_err());
+}
@@ -1,4 +1,4 @@
-use crate::state::{VirtualPool, CREATOR_MASK, PARTNER_MASK};
+use crate::state::{MigrationFeeDistribution, PoolConfig, VirtualPool, CREATOR_MASK, PARTNER_MASK};
 
 #[test]
 fn test_migration_claim_fee_status() {
@@ -15,3 +15,30 @@ fn test_migration_claim_fee_status() {
     assert_eq!(pool.eligible_to_withdraw_migration_fee(PARTNER_MASK), false);
     assert_eq!(pool.eligible_to_withdraw_migration_fee(CREATOR_MASK), false);
 }
+
+#[test]
+fn test_migration_fee_distribution_with_crank_reward() {
+    let config = PoolConfig {
+        migration_quote_threshold: 1_000_000,
+        migration_fee_percentage: 10,
+        creator_migration_fee_percentage: 20,
+        ..Default::default()
+    };
+
+    let MigrationFeeDistribution {
+        partner_migration_fee,
+        creator_migration_fee,
+    } = config.get_migration_fee_distribution(0).unwrap();
+    assert_eq!(partner_migration_fee, 80_000);
+    assert_eq!(creator_migration_fee, 20_000);
+
+    // crank reward is reserved before migration fee is shared
+    let MigrationFeeDistribution {
+        partner_migration_fee,
+        creator_migration_fee,
+    } = config.get_migration_fee_distribution(10_000).unwrap();
+    assert_eq!(partner_migration_fee, 72_000);
+    assert_eq!(creator_migration_fee, 18_000);
+
+    assert!(config.get_migration_fee_distribution(100_001).is_err());
+}
@@ -0,0 +1,85 @@
+use crate::{
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    params::liquidity_distribution::{get_migration_base_token, MigrationPriceRange},
+    state::{MigrationOption, PoolConfigExtension},
+};
+
+#[test]
+fn test_migration_price_range_validate() {
+    let range = |min_price_bps, max_price_bps| MigrationPriceRange {
+        min_price_bps,
+        max_price_bps,
+    };
+    assert!(range(5_000, 20_000).validate().is_ok());
+    assert!(range(0, 20_000).validate().is_err());
+    assert!(range(10_000, 20_000).validate().is_err());
+    assert!(range(5_000, 10_000).validate().is_err());
+}
+
+#[test]
+fn test_migration_sqrt_price_range() {
+    let sqrt_migration_price = 1u128 << 64; // price = 1
+    let range = MigrationPriceRange {
+        min_price_bps: 2_500,
+        max_price_bps: 40_000,
+    };
+    let (sqrt_min_price, sqrt_max_price) =
+        range.get_sqrt_price_range(sqrt_migration_price).unwrap();
+    assert_eq!(sqrt_min_price, 1u128 << 63);
+    assert_eq!(sqrt_max_price, 1u128 << 65);
+
+    // range must be within damm v2 price bounds
+    assert!(range.get_sqrt_price_range(MIN_SQRT_PRICE + 1).is_err());
+    assert!(range.get_sqrt_price_range(MAX_SQRT_PRICE - 1).is_err());
+
+    // config without range uses full price range
+    assert_eq!(
+        PoolConfigExtension::default().get_migration_sqrt_price_range(),
+        (MIN_SQRT_PRICE, MAX_SQRT_PRICE)
+    );
+}
+
+#[test]
+fn test_migration_base_token_with_price_range() {
+    let migration_quote_threshold = 50_000_000_000;
+    let sqrt_migration_price = 1u128 << 64; // price = 1
+    let get_base_token = |min_price_bps, max_price_bps| {
+        let (sqrt_min_price, sqrt_max_price) = MigrationPriceRange {
+            min_price_bps,
+            max_price_bps,
+        }
+        .get_sqrt_price_range(sqrt_migration_price)
+        .unwrap();
+        get_migration_base_token(
+            migration_quote_threshold,
+            0,
+            sqrt_migration_price,
+            MigrationOption::DammV2,
+            0,
+            sqrt_min_price,
+            sqrt_max_price,
+        )
+        .unwrap()
+    };
+    let full_range_base_amount = get_migration_base_token(
+        migration_quote_threshold,
+        0,
+        sqrt_migration_price,
+        MigrationOption::DammV2,
+        0,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
+    )
+    .unwrap();
+
+    // range symmetric around migration price needs the same base token as full range
+    let symmetric_base_amount = get_base_token(2_500, 40_000);
+    assert!(symmetric_base_amount.abs_diff(full_range_base_amount) <= 1);
+    assert!(symmetric_base_amount.abs_diff(migration_quote_threshold) <= 1);
+
+    // liquidity is deeper when the range below migration price is narrower, so a higher min price or a
+    // higher max price needs more base token
+    assert!(get_base_token(5_000, 40_000) > symmetric_base_amount);
+    assert!(get_base_token(2_500, 80_000) > symmetric_base_amount);
+    assert!(get_base_token(2_500, 20_000) < symmetric_base_amount);
+}
@@ -0,0 +1,9 @@
+use std::mem::size_of;
+
+#[test]
+fn test_migration_account_layout_sizes() {
+    // rent funded to pool authority is computed from the zero copy layouts of the amm accounts
+    assert_eq!(size_of::<damm_v2::accounts::Pool>(), 1104);
+    assert_eq!(size_of::<damm_v2::accounts::Position>(), 400);
+    assert_eq!(size_of::<locker::accounts::VestingEscrow>(), 288);
+}
@@ -0,0 +1,46 @@
+use crate::{
+    constants::ONE_Q64, get_price_from_amounts, get_price_from_sqrt_price,
+    state::PoolConfigExtension, validate_migration_deposit_amount, validate_migration_price,
+};
+
+#[test]
+fn test_migration_price_tolerance_of_config_extension() {
+    let mut config_extension = PoolConfigExtension::default();
+    assert_eq!(config_extension.get_migration_price_tolerance_bps(), None);
+
+    config_extension.migration_price_tolerance_bps = 100;
+    assert_eq!(
+        config_extension.get_migration_price_tolerance_bps(),
+        Some(100)
+    );
+}
+
+#[test]
+fn test_validate_migration_price() {
+    // curve final price is 1
+    let curve_sqrt_price = ONE_Q64;
+    let price = |quote_amount| get_price_from_amounts(1_000_000, quote_amount).unwrap();
+
+    assert!(validate_migration_price(price(1_000_000), curve_sqrt_price, 100).is_ok());
+    assert!(validate_migration_price(price(1_010_000), curve_sqrt_price, 100).is_ok());
+    assert!(validate_migration_price(price(990_001), curve_sqrt_price, 100).is_ok());
+    assert!(validate_migration_price(price(1_010_001), curve_sqrt_price, 100).is_err());
+    assert!(validate_migration_price(price(989_999), curve_sqrt_price, 100).is_err());
+
+    let sqrt_price = get_price_from_sqrt_price(ONE_Q64).unwrap();
+    assert_eq!(sqrt_price, price(1_000_000));
+    assert!(validate_migration_price(sqrt_price, curve_sqrt_price * 2, 1_000).is_err());
+
+    assert!(get_price_from_amounts(0, 1_000_000).is_err());
+}
+
+#[test]
+fn test_validate_migration_deposit_amount() {
+    assert!(validate_migration_deposit_amount(1_000, 1_000, None).is_ok());
+    assert!(validate_migration_deposit_amount(0, 1_000, None).is_ok());
+    assert!(validate_migration_deposit_amount(1_001, 1_000, None).is_err());
+
+    assert!(validate_migration_deposit_amount(990, 1_000, Some(100)).is_ok());
+    assert!(validate_migration_deposit_amount(989, 1_000, Some(100)).is_err());
+    assert!(validate_migration_deposit_amount(1_001, 1_000, Some(100)).is_err());
+}
@@ -0,0 +1,25 @@
+use crate::{
+    constants::{
+        MAX_POOL_METADATA_LINK_LENGTH, MAX_POOL_METADATA_LOGO_LENGTH, MAX_POOL_METADATA_NAME_LENGTH,
+    },
+    state::PartnerMetadata,
+};
+
+#[test]
+fn test_validate_partner_metadata() {
+    assert!(PartnerMetadata::validate(
+        &"a".repeat(MAX_POOL_METADATA_NAME_LENGTH),
+        &"a".repeat(MAX_POOL_METADATA_LINK_LENGTH),
+        &"a".repeat(MAX_POOL_METADATA_LOGO_LENGTH),
+    )
+    .is_ok());
+    assert!(
+        PartnerMetadata::validate(&"a".repeat(MAX_POOL_METADATA_NAME_LENGTH + 1), "", "").is_err()
+    );
+    assert!(
+        PartnerMetadata::validate("", &"a".repeat(MAX_POOL_METADATA_LINK_LENGTH + 1), "").is_err()
+    );
+    assert!(
+        PartnerMetadata::validate("", "", &"a".repeat(MAX_POOL_METADATA_LOGO_LENGTH + 1)).is_err()
+    );
+}
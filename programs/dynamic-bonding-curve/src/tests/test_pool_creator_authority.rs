@@ -0,0 +1,87 @@
+use anchor_lang::prelude::Pubkey;
+
+use crate::{
+    get_pool_creator_permit_message, is_signed_by, state::PoolConfigExtension,
+    validate_pool_creator_authority,
+};
+
+// build ed25519 instruction data with single signature, offsets follow the ed25519 program layout
+fn build_ed25519_data(signer: &Pubkey, message: &[u8], instruction_index: u16) -> Vec<u8> {
+    let public_key_offset: u16 = 2 + 14;
+    let signature_offset: u16 = public_key_offset + 32;
+    let message_data_offset: u16 = signature_offset + 64;
+    let mut data = vec![1u8, 0];
+    for value in [
+        signature_offset,
+        instruction_index,
+        public_key_offset,
+        instruction_index,
+        message_data_offset,
+        message.len() as u16,
+        instruction_index,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(&[0u8; 64]);
+    data.extend_from_slice(message);
+    data
+}
+
+#[test]
+fn test_config_without_pool_creator_authority() {
+    let config_extension = PoolConfigExtension::default();
+    assert!(!config_extension.has_pool_creator_authority());
+    validate_pool_creator_authority(
+        &config_extension,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_config_with_pool_creator_authority_requires_approval() {
+    let config_extension = PoolConfigExtension {
+        pool_creator_authority: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    assert!(config_extension.has_pool_creator_authority());
+    assert!(validate_pool_creator_authority(
+        &config_extension,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &[],
+    )
+    .is_err());
+}
+
+#[test]
+fn test_pool_creator_permit_signature() {
+    let authority = Pubkey::new_unique();
+    let config = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let base_mint = Pubkey::new_unique();
+    let message = get_pool_creator_permit_message(&config, &creator, &base_mint);
+
+    let data = build_ed25519_data(&authority, &message, u16::MAX);
+    assert!(is_signed_by(&data, &authority, &message));
+
+    // signed by other key
+    assert!(!is_signed_by(&data, &Pubkey::new_unique(), &message));
+
+    // permit for other creator
+    let other_message = get_pool_creator_permit_message(&config, &Pubkey::new_unique(), &base_mint);
+    assert!(!is_signed_by(&data, &authority, &other_message));
+
+    // data referenced from other instruction is rejected
+    let data = build_ed25519_data(&authority, &message, 0);
+    assert!(!is_signed_by(&data, &authority, &message));
+
+    // malformed data
+    assert!(!is_signed_by(&[], &authority, &message));
+    assert!(!is_signed_by(&[1, 0, 2], &authority, &message));
+}
@@ -0,0 +1,86 @@
+use crate::state::{MigrationProgress, PoolConfigExtension, RefundFeeMode, VirtualPool};
+
+fn virtual_pool() -> VirtualPool {
+    VirtualPool {
+        // 400 base token sold of 1_000 initial supply
+        base_reserve: 600,
+        quote_reserve: 2_000,
+        partner_base_fee: 10,
+        creator_base_fee: 20,
+        partner_quote_fee: 30,
+        creator_quote_fee: 40,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_refund_deadline() {
+    let config_extension = PoolConfigExtension::default();
+    assert!(!config_extension
+        .is_refund_deadline_passed(100, u64::MAX)
+        .unwrap());
+
+    let config_extension = PoolConfigExtension {
+        refund_deadline_duration: 1_000,
+        ..Default::default()
+    };
+    assert!(!config_extension
+        .is_refund_deadline_passed(100, 1_099)
+        .unwrap());
+    assert!(config_extension
+        .is_refund_deadline_passed(100, 1_100)
+        .unwrap());
+}
+
+#[test]
+fn test_start_refund_keep_trading_fee() {
+    let mut pool = virtual_pool();
+    pool.start_refund(1_000, RefundFeeMode::KeepTradingFee)
+        .unwrap();
+    assert_eq!(
+        pool.get_migration_progress().unwrap(),
+        MigrationProgress::Refunding
+    );
+    assert_eq!(pool.refund_base_amount, 400);
+    assert_eq!(pool.refund_quote_amount, 2_000);
+    assert_eq!(pool.partner_quote_fee, 30);
+    assert_eq!(pool.creator_base_fee, 20);
+    // curve state is unchanged
+    assert_eq!(pool.quote_reserve, 2_000);
+}
+
+#[test]
+fn test_start_refund_refund_trading_fee() {
+    let mut pool = virtual_pool();
+    pool.start_refund(1_000, RefundFeeMode::RefundTradingFee)
+        .unwrap();
+    assert_eq!(pool.refund_base_amount, 370);
+    assert_eq!(pool.refund_quote_amount, 2_070);
+    assert_eq!(pool.partner_base_fee, 0);
+    assert_eq!(pool.partner_quote_fee, 0);
+    assert_eq!(pool.creator_base_fee, 0);
+    assert_eq!(pool.creator_quote_fee, 0);
+}
+
+#[test]
+fn test_redeem_refund_pro_rata() {
+    let mut pool = VirtualPool {
+        quote_reserve: 2_001,
+        ..virtual_pool()
+    };
+    pool.start_refund(1_000, RefundFeeMode::KeepTradingFee)
+        .unwrap();
+
+    assert!(pool.redeem_refund(0).is_err());
+    assert!(pool.redeem_refund(401).is_err());
+
+    // 100 / 400 of 2_001 quote, rounded down
+    assert_eq!(pool.redeem_refund(100).unwrap(), 500);
+    let mut total_quote_amount = 500;
+    // rounding down leaves dust to the remaining holders, and the last holder takes what is left
+    while pool.refund_base_amount > 0 {
+        total_quote_amount += pool.redeem_refund(pool.refund_base_amount.min(7)).unwrap();
+    }
+    assert_eq!(total_quote_amount, 2_001);
+    assert_eq!(pool.refund_quote_amount, 0);
+}
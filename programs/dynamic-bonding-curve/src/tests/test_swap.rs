@@ -1,7 +1,7 @@
 use anchor_lang::prelude::Pubkey;
 
 use crate::{
-    constants::{MAX_CURVE_POINT, MAX_SQRT_PRICE},
+    constants::{MAX_CURVE_POINT, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
     params::{
         liquidity_distribution::{
             get_base_token_for_swap, get_migration_base_token, get_migration_threshold_price,
@@ -11,7 +11,8 @@ use crate::{
     },
     state::{
         fee::{FeeMode, VolatilityTracker},
-        CollectFeeMode, LiquidityDistributionConfig, MigrationOption, PoolConfig, VirtualPool,
+        CollectFeeMode, LiquidityDistributionConfig, MigrationOption, PoolConfig,
+        PoolConfigExtension, VirtualPool,
     },
 };
 
@@ -58,6 +59,9 @@ fn test_swap() {
         0,
         sqrt_migration_price,
         MigrationOption::MeteoraDamm,
+        0,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
     )
     .unwrap();
     println!(
@@ -76,7 +80,9 @@ fn test_swap() {
         config.sqrt_start_price,
         0,
         0,
-        config.get_initial_base_supply().unwrap(),
+        config
+            .get_initial_base_supply(&PoolConfigExtension::default())
+            .unwrap(),
     );
     let amount_in = 1_000_000_000; // 1k
     let fee_mode = FeeMode::default();
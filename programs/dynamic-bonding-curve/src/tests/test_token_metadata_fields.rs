@@ -0,0 +1,77 @@
+use crate::{
+    constants::{MAX_TOKEN_METADATA_FIELDS, MAX_TOKEN_METADATA_VALUE_LENGTH},
+    get_token_metadata_field_count, validate_token_metadata_fields, TokenMetadataField,
+};
+
+fn field(key: &str, value: &str) -> TokenMetadataField {
+    TokenMetadataField {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn test_validate_token_metadata_fields() {
+    assert!(validate_token_metadata_fields(&[]).is_ok());
+    assert!(validate_token_metadata_fields(&[
+        field("twitter", "https://x.com/token"),
+        field("telegram", "https://t.me/token"),
+        field("website", "https://token.com"),
+        field("description", "a token"),
+    ])
+    .is_ok());
+
+    // empty value removes the key
+    assert!(validate_token_metadata_fields(&[field("twitter", "")]).is_ok());
+
+    assert!(validate_token_metadata_fields(&[field("", "value")]).is_err());
+    assert!(validate_token_metadata_fields(&[field("name", "value")]).is_err());
+    assert!(
+        validate_token_metadata_fields(&[field("twitter", "a"), field("twitter", "b")]).is_err()
+    );
+    assert!(validate_token_metadata_fields(&[field(
+        "description",
+        &"a".repeat(MAX_TOKEN_METADATA_VALUE_LENGTH + 1)
+    )])
+    .is_err());
+
+    let too_many_fields: Vec<TokenMetadataField> = (0..=MAX_TOKEN_METADATA_FIELDS)
+        .map(|i| field(&format!("key{}", i), "value"))
+        .collect();
+    assert!(validate_token_metadata_fields(&too_many_fields).is_err());
+}
+
+#[test]
+fn test_token_metadata_field_count() {
+    let additional_metadata: Vec<(String, String)> = ["twitter", "telegram", "website"]
+        .iter()
+        .map(|key| (key.to_string(), "value".to_string()))
+        .collect();
+
+    assert_eq!(get_token_metadata_field_count(&additional_metadata, &[]), 3);
+    // updated key isn't counted twice
+    assert_eq!(
+        get_token_metadata_field_count(
+            &additional_metadata,
+            &[field("twitter", "new"), field("discord", "value")]
+        ),
+        4
+    );
+    // removed keys are not counted
+    assert_eq!(
+        get_token_metadata_field_count(
+            &additional_metadata,
+            &[field("twitter", ""), field("description", "")]
+        ),
+        2
+    );
+
+    // fields of an update are within the cap, but not the total on the mint
+    let fields: Vec<TokenMetadataField> = (0..MAX_TOKEN_METADATA_FIELDS)
+        .map(|i| field(&format!("key{}", i), "value"))
+        .collect();
+    assert!(validate_token_metadata_fields(&fields).is_ok());
+    assert!(
+        get_token_metadata_field_count(&additional_metadata, &fields) > MAX_TOKEN_METADATA_FIELDS
+    );
+}
@@ -1,7 +1,7 @@
 use std::ops::Shl;
 
 use crate::{
-    constants::MAX_SQRT_PRICE,
+    constants::{MAX_SQRT_PRICE, MIN_SQRT_PRICE},
     curve::{get_initial_liquidity_from_delta_base, get_initial_liquidity_from_delta_quote},
     params::liquidity_distribution::{
         get_base_token_for_swap, get_migration_base_token, get_migration_threshold_price,
@@ -84,6 +84,9 @@ fn get_constant_product_curve(
         0,
         migration_sqrt_price,
         migration_option,
+        0,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
     )
     .unwrap();
 
@@ -158,13 +161,16 @@ fn get_total_supply_from_curve(
         0,
         sqrt_migration_price,
         migration_option,
+        0,
+        MIN_SQRT_PRICE,
+        MAX_SQRT_PRICE,
     )
     .unwrap();
 
     let minimum_base_supply_with_buffer = PoolConfig::get_total_token_supply(
         swap_base_amount_buffer,
         migration_base_amount,
-        &locked_vesting,
+        &[locked_vesting],
     )
     .unwrap();
 
@@ -0,0 +1,118 @@
+use anchor_spl::token_2022::spl_token_2022::extension::{transfer_fee::TransferFee, ExtensionType};
+
+use crate::{
+    state::PoolConfig,
+    token::{
+        get_transfer_fee_excluded_amount, get_transfer_fee_included_amount,
+        is_supported_quote_mint_extensions,
+    },
+    LockedVestingParams,
+};
+
+fn transfer_fee(transfer_fee_basis_points: u16, maximum_fee: u64) -> TransferFee {
+    TransferFee {
+        epoch: 0.into(),
+        maximum_fee: maximum_fee.into(),
+        transfer_fee_basis_points: transfer_fee_basis_points.into(),
+    }
+}
+
+#[test]
+fn test_transfer_fee_excluded_amount_without_transfer_fee() {
+    let result = get_transfer_fee_excluded_amount(None, 1_000_000).unwrap();
+    assert_eq!(result.amount, 1_000_000);
+    assert_eq!(result.transfer_fee, 0);
+}
+
+#[test]
+fn test_transfer_fee_excluded_amount() {
+    // 1% transfer fee
+    let fee = transfer_fee(100, u64::MAX);
+    let result = get_transfer_fee_excluded_amount(Some(&fee), 1_000_000).unwrap();
+    assert_eq!(result.transfer_fee, 10_000);
+    assert_eq!(result.amount, 990_000);
+
+    // transfer fee is capped by maximum fee
+    let fee = transfer_fee(100, 5_000);
+    let result = get_transfer_fee_excluded_amount(Some(&fee), 1_000_000).unwrap();
+    assert_eq!(result.transfer_fee, 5_000);
+    assert_eq!(result.amount, 995_000);
+
+    // transfer fee is rounded up
+    let fee = transfer_fee(100, u64::MAX);
+    let result = get_transfer_fee_excluded_amount(Some(&fee), 1).unwrap();
+    assert_eq!(result.transfer_fee, 1);
+    assert_eq!(result.amount, 0);
+}
+
+#[test]
+fn test_transfer_fee_included_amount() {
+    let result = get_transfer_fee_included_amount(None, 1_000_000).unwrap();
+    assert_eq!(result.amount, 1_000_000);
+    assert_eq!(result.transfer_fee, 0);
+
+    // 1% transfer fee, receiver gets exactly the excluded amount
+    let fee = transfer_fee(100, u64::MAX);
+    let result = get_transfer_fee_included_amount(Some(&fee), 990_000).unwrap();
+    assert_eq!(result.amount, 1_000_000);
+    assert_eq!(result.transfer_fee, 10_000);
+    let excluded = get_transfer_fee_excluded_amount(Some(&fee), result.amount).unwrap();
+    assert_eq!(excluded.amount, 990_000);
+
+    // transfer fee is capped by maximum fee
+    let fee = transfer_fee(100, 5_000);
+    let result = get_transfer_fee_included_amount(Some(&fee), 995_000).unwrap();
+    assert_eq!(result.amount, 1_000_000);
+    assert_eq!(result.transfer_fee, 5_000);
+}
+
+#[test]
+fn test_migration_base_threshold_with_transfer_fee() {
+    let locked_vesting = LockedVestingParams {
+        amount_per_period: 1_000,
+        cliff_duration_from_migration_time: 0,
+        frequency: 1,
+        number_of_period: 99,
+        cliff_unlock_amount: 1_000,
+    };
+    let threshold = PoolConfig::get_migration_base_threshold_with_transfer_fee(
+        1_000_000,
+        &[locked_vesting],
+        None,
+    )
+    .unwrap();
+    assert_eq!(threshold, 1_000_000);
+
+    // 1% transfer fee on both migration amount (990_000) and locked vesting (100_000)
+    let fee = transfer_fee(100, u64::MAX);
+    let threshold = PoolConfig::get_migration_base_threshold_with_transfer_fee(
+        990_000,
+        &[locked_vesting],
+        Some(&fee),
+    )
+    .unwrap();
+    assert_eq!(threshold, 990_000 + 10_000 + 1_011);
+
+    // each locked vesting is transferred to its own escrow, so maximum fee caps each transfer
+    let fee = transfer_fee(100, 500);
+    let threshold = PoolConfig::get_migration_base_threshold_with_transfer_fee(
+        990_000,
+        &[locked_vesting, locked_vesting],
+        Some(&fee),
+    )
+    .unwrap();
+    assert_eq!(threshold, 990_000 + 500 + 500 + 500);
+}
+
+#[test]
+fn test_quote_mint_with_transfer_fee_is_supported() {
+    assert!(is_supported_quote_mint_extensions(&[
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::MetadataPointer,
+        ExtensionType::TokenMetadata,
+    ]));
+    assert!(!is_supported_quote_mint_extensions(&[
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::PermanentDelegate,
+    ]));
+}
@@ -0,0 +1,97 @@
+use anchor_lang::{prelude::*, AccountDeserialize, AccountSerialize, Discriminator};
+
+use crate::{
+    constants::{
+        MAX_POOL_METADATA_DESCRIPTION_LENGTH, MAX_POOL_METADATA_NAME_LENGTH,
+        MAX_POOL_METADATA_TAGS, MAX_POOL_METADATA_TAG_LENGTH,
+    },
+    state::{VirtualPoolMetadata, VirtualPoolMetadataVersion, VirtualPoolSocials},
+};
+
+fn socials() -> VirtualPoolSocials {
+    VirtualPoolSocials {
+        description: "a token".to_string(),
+        twitter: "https://x.com/token".to_string(),
+        telegram: "https://t.me/token".to_string(),
+        discord: "https://discord.gg/token".to_string(),
+        tags: vec!["meme".to_string(), "ai".to_string()],
+    }
+}
+
+#[test]
+fn test_deserialize_v0_virtual_pool_metadata() {
+    let virtual_pool = Pubkey::new_unique();
+    let (name, website, logo) = ("token", "token.com", "token.com/logo.png");
+
+    // original layout ends after logo
+    let mut data = VirtualPoolMetadata::DISCRIMINATOR.to_vec();
+    (virtual_pool, [0u128; 6], name, website, logo)
+        .serialize(&mut data)
+        .unwrap();
+
+    let metadata = VirtualPoolMetadata::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(metadata.virtual_pool, virtual_pool);
+    assert_eq!(metadata.version, u8::from(VirtualPoolMetadataVersion::V0));
+    assert_eq!(metadata.name, name);
+    assert_eq!(metadata.website, website);
+    assert_eq!(metadata.logo, logo);
+    assert_eq!(metadata.socials, VirtualPoolSocials::default());
+}
+
+#[test]
+fn test_serialize_v1_virtual_pool_metadata() {
+    let metadata = VirtualPoolMetadata {
+        virtual_pool: Pubkey::new_unique(),
+        version: VirtualPoolMetadataVersion::V1.into(),
+        name: "token".to_string(),
+        website: "token.com".to_string(),
+        logo: "token.com/logo.png".to_string(),
+        socials: socials(),
+        ..Default::default()
+    };
+
+    let mut data = vec![];
+    metadata.try_serialize(&mut data).unwrap();
+    assert_eq!(
+        data.len(),
+        8 + VirtualPoolMetadata::space(
+            &metadata.name,
+            &metadata.website,
+            &metadata.logo,
+            &metadata.socials
+        )
+    );
+
+    let deserialized = VirtualPoolMetadata::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(deserialized.version, metadata.version);
+    assert_eq!(deserialized.logo, metadata.logo);
+    assert_eq!(deserialized.socials, metadata.socials);
+}
+
+#[test]
+fn test_validate_virtual_pool_metadata() {
+    assert!(VirtualPoolMetadata::validate("token", "token.com", "", &socials()).is_ok());
+    assert!(VirtualPoolMetadata::validate(
+        &"a".repeat(MAX_POOL_METADATA_NAME_LENGTH + 1),
+        "",
+        "",
+        &socials()
+    )
+    .is_err());
+
+    let mut invalid_socials = socials();
+    invalid_socials.description = "a".repeat(MAX_POOL_METADATA_DESCRIPTION_LENGTH + 1);
+    assert!(invalid_socials.validate().is_err());
+
+    let mut invalid_socials = socials();
+    invalid_socials.tags = vec!["tag".to_string(); MAX_POOL_METADATA_TAGS + 1];
+    assert!(invalid_socials.validate().is_err());
+
+    let mut invalid_socials = socials();
+    invalid_socials.tags = vec!["a".repeat(MAX_POOL_METADATA_TAG_LENGTH + 1)];
+    assert!(invalid_socials.validate().is_err());
+
+    let mut invalid_socials = socials();
+    invalid_socials.tags = vec![String::new()];
+    assert!(invalid_socials.validate().is_err());
+}
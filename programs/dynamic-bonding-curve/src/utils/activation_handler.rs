@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use std::convert::TryFrom;
 
-use crate::PoolError;
+use crate::{constants::SLOT_DURATION_IN_MS, safe_math::SafeMath, PoolError};
 
 #[derive(
     Copy,
@@ -32,6 +32,39 @@ pub fn get_current_point(activation_type: u8) -> Result<u64> {
     Ok(current_point)
 }
 
+/// Unix timestamp of an activation point. Slot activation point is converted from the slots elapsed since (or
+/// remaining until) the current slot at SLOT_DURATION_IN_MS per slot, so it's an estimate that drifts when slots
+/// are slower or faster than expected
+pub fn get_activation_timestamp(
+    activation_type: u8,
+    activation_point: u64,
+    clock: &Clock,
+) -> Result<u64> {
+    let activation_type =
+        ActivationType::try_from(activation_type).map_err(|_| PoolError::InvalidActivationType)?;
+    let current_timestamp = clock.unix_timestamp as u64;
+    let activation_timestamp = match activation_type {
+        ActivationType::Slot => {
+            if activation_point <= clock.slot {
+                let elapsed_seconds = clock
+                    .slot
+                    .safe_sub(activation_point)?
+                    .safe_mul(SLOT_DURATION_IN_MS)?
+                    .safe_div(1000)?;
+                current_timestamp.saturating_sub(elapsed_seconds)
+            } else {
+                let remaining_seconds = activation_point
+                    .safe_sub(clock.slot)?
+                    .safe_mul(SLOT_DURATION_IN_MS)?
+                    .safe_div(1000)?;
+                current_timestamp.safe_add(remaining_seconds)?
+            }
+        }
+        ActivationType::Timestamp => activation_point,
+    };
+    Ok(activation_timestamp)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,6 +91,29 @@ mod tests {
         assert_eq!(result.unwrap_err(), PoolError::InvalidActivationType.into());
     }
 
+    #[test]
+    fn test_get_activation_timestamp() {
+        let clock = Clock {
+            slot: 1_000,
+            unix_timestamp: 10_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            get_activation_timestamp(ActivationType::Timestamp.into(), 5_000, &clock).unwrap(),
+            5_000
+        );
+        // 500 slots ago at 400ms per slot
+        assert_eq!(
+            get_activation_timestamp(ActivationType::Slot.into(), 500, &clock).unwrap(),
+            9_800
+        );
+        assert_eq!(
+            get_activation_timestamp(ActivationType::Slot.into(), 1_500, &clock).unwrap(),
+            10_200
+        );
+        assert!(get_activation_timestamp(2, 500, &clock).is_err());
+    }
+
     // Note: We cannot directly test get_current_point with slot/timestamp
     // as it requires access to the Clock sysvar which is not available in unit tests.
     // These tests should be done in integration tests or program tests instead.
@@ -1,2 +1,3 @@
 pub mod activation_handler;
+pub mod rent;
 pub mod token;
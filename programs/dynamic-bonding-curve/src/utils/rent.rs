@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction::transfer,
+};
+
+use crate::{const_pda, safe_math::SafeMath};
+
+/// Rent exempt lamports of accounts with the given data lengths
+pub fn get_rent_exempt_lamports(account_spaces: &[usize]) -> Result<u64> {
+    let rent = Rent::get()?;
+    let mut lamports: u64 = 0;
+    for &space in account_spaces {
+        lamports = lamports.safe_add(rent.minimum_balance(space))?;
+    }
+    Ok(lamports)
+}
+
+/// Fund pool authority to pay rent of accounts created by a cpi it signs as payer. Returns lamports of pool
+/// authority before funding, which [refund_pool_authority] keeps after the cpi
+pub fn fund_pool_authority<'info>(
+    payer: AccountInfo<'info>,
+    pool_authority: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    lamports: u64,
+) -> Result<u64> {
+    let lamports_before = pool_authority.lamports();
+    msg!("transfer {} lamports to pool_authority", lamports);
+    invoke(
+        &transfer(payer.key, pool_authority.key, lamports),
+        &[payer, pool_authority, system_program],
+    )?;
+    Ok(lamports_before)
+}
+
+/// Refund lamports of pool authority not spent by the cpi to payer, so they don't accumulate in pool authority
+pub fn refund_pool_authority<'info>(
+    pool_authority: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    lamports_before: u64,
+) -> Result<()> {
+    let unspent_lamports = pool_authority.lamports().saturating_sub(lamports_before);
+    if unspent_lamports > 0 {
+        let seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+        invoke_signed(
+            &transfer(pool_authority.key, payer.key, unspent_lamports),
+            &[pool_authority, payer, system_program],
+            &[&seeds[..]],
+        )?;
+    }
+    Ok(())
+}
@@ -2,18 +2,29 @@ use anchor_lang::prelude::*;
 use anchor_lang::{
     prelude::InterfaceAccount,
     solana_program::program::{invoke, invoke_signed},
+    solana_program::program_pack::Pack,
     solana_program::system_instruction::transfer,
 };
 use anchor_spl::{
     token::Token,
     token_2022::spl_token_2022::{
         self,
-        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        extension::{
+            default_account_state::DefaultAccountState,
+            transfer_fee::{TransferFee, TransferFeeConfig, MAX_FEE_BASIS_POINTS},
+            BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+        },
+        state::AccountState,
+    },
+    token_2022_extensions::{default_account_state_update, DefaultAccountStateUpdate},
+    token_interface::{
+        freeze_account, thaw_account, FreezeAccount, Mint, ThawAccount, TokenAccount,
+        TokenInterface,
     },
-    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
+use crate::const_pda;
 use crate::safe_math::SafeMath;
 use crate::PoolError;
 
@@ -105,25 +116,241 @@ pub fn transfer_from_pool<'c: 'info, 'info>(
     Ok(())
 }
 
+/// Whether the base mint is non-transferable until migration: its token accounts are created frozen by default
+/// account state extension, and pool authority, the freeze authority, thaws them for the transfers of the program
+pub fn is_base_transfer_restricted(mint_info: &AccountInfo) -> Result<bool> {
+    if *mint_info.owner == Token::id() {
+        return Ok(false);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(mint
+        .get_extension::<DefaultAccountState>()
+        .is_ok_and(|default_account_state| {
+            default_account_state.state == AccountState::Frozen as u8
+        }))
+}
+
+/// Thaw a base token account frozen by pool authority, returns false when the account isn't frozen
+pub fn thaw_base_token_account<'info>(
+    pool_authority: AccountInfo<'info>,
+    base_mint: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<bool> {
+    let is_frozen = {
+        let token_account_data = token_account.try_borrow_data()?;
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_account_data)?
+            .base
+            .is_frozen()
+    };
+    if !is_frozen {
+        return Ok(false);
+    }
+
+    let signer_seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+    thaw_account(CpiContext::new_with_signer(
+        token_program,
+        ThawAccount {
+            account: token_account,
+            mint: base_mint,
+            authority: pool_authority,
+        },
+        &[&signer_seeds[..]],
+    ))?;
+    Ok(true)
+}
+
+pub fn freeze_base_token_account<'info>(
+    pool_authority: AccountInfo<'info>,
+    base_mint: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let signer_seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+    freeze_account(CpiContext::new_with_signer(
+        token_program,
+        FreezeAccount {
+            account: token_account,
+            mint: base_mint,
+            authority: pool_authority,
+        },
+        &[&signer_seeds[..]],
+    ))
+}
+
+/// Thaw a base token account for a transfer of the program while the base mint is non-transferable, returns
+/// whether the account must be frozen again after the transfer
+pub fn thaw_restricted_base_token_account<'info>(
+    pool_authority: AccountInfo<'info>,
+    base_mint: AccountInfo<'info>,
+    token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<bool> {
+    if !is_base_transfer_restricted(&base_mint)? {
+        return Ok(false);
+    }
+    thaw_base_token_account(pool_authority, base_mint, token_account, token_program)
+}
+
+/// Make a non-transferable base mint transferable, token accounts are created initialized from now on. Accounts
+/// created before stay frozen until thawed with `thaw_base_token_account` instruction. Returns false when the
+/// base mint isn't restricted
+pub fn lift_base_transfer_restriction<'info>(
+    pool_authority: AccountInfo<'info>,
+    base_mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<bool> {
+    if !is_base_transfer_restricted(&base_mint)? {
+        return Ok(false);
+    }
+
+    let signer_seeds = pool_authority_seeds!(const_pda::pool_authority::BUMP);
+    default_account_state_update(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            DefaultAccountStateUpdate {
+                token_program_id: token_program,
+                mint: base_mint,
+                freeze_authority: pool_authority,
+            },
+            &[&signer_seeds[..]],
+        ),
+        &AccountState::Initialized,
+    )?;
+    Ok(true)
+}
+
 pub fn is_supported_quote_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
     let mint_info = mint_account.to_account_info();
     if *mint_info.owner == Token::id() {
         return Ok(true);
     }
 
-    if spl_token_2022::native_mint::check_id(&mint_account.key()) {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let extensions = mint.get_extension_types()?;
+    is_supported_token_2022_quote_mint(&mint_account.key(), &extensions)
+}
+
+/// Token 2022 quote mint check from the mint address and its extensions
+pub fn is_supported_token_2022_quote_mint(
+    mint: &Pubkey,
+    extensions: &[ExtensionType],
+) -> Result<bool> {
+    if spl_token_2022::native_mint::check_id(mint) {
         return Err(PoolError::UnsupportNativeMintToken2022.into());
     }
+    Ok(is_supported_quote_mint_extensions(extensions))
+}
+
+/// Existing base mint can't have freeze authority, otherwise base vault could be frozen.
+/// Token 2022 base mint is only supported with metadata extensions, transfer fee of an existing mint can be
+/// updated by its authority and wouldn't be covered by migration base threshold
+pub fn is_supported_base_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
+    if mint_account.freeze_authority.is_some() {
+        return Ok(false);
+    }
+    let mint_info = mint_account.to_account_info();
+    if *mint_info.owner == Token::id() {
+        return Ok(true);
+    }
+
+    if spl_token_2022::native_mint::check_id(&mint_account.key()) {
+        return Ok(false);
+    }
 
     let mint_data = mint_info.try_borrow_data()?;
     let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
     let extensions = mint.get_extension_types()?;
-    for e in extensions {
-        if e != ExtensionType::MetadataPointer && e != ExtensionType::TokenMetadata {
-            return Ok(false);
-        }
+    Ok(is_supported_base_mint_extensions(&extensions))
+}
+
+pub fn is_supported_base_mint_extensions(extensions: &[ExtensionType]) -> bool {
+    extensions
+        .iter()
+        .all(|e| *e == ExtensionType::MetadataPointer || *e == ExtensionType::TokenMetadata)
+}
+
+/// token 2022 quote mint is only supported if it doesn't carry any extension other than metadata and transfer fee
+pub fn is_supported_quote_mint_extensions(extensions: &[ExtensionType]) -> bool {
+    extensions.iter().all(|e| {
+        *e == ExtensionType::MetadataPointer
+            || *e == ExtensionType::TokenMetadata
+            || *e == ExtensionType::TransferFeeConfig
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferFeeIncludedAmount {
+    pub amount: u64,
+    pub transfer_fee: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferFeeExcludedAmount {
+    pub amount: u64,
+    pub transfer_fee: u64,
+}
+
+/// Transfer fee of the mint at current epoch, none if mint doesn't have transfer fee extension
+pub fn get_epoch_transfer_fee(mint_info: &AccountInfo) -> Result<Option<TransferFee>> {
+    if *mint_info.owner == Token::id() {
+        return Ok(None);
     }
-    Ok(true)
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+        let epoch = Clock::get()?.epoch;
+        return Ok(Some(*transfer_fee_config.get_epoch_fee(epoch)));
+    }
+
+    Ok(None)
+}
+
+/// Space of a token account of the mint, with the account extensions required by the mint and immutable owner
+/// for token 2022
+pub fn get_token_account_space(mint_info: &AccountInfo) -> Result<usize> {
+    if *mint_info.owner == Token::id() {
+        return Ok(spl_token_2022::state::Account::LEN);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let mut account_extensions =
+        ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+    account_extensions.push(ExtensionType::ImmutableOwner);
+    Ok(ExtensionType::try_calculate_account_len::<
+        spl_token_2022::state::Account,
+    >(&account_extensions)?)
+}
+
+/// Amount received by destination when transferring transfer_fee_included_amount of the mint
+pub fn calculate_transfer_fee_excluded_amount(
+    mint_info: &AccountInfo,
+    transfer_fee_included_amount: u64,
+) -> Result<TransferFeeExcludedAmount> {
+    let epoch_transfer_fee = get_epoch_transfer_fee(mint_info)?;
+    get_transfer_fee_excluded_amount(epoch_transfer_fee.as_ref(), transfer_fee_included_amount)
+}
+
+pub fn get_transfer_fee_excluded_amount(
+    epoch_transfer_fee: Option<&TransferFee>,
+    transfer_fee_included_amount: u64,
+) -> Result<TransferFeeExcludedAmount> {
+    let transfer_fee = match epoch_transfer_fee {
+        Some(epoch_transfer_fee) => epoch_transfer_fee
+            .calculate_fee(transfer_fee_included_amount)
+            .ok_or(PoolError::MathOverflow)?,
+        None => 0,
+    };
+    let amount = transfer_fee_included_amount.safe_sub(transfer_fee)?;
+    Ok(TransferFeeExcludedAmount {
+        amount,
+        transfer_fee,
+    })
 }
 
 pub fn update_account_lamports_to_minimum_balance<'info>(
@@ -143,3 +370,27 @@ pub fn update_account_lamports_to_minimum_balance<'info>(
 
     Ok(())
 }
+
+/// Amount to transfer so destination receives transfer_fee_excluded_amount of the mint
+pub fn get_transfer_fee_included_amount(
+    epoch_transfer_fee: Option<&TransferFee>,
+    transfer_fee_excluded_amount: u64,
+) -> Result<TransferFeeIncludedAmount> {
+    let transfer_fee = match epoch_transfer_fee {
+        Some(epoch_transfer_fee) if transfer_fee_excluded_amount > 0 => {
+            if u16::from(epoch_transfer_fee.transfer_fee_basis_points) == MAX_FEE_BASIS_POINTS {
+                u64::from(epoch_transfer_fee.maximum_fee)
+            } else {
+                epoch_transfer_fee
+                    .calculate_inverse_fee(transfer_fee_excluded_amount)
+                    .ok_or(PoolError::MathOverflow)?
+            }
+        }
+        _ => 0,
+    };
+    let amount = transfer_fee_excluded_amount.safe_add(transfer_fee)?;
+    Ok(TransferFeeIncludedAmount {
+        amount,
+        transfer_fee,
+    })
+}
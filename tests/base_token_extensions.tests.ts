@@ -0,0 +1,269 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithToken2022,
+  swap,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import {
+  derivePoolAuthority,
+  designCurve,
+  fundSol,
+  processTransactionMaybeThrow,
+  startTest,
+} from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { expect } from "chai";
+import { createToken, getTokenAccount, mintSplTokenTo } from "./utils/token";
+import {
+  AccountState,
+  createAssociatedTokenAccountIdempotentInstruction,
+  createTransferCheckedInstruction,
+  getAssociatedTokenAddressSync,
+  getDefaultAccountState,
+  getInterestBearingMintConfigState,
+  TOKEN_2022_PROGRAM_ID,
+  unpackMint,
+} from "@solana/spl-token";
+
+describe("Token2022 base mint with interest and non-transferable until migration", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let config: PublicKey;
+  let virtualPool: PublicKey;
+  let baseMint: PublicKey;
+  // 5% interest rate
+  const interestRate = 500;
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    user = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      user.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      admin,
+      quoteMint,
+      admin,
+      user.publicKey,
+      1_000_000_000_000
+    );
+  });
+
+  it("Partner create config with interest bearing and non-transferable base mint", async () => {
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      3, // external program
+      6,
+      9,
+      0,
+      0, // collect fee on quote token
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    instructionParams.tokenType = 1; // token 2022
+    instructionParams.migrationProgram = Keypair.generate().publicKey;
+    instructionParams.baseInterestRate = interestRate;
+    instructionParams.baseNonTransferableFlag = 1;
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(context.banksClient, program, params);
+  });
+
+  it("Create pool, base mint accounts are frozen by default", async () => {
+    virtualPool = await createPoolWithToken2022(
+      context.banksClient,
+      program,
+      {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams: {
+          name: "test token",
+          symbol: "TEST",
+          uri: "abc.com",
+        },
+      }
+    );
+    const poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    baseMint = poolState.baseMint;
+    const mintAccount = await context.banksClient.getAccount(baseMint);
+    const mint = unpackMint(
+      baseMint,
+      {
+        ...mintAccount,
+        data: Buffer.from(mintAccount.data),
+      },
+      TOKEN_2022_PROGRAM_ID
+    );
+    const interestBearingConfig = getInterestBearingMintConfigState(mint);
+    expect(interestBearingConfig.currentRate).eq(interestRate);
+    expect(interestBearingConfig.rateAuthority.toBase58()).eq(
+      PublicKey.default.toBase58()
+    );
+    expect(getDefaultAccountState(mint).state).eq(AccountState.Frozen);
+    expect(mint.freezeAuthority.toBase58()).eq(
+      derivePoolAuthority().toBase58()
+    );
+
+    const baseVault = await getTokenAccount(
+      context.banksClient,
+      poolState.baseVault
+    );
+    expect(baseVault.state).eq(AccountState.Initialized);
+  });
+
+  it("User swaps base token, but can't transfer it", async () => {
+    await swap(context.banksClient, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: baseMint,
+      amountIn: new BN(10_000_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    const userBaseTokenAccount = getAssociatedTokenAddressSync(
+      baseMint,
+      user.publicKey,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    let userBaseTokenState = await getTokenAccount(
+      context.banksClient,
+      userBaseTokenAccount
+    );
+    expect(userBaseTokenState.state).eq(AccountState.Frozen);
+    const baseAmount = new BN(userBaseTokenState.amount.toString());
+    expect(baseAmount.gtn(0)).eq(true);
+
+    // transfer to another holder fails
+    const receiver = Keypair.generate().publicKey;
+    const receiverBaseTokenAccount = getAssociatedTokenAddressSync(
+      baseMint,
+      receiver,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const transaction = new Transaction().add(
+      createAssociatedTokenAccountIdempotentInstruction(
+        user.publicKey,
+        receiverBaseTokenAccount,
+        receiver,
+        baseMint,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createTransferCheckedInstruction(
+        userBaseTokenAccount,
+        baseMint,
+        receiverBaseTokenAccount,
+        user.publicKey,
+        BigInt(baseAmount.toString()),
+        6,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    transaction.recentBlockhash = (
+      await context.banksClient.getLatestBlockhash()
+    )[0];
+    transaction.sign(user);
+    let transferFailed = false;
+    try {
+      await processTransactionMaybeThrow(context.banksClient, transaction);
+    } catch (e) {
+      transferFailed = true;
+    }
+    expect(transferFailed).eq(true);
+
+    // selling back to the pool is allowed, the account stays frozen
+    await swap(context.banksClient, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: baseMint,
+      outputTokenMint: quoteMint,
+      amountIn: baseAmount.divn(2),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+    userBaseTokenState = await getTokenAccount(
+      context.banksClient,
+      userBaseTokenAccount
+    );
+    expect(userBaseTokenState.state).eq(AccountState.Frozen);
+    expect(userBaseTokenState.amount.toString()).eq(
+      baseAmount.sub(baseAmount.divn(2)).toString()
+    );
+  });
+
+  it("Base token account can't be thawed before migration", async () => {
+    const transaction = await program.methods
+      .thawBaseTokenAccount()
+      .accountsPartial({
+        poolAuthority: derivePoolAuthority(),
+        virtualPool,
+        baseTokenAccount: getAssociatedTokenAddressSync(
+          baseMint,
+          user.publicKey,
+          true,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        baseMint,
+        tokenBaseProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .transaction();
+    transaction.recentBlockhash = (
+      await context.banksClient.getLatestBlockhash()
+    )[0];
+    transaction.sign(user);
+    let thawFailed = false;
+    try {
+      await processTransactionMaybeThrow(context.banksClient, transaction);
+    } catch (e) {
+      thawFailed = true;
+    }
+    expect(thawFailed).eq(true);
+  });
+});
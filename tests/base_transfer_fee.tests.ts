@@ -0,0 +1,183 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithToken2022,
+  swap,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { designCurve, fundSol, startTest } from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { getConfig, getVirtualPool } from "./utils/fetcher";
+import { expect } from "chai";
+import { createToken, getTokenAccount, mintSplTokenTo } from "./utils/token";
+import {
+  getAssociatedTokenAddressSync,
+  getTransferFeeConfig,
+  TOKEN_2022_PROGRAM_ID,
+  unpackMint,
+} from "@solana/spl-token";
+
+describe("Token2022 base mint with transfer fee", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let config: PublicKey;
+  let virtualPool: PublicKey;
+  // 1% transfer fee
+  const transferFeeBasisPoints = 100;
+  const transferFeeMaximumFee = new BN(1_000_000_000_000);
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    user = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      user.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      admin,
+      quoteMint,
+      admin,
+      user.publicKey,
+      1_000_000_000_000
+    );
+  });
+
+  it("Partner create config with base transfer fee", async () => {
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      1, // damm v2
+      6,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    instructionParams.tokenType = 1; // token 2022
+    instructionParams.baseTransferFeeBasisPoints = transferFeeBasisPoints;
+    instructionParams.baseTransferFeeMaximumFee = transferFeeMaximumFee;
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(context.banksClient, program, params);
+    const configState = await getConfig(context.banksClient, program, config);
+    expect(configState.tokenType).eq(1);
+  });
+
+  it("Create pool, base mint charges transfer fee", async () => {
+    virtualPool = await createPoolWithToken2022(
+      context.banksClient,
+      program,
+      {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams: {
+          name: "test token",
+          symbol: "TEST",
+          uri: "abc.com",
+        },
+      }
+    );
+    const poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    const mintAccount = await context.banksClient.getAccount(
+      poolState.baseMint
+    );
+    const mint = unpackMint(
+      poolState.baseMint,
+      {
+        ...mintAccount,
+        data: Buffer.from(mintAccount.data),
+      },
+      TOKEN_2022_PROGRAM_ID
+    );
+    const transferFeeConfig = getTransferFeeConfig(mint);
+    expect(transferFeeConfig.transferFeeConfigAuthority.toBase58()).eq(
+      PublicKey.default.toBase58()
+    );
+    expect(transferFeeConfig.withdrawWithheldAuthority.toBase58()).eq(
+      poolCreator.publicKey.toBase58()
+    );
+    expect(
+      transferFeeConfig.newerTransferFee.transferFeeBasisPoints
+    ).eq(transferFeeBasisPoints);
+  });
+
+  it("User receives base amount excluding transfer fee", async () => {
+    let poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    const baseReserveBefore = poolState.baseReserve;
+    await swap(context.banksClient, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: poolState.baseMint,
+      amountIn: new BN(10_000_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    poolState = await getVirtualPool(context.banksClient, program, virtualPool);
+    const outputAmount = baseReserveBefore.sub(poolState.baseReserve);
+    // transfer fee is rounded up
+    const transferFee = BN.min(
+      outputAmount
+        .muln(transferFeeBasisPoints)
+        .addn(9_999)
+        .divn(10_000),
+      transferFeeMaximumFee
+    );
+    const userBaseTokenAccount = await getTokenAccount(
+      context.banksClient,
+      getAssociatedTokenAddressSync(
+        poolState.baseMint,
+        user.publicKey,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    expect(userBaseTokenAccount.amount.toString()).eq(
+      outputAmount.sub(transferFee).toString()
+    );
+  });
+});
@@ -0,0 +1,174 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  closeConfig,
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  deprecateConfig,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { designCurve, fundSol, startTest } from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { getConfig } from "./utils/fetcher";
+import { assert, expect } from "chai";
+import { createToken } from "./utils/token";
+
+describe("Close config", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+  });
+
+  async function createClosableConfig(
+    closeAuthority: PublicKey,
+    config?: Keypair
+  ): Promise<PublicKey> {
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    instructionParams.closeAuthority = closeAuthority;
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+      config,
+    };
+    return createConfig(context.banksClient, program, params);
+  }
+
+  async function createPool(config: PublicKey) {
+    await createPoolWithSplToken(context.banksClient, program, {
+      payer: poolCreator,
+      poolCreator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token spl",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+  }
+
+  it("Close authority close config without pool", async () => {
+    const config = await createClosableConfig(partner.publicKey);
+    await closeConfig(context.banksClient, program, {
+      closeAuthority: partner,
+      config,
+      rentReceiver: partner.publicKey,
+    });
+  });
+
+  it("Config without close authority can not be closed", async () => {
+    const config = await createClosableConfig(PublicKey.default);
+    try {
+      await closeConfig(context.banksClient, program, {
+        closeAuthority: partner,
+        config,
+        rentReceiver: partner.publicKey,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+
+  it("Closed config can not be created again", async () => {
+    const configKeypair = Keypair.generate();
+    const config = await createClosableConfig(
+      partner.publicKey,
+      configKeypair
+    );
+    await closeConfig(context.banksClient, program, {
+      closeAuthority: partner,
+      config,
+      rentReceiver: partner.publicKey,
+    });
+
+    try {
+      await createClosableConfig(partner.publicKey, configKeypair);
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+
+  it("Config with pool can not be closed, even after admin deprecate it", async () => {
+    const config = await createClosableConfig(partner.publicKey);
+    await createPool(config);
+
+    const configState = await getConfig(context.banksClient, program, config);
+    expect(configState.poolCount).eq(1);
+
+    try {
+      await closeConfig(context.banksClient, program, {
+        closeAuthority: partner,
+        config,
+        rentReceiver: partner.publicKey,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+
+    await deprecateConfig(context.banksClient, program, admin, config);
+
+    // no new pool on deprecated config
+    try {
+      await createPool(config);
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+
+    // the pool still loads the config
+    try {
+      await closeConfig(context.banksClient, program, {
+        closeAuthority: partner,
+        config,
+        rentReceiver: partner.publicKey,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+});
@@ -0,0 +1,162 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithExistingToken,
+  swap,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { designCurve, fundSol, startTest } from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { assert, expect } from "chai";
+import { createToken, getTokenAccount, mintSplTokenTo } from "./utils/token";
+
+describe("Initialize pool with existing token", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let baseMint: PublicKey;
+  let config: PublicKey;
+  const totalTokenSupply = 1_000_000_000;
+  const baseDecimal = 6;
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    user = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      user.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      admin,
+      quoteMint,
+      admin,
+      user.publicKey,
+      1_000_000_000_000
+    );
+    baseMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      baseDecimal
+    );
+
+    const instructionParams = designCurve(
+      totalTokenSupply,
+      10,
+      300,
+      1, // damm v2
+      baseDecimal,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(context.banksClient, program, params);
+  });
+
+  it("Pool creation without enough base token is rejected", async () => {
+    await mintSplTokenTo(
+      context.banksClient,
+      admin,
+      baseMint,
+      admin,
+      poolCreator.publicKey,
+      1_000
+    );
+    try {
+      await createPoolWithExistingToken(context.banksClient, program, {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        baseMint,
+        config,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+
+  it("Pool creation deposits initial base supply", async () => {
+    const initialBaseSupply = new BN(totalTokenSupply).mul(
+      new BN(10).pow(new BN(baseDecimal))
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      admin,
+      baseMint,
+      admin,
+      partner.publicKey,
+      initialBaseSupply.toNumber()
+    );
+    const virtualPool = await createPoolWithExistingToken(
+      context.banksClient,
+      program,
+      {
+        payer: partner,
+        poolCreator,
+        quoteMint,
+        baseMint,
+        config,
+      }
+    );
+    let poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    expect(poolState.baseMint.toBase58()).eq(baseMint.toBase58());
+    expect(poolState.baseReserve.toString()).eq(initialBaseSupply.toString());
+    const baseVault = await getTokenAccount(
+      context.banksClient,
+      poolState.baseVault
+    );
+    expect(baseVault.amount.toString()).eq(initialBaseSupply.toString());
+
+    await swap(context.banksClient, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: baseMint,
+      amountIn: new BN(10_000_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+    poolState = await getVirtualPool(context.banksClient, program, virtualPool);
+    expect(poolState.baseReserve.lt(initialBaseSupply)).eq(true);
+  });
+});
@@ -82,6 +82,29 @@ export async function closeClaimFeeOperator(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
+export async function deprecateConfig(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  admin: Keypair,
+  config: PublicKey
+) {
+  const transaction = await program.methods
+    .deprecateConfig()
+    .accountsPartial({
+      config,
+      admin: admin.publicKey,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(admin);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  const configState = await getConfig(banksClient, program, config);
+  expect(configState.isDeprecated).eq(1);
+}
+
 export type ClaimProtocolFeeParams = {
   operator: Keypair;
   pool: PublicKey;
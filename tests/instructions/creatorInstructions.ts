@@ -192,6 +192,42 @@ export async function transferCreator(
   await processTransactionMaybeThrow(banksClient, transaction);
 }
 
+export type TokenMetadataField = {
+  key: string;
+  // empty value removes the key
+  value: string;
+};
+
+export type UpdateTokenMetadataFieldsParams = {
+  // metadata update authority of base mint, pool creator at launch
+  updateAuthority: Keypair;
+  virtualPool: PublicKey;
+  fields: TokenMetadataField[];
+};
+export async function updateTokenMetadataFields(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: UpdateTokenMetadataFieldsParams
+): Promise<void> {
+  const { updateAuthority, virtualPool, fields } = params;
+  const poolState = await getVirtualPool(banksClient, program, virtualPool);
+  const transaction = await program.methods
+    .updateTokenMetadataFields(fields)
+    .accountsPartial({
+      virtualPool,
+      config: poolState.config,
+      baseMint: poolState.baseMint,
+      updateAuthority: updateAuthority.publicKey,
+      payer: updateAuthority.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(updateAuthority);
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
 export type CreatorWithdrawMigrationFeeParams = {
   creator: Keypair;
   virtualPool: PublicKey;
@@ -129,6 +129,9 @@ export async function migrateToDammV2(
             token2022Program: TOKEN_2022_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
             dammEventAuthority: deriveDammV2EventAuthority(),
+            crankRewardTokenAccount: null,
+            eventAuthority: deriveEventAuthority(program.programId),
+            program: program.programId,
         }).remainingAccounts([
             {
                 isSigner: false,
@@ -147,6 +150,13 @@ export async function migrateToDammV2(
     await processTransactionMaybeThrow(banksClient, transaction);
 }
 
+export function deriveEventAuthority(programId: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("__event_authority")],
+        programId
+    )[0];
+}
+
 export function deriveDammV2EventAuthority() {
     return PublicKey.findProgramAddressSync(
         [Buffer.from("__event_authority")],
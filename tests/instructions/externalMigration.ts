@@ -0,0 +1,103 @@
+import {
+    AccountMeta,
+    ComputeBudgetProgram,
+    Keypair,
+    PublicKey,
+} from "@solana/web3.js";
+import {
+    getVirtualPool,
+    processTransactionMaybeThrow,
+    VirtualCurveProgram,
+    getConfig,
+    derivePoolAuthority,
+} from "../utils";
+import { BanksClient } from "solana-bankrun";
+import {
+    getAssociatedTokenAddressSync,
+    TOKEN_2022_PROGRAM_ID,
+    TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+export type MigrateExternalProgramParams = {
+    payer: Keypair;
+    virtualPool: PublicKey;
+    migrationProgram: PublicKey;
+    remainingAccounts?: AccountMeta[];
+};
+
+export function deriveExternalMigrationAuthority(
+    virtualPool: PublicKey,
+    migrationProgram: PublicKey
+): PublicKey {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("dbc_migration"), virtualPool.toBuffer()],
+        migrationProgram
+    )[0];
+}
+
+export async function migrateExternalProgram(
+    banksClient: BanksClient,
+    program: VirtualCurveProgram,
+    params: MigrateExternalProgramParams
+): Promise<any> {
+    const { payer, virtualPool, migrationProgram } = params;
+    const virtualPoolState = await getVirtualPool(
+        banksClient,
+        program,
+        virtualPool
+    );
+    const configState = await getConfig(
+        banksClient,
+        program,
+        virtualPoolState.config
+    );
+
+    const migrationAuthority = deriveExternalMigrationAuthority(
+        virtualPool,
+        migrationProgram
+    );
+    const tokenBaseProgram =
+        configState.tokenType == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+    const tokenQuoteProgram =
+        configState.quoteTokenFlag == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+
+    // migration signer, event authority and program are resolved by anchor
+    const transaction = await program.methods
+        .migrateExternalProgram()
+        .accountsPartial({
+            virtualPool,
+            config: virtualPoolState.config,
+            poolAuthority: derivePoolAuthority(),
+            migrationAuthority,
+            baseTokenAccount: getAssociatedTokenAddressSync(
+                virtualPoolState.baseMint,
+                migrationAuthority,
+                true,
+                tokenBaseProgram
+            ),
+            quoteTokenAccount: getAssociatedTokenAddressSync(
+                configState.quoteMint,
+                migrationAuthority,
+                true,
+                tokenQuoteProgram
+            ),
+            baseMint: virtualPoolState.baseMint,
+            quoteMint: configState.quoteMint,
+            baseVault: virtualPoolState.baseVault,
+            quoteVault: virtualPoolState.quoteVault,
+            migrationProgram,
+            payer: payer.publicKey,
+            tokenBaseProgram,
+            tokenQuoteProgram,
+        })
+        .remainingAccounts(params.remainingAccounts ?? [])
+        .transaction();
+    transaction.add(
+        ComputeBudgetProgram.setComputeUnitLimit({
+            units: 400_000,
+        })
+    );
+    transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+    transaction.sign(payer);
+    await processTransactionMaybeThrow(banksClient, transaction);
+}
@@ -4,4 +4,5 @@ export * from "./userInstructions";
 export * from "./meteoraMigration";
 export * from "./dammV2Migration";
 export * from "./lockerInstructions";
-export * from "./creatorInstructions";
\ No newline at end of file
+export * from "./creatorInstructions";
+export * from "./externalMigration";
\ No newline at end of file
@@ -161,6 +161,7 @@ export async function migrateToMeteoraDamm(
       vaultProgram: VAULT_PROGRAM_ID,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      crankRewardTokenAccount: null,
     })
     .transaction();
   transaction.add(
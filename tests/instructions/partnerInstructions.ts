@@ -46,6 +46,22 @@ export type DynamicFee = {
   variableFeeControl: number;
 };
 
+export type MigratedPoolFee = {
+  cliffFeeNumerator: BN;
+  numberOfPeriod: number;
+  periodFrequency: BN;
+  reductionFactor: BN;
+  feeSchedulerMode: number;
+  collectFeeMode: number;
+  partnerFeePercent: number;
+  dynamicFee: DynamicFee | null;
+};
+
+export type MigrationPriceRange = {
+  minPriceBps: number;
+  maxPriceBps: number;
+};
+
 export type LockedVestingParams = {
   amountPerPeriod: BN;
   cliffDurationFromMigrationTime: BN;
@@ -54,6 +70,11 @@ export type LockedVestingParams = {
   cliffUnlockAmount: BN;
 };
 
+export type LockedVestingTrancheParams = {
+  recipient: PublicKey;
+  lockedVesting: LockedVestingParams;
+};
+
 export type TokenSupplyParams = {
   preMigrationTokenSupply: BN;
   postMigrationTokenSupply: BN;
@@ -89,8 +110,33 @@ export type ConfigParameters = {
     feePercentage: number;
     creatorFeePercentage: number;
   };
-  padding0: number[];
-  padding1: BN[];
+  lockedVestingTranches: Array<LockedVestingTrancheParams>;
+  lockedVestingUpdateRecipientMode: number;
+  lockedVestingCancelMode: number;
+  lockedVestingStartAnchor: number;
+  lockedVestingStartTimestamp: BN;
+  poolCreationFeeToken: number;
+  baseTransferFeeBasisPoints: number;
+  metadataCreatorFlag: number;
+  closeAuthority: PublicKey;
+  poolCreatorAuthority: PublicKey;
+  poolCreationFee: BN;
+  poolCreationFeeRecipient: PublicKey;
+  baseTransferFeeMaximumFee: BN;
+  metadataCollectionMint: PublicKey;
+  migrationProgram: PublicKey;
+  migrationCrankReward: BN;
+  migratedPoolFee: MigratedPoolFee | null;
+  migrationPriceRange: MigrationPriceRange | null;
+  dlmmBinStep: number;
+  autoMigrationFlag: number;
+  migrationBaseFeeBps: number;
+  migrationPriceToleranceBps: number;
+  refundDeadlineDuration: BN;
+  refundFeeMode: number;
+  baseInterestRate: number;
+  baseNonTransferableFlag: number;
+  padding: BN[];
   curve: Array<LiquidityDistributionParameters>;
 };
 
@@ -100,6 +146,7 @@ export type CreateConfigParams = {
   feeClaimer: PublicKey;
   quoteMint: PublicKey;
   instructionParams: ConfigParameters;
+  config?: Keypair;
 };
 
 export async function createConfig(
@@ -109,7 +156,7 @@ export async function createConfig(
 ): Promise<PublicKey> {
   const { payer, leftoverReceiver, feeClaimer, quoteMint, instructionParams } =
     params;
-  const config = Keypair.generate();
+  const config = params.config ?? Keypair.generate();
 
   const transaction = await program.methods
     .createConfig(instructionParams)
@@ -192,6 +239,105 @@ export async function createPartnerMetadata(
   expect(metadataState.logo.toString()).equal(logo.toString());
 }
 
+export async function updatePartnerMetadata(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: {
+    name: string;
+    website: string;
+    logo: string;
+    feeClaimer: Keypair;
+    payer: Keypair;
+  }
+) {
+  const { payer, feeClaimer, name, website, logo } = params;
+  const partnerMetadata = derivePartnerMetadata(feeClaimer.publicKey);
+  const transaction = await program.methods
+    .updatePartnerMetadata({
+      name,
+      website,
+      logo,
+    })
+    .accountsPartial({
+      partnerMetadata,
+      feeClaimer: feeClaimer.publicKey,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, feeClaimer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+  //
+  const metadataState = await getPartnerMetadata(
+    banksClient,
+    program,
+    partnerMetadata
+  );
+  expect(metadataState.name.toString()).equal(name.toString());
+  expect(metadataState.website.toString()).equal(website.toString());
+  expect(metadataState.logo.toString()).equal(logo.toString());
+}
+
+export async function closePartnerMetadata(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: {
+    feeClaimer: Keypair;
+    rentReceiver: PublicKey;
+  }
+) {
+  const { feeClaimer, rentReceiver } = params;
+  const partnerMetadata = derivePartnerMetadata(feeClaimer.publicKey);
+  const transaction = await program.methods
+    .closePartnerMetadata()
+    .accountsPartial({
+      partnerMetadata,
+      feeClaimer: feeClaimer.publicKey,
+      rentReceiver,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(feeClaimer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export type CloseConfigParams = {
+  closeAuthority: Keypair;
+  config: PublicKey;
+  rentReceiver: PublicKey;
+};
+
+export async function closeConfig(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: CloseConfigParams
+) {
+  const { closeAuthority, config, rentReceiver } = params;
+  const transaction = await program.methods
+    .closeConfig()
+    .accountsPartial({
+      config,
+      closeAuthority: closeAuthority.publicKey,
+      rentReceiver,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(closeAuthority);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  // closed config keeps the closed discriminator so the address can't be initialized again
+  const configAccount = await banksClient.getAccount(config);
+  expect(configAccount.owner.toBase58()).eq(program.programId.toBase58());
+  expect(Array.from(configAccount.data)).deep.eq(new Array(8).fill(255));
+}
+
 export type ClaimTradeFeeParams = {
   feeClaimer: Keypair;
   pool: PublicKey;
@@ -1,5 +1,7 @@
 import {
+  AccountMeta,
   ComputeBudgetProgram,
+  Ed25519Program,
   Keypair,
   PublicKey,
   SystemProgram,
@@ -46,10 +48,12 @@ import {
 import {
   getOrCreateAssociatedTokenAccount,
   getTokenAccount,
+  getTokenProgram,
   unwrapSOLInstruction,
   wrapSOLInstruction,
 } from "../utils";
 import { expect } from "chai";
+import { TokenMetadataField } from "./creatorInstructions";
 
 export type InitializePoolParameters = {
   name: string;
@@ -62,20 +66,105 @@ export type CreatePoolSplTokenParams = {
   quoteMint: PublicKey;
   config: PublicKey;
   instructionParams: InitializePoolParameters;
+  // approve pool creation on config with pool creator authority
+  poolCreatorAuthority?: Keypair;
+  // approve by ed25519 signed permit instead of co-signing
+  usePoolCreatorPermit?: boolean;
+  // recipient (SOL fee), or payer and recipient quote token accounts (quote token fee)
+  poolCreationFeeAccounts?: PublicKey[];
+  // collection mint, metadata, master edition, collection authority record and collection authority of config to verify metaplex collection
+  metadataCollectionAccounts?: PublicKey[];
 };
 
-export type CreatePoolToken2022Params = CreatePoolSplTokenParams;
+export type CreatePoolToken2022Params = CreatePoolSplTokenParams & {
+  // additional token metadata fields, e.g. twitter, telegram, website, description
+  additionalMetadata?: TokenMetadataField[];
+};
+
+export type CreatePoolExistingTokenParams = Omit<
+  CreatePoolSplTokenParams,
+  "instructionParams"
+> & {
+  baseMint: PublicKey;
+};
+
+function getPoolCreatorApproval(
+  config: PublicKey,
+  poolCreator: PublicKey,
+  baseMint: PublicKey,
+  poolCreatorAuthority?: Keypair,
+  usePoolCreatorPermit?: boolean
+): {
+  remainingAccounts: AccountMeta[];
+  preInstructions: TransactionInstruction[];
+  signers: Keypair[];
+} {
+  if (!poolCreatorAuthority) {
+    return { remainingAccounts: [], preInstructions: [], signers: [] };
+  }
+  if (usePoolCreatorPermit) {
+    const message = Buffer.concat([
+      config.toBuffer(),
+      poolCreator.toBuffer(),
+      baseMint.toBuffer(),
+    ]);
+    return {
+      remainingAccounts: [
+        {
+          isSigner: false,
+          isWritable: false,
+          pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
+        },
+      ],
+      preInstructions: [
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: poolCreatorAuthority.secretKey,
+          message,
+        }),
+      ],
+      signers: [],
+    };
+  }
+  return {
+    remainingAccounts: [
+      {
+        isSigner: true,
+        isWritable: false,
+        pubkey: poolCreatorAuthority.publicKey,
+      },
+    ],
+    preInstructions: [],
+    signers: [poolCreatorAuthority],
+  };
+}
 
 export async function createPoolWithSplToken(
   banksClient: BanksClient,
   program: VirtualCurveProgram,
   params: CreatePoolSplTokenParams
 ): Promise<PublicKey> {
-  const { payer, quoteMint, poolCreator, config, instructionParams } = params;
+  const {
+    payer,
+    quoteMint,
+    poolCreator,
+    config,
+    instructionParams,
+    poolCreatorAuthority,
+    usePoolCreatorPermit,
+    poolCreationFeeAccounts,
+    metadataCollectionAccounts,
+  } = params;
   const configState = await getConfig(banksClient, program, config);
 
   const poolAuthority = derivePoolAuthority();
   const baseMintKP = Keypair.generate();
+  const approval = getPoolCreatorApproval(
+    config,
+    poolCreator.publicKey,
+    baseMintKP.publicKey,
+    poolCreatorAuthority,
+    usePoolCreatorPermit
+  );
   const pool = derivePoolAddress(config, baseMintKP.publicKey, quoteMint);
   const baseVault = deriveTokenVaultAddress(baseMintKP.publicKey, pool);
   const quoteVault = deriveTokenVaultAddress(quoteMint, pool);
@@ -98,13 +187,108 @@ export async function createPoolWithSplToken(
       quoteVault,
       mintMetadata,
       metadataProgram: METAPLEX_PROGRAM_ID,
-      tokenQuoteProgram: TOKEN_PROGRAM_ID,
+      tokenQuoteProgram: getTokenProgram(configState.quoteTokenFlag),
+      tokenProgram,
+    })
+    .remainingAccounts([
+      ...approval.remainingAccounts,
+      ...(poolCreationFeeAccounts ?? []).map((pubkey) => ({
+        isSigner: false,
+        isWritable: true,
+        pubkey,
+      })),
+      ...(metadataCollectionAccounts ?? []).map((pubkey, i) => ({
+        isSigner: false,
+        // collection metadata is written by collection verification
+        isWritable: i == 1,
+        pubkey,
+      })),
+      ...(metadataCollectionAccounts
+        ? [
+            {
+              isSigner: false,
+              isWritable: false,
+              pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
+            },
+          ]
+        : []),
+    ])
+    .preInstructions(approval.preInstructions)
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, baseMintKP, poolCreator, ...approval.signers);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return pool;
+}
+
+export async function createPoolWithExistingToken(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: CreatePoolExistingTokenParams
+): Promise<PublicKey> {
+  const {
+    payer,
+    quoteMint,
+    baseMint,
+    poolCreator,
+    config,
+    poolCreatorAuthority,
+    usePoolCreatorPermit,
+    poolCreationFeeAccounts,
+  } = params;
+  const configState = await getConfig(banksClient, program, config);
+
+  const poolAuthority = derivePoolAuthority();
+  const approval = getPoolCreatorApproval(
+    config,
+    poolCreator.publicKey,
+    baseMint,
+    poolCreatorAuthority,
+    usePoolCreatorPermit
+  );
+  const pool = derivePoolAddress(config, baseMint, quoteMint);
+  const baseVault = deriveTokenVaultAddress(baseMint, pool);
+  const quoteVault = deriveTokenVaultAddress(quoteMint, pool);
+
+  const tokenProgram =
+    configState.tokenType == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+  const transaction = await program.methods
+    .initializeVirtualPoolWithExistingToken()
+    .accountsPartial({
+      config,
+      baseMint,
+      quoteMint,
+      pool,
+      payer: payer.publicKey,
+      creator: poolCreator.publicKey,
+      poolAuthority,
+      baseVault,
+      quoteVault,
+      payerBaseToken: getAssociatedTokenAddressSync(
+        baseMint,
+        payer.publicKey,
+        true,
+        tokenProgram
+      ),
+      tokenQuoteProgram: getTokenProgram(configState.quoteTokenFlag),
       tokenProgram,
     })
+    .remainingAccounts([
+      ...approval.remainingAccounts,
+      ...(poolCreationFeeAccounts ?? []).map((pubkey) => ({
+        isSigner: false,
+        isWritable: true,
+        pubkey,
+      })),
+    ])
+    .preInstructions(approval.preInstructions)
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, baseMintKP, poolCreator);
+  transaction.sign(payer, poolCreator, ...approval.signers);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
@@ -116,15 +300,36 @@ export async function createPoolWithToken2022(
   program: VirtualCurveProgram,
   params: CreatePoolToken2022Params
 ): Promise<PublicKey> {
-  const { payer, quoteMint, config, instructionParams, poolCreator } = params;
+  const {
+    payer,
+    quoteMint,
+    config,
+    instructionParams,
+    poolCreator,
+    poolCreatorAuthority,
+    usePoolCreatorPermit,
+    poolCreationFeeAccounts,
+    additionalMetadata,
+  } = params;
+  const configState = await getConfig(banksClient, program, config);
 
   const poolAuthority = derivePoolAuthority();
   const baseMintKP = Keypair.generate();
+  const approval = getPoolCreatorApproval(
+    config,
+    poolCreator.publicKey,
+    baseMintKP.publicKey,
+    poolCreatorAuthority,
+    usePoolCreatorPermit
+  );
   const pool = derivePoolAddress(config, baseMintKP.publicKey, quoteMint);
   const baseVault = deriveTokenVaultAddress(baseMintKP.publicKey, pool);
   const quoteVault = deriveTokenVaultAddress(quoteMint, pool);
   const transaction = await program.methods
-    .initializeVirtualPoolWithToken2022(instructionParams)
+    .initializeVirtualPoolWithToken2022(
+      instructionParams,
+      additionalMetadata ?? []
+    )
     .accountsPartial({
       config,
       baseMint: baseMintKP.publicKey,
@@ -135,13 +340,22 @@ export async function createPoolWithToken2022(
       poolAuthority,
       baseVault,
       quoteVault,
-      tokenQuoteProgram: TOKEN_PROGRAM_ID,
+      tokenQuoteProgram: getTokenProgram(configState.quoteTokenFlag),
       tokenProgram: TOKEN_2022_PROGRAM_ID,
     })
+    .remainingAccounts([
+      ...approval.remainingAccounts,
+      ...(poolCreationFeeAccounts ?? []).map((pubkey) => ({
+        isSigner: false,
+        isWritable: true,
+        pubkey,
+      })),
+    ])
+    .preInstructions(approval.preInstructions)
     .transaction();
 
   transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
-  transaction.sign(payer, baseMintKP, poolCreator);
+  transaction.sign(payer, baseMintKP, poolCreator, ...approval.signers);
 
   await processTransactionMaybeThrow(banksClient, transaction);
 
@@ -189,12 +403,14 @@ export async function swap(
   const tokenBaseProgram =
     configState.tokenType == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
 
+  const tokenQuoteProgram = getTokenProgram(configState.quoteTokenFlag);
+
   const isInputBaseMint = inputTokenMint.equals(poolState.baseMint);
 
   const quoteMint = isInputBaseMint ? outputTokenMint : inputTokenMint;
   const [inputTokenProgram, outputTokenProgram] = isInputBaseMint
-    ? [tokenBaseProgram, TOKEN_PROGRAM_ID]
-    : [TOKEN_PROGRAM_ID, tokenBaseProgram];
+    ? [tokenBaseProgram, tokenQuoteProgram]
+    : [tokenQuoteProgram, tokenBaseProgram];
 
   const preInstructions: TransactionInstruction[] = [];
   const postInstructions: TransactionInstruction[] = [];
@@ -255,7 +471,7 @@ export async function swap(
       quoteMint,
       payer: payer.publicKey,
       tokenBaseProgram,
-      tokenQuoteProgram: TOKEN_PROGRAM_ID,
+      tokenQuoteProgram,
       referralTokenAccount,
     }).remainingAccounts( // TODO should check condition to add this in remaning accounts
       [
@@ -316,12 +532,14 @@ export async function getSwapInstruction(
   const tokenBaseProgram =
     configState.tokenType == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
 
+  const tokenQuoteProgram = getTokenProgram(configState.quoteTokenFlag);
+
   const isInputBaseMint = inputTokenMint.equals(poolState.baseMint);
 
   const quoteMint = isInputBaseMint ? outputTokenMint : inputTokenMint;
   const [inputTokenProgram, outputTokenProgram] = isInputBaseMint
-    ? [tokenBaseProgram, TOKEN_PROGRAM_ID]
-    : [TOKEN_PROGRAM_ID, tokenBaseProgram];
+    ? [tokenBaseProgram, tokenQuoteProgram]
+    : [tokenQuoteProgram, tokenBaseProgram];
 
   const [
     { ata: inputTokenAccount, ix: _createInputTokenXIx },
@@ -357,7 +575,7 @@ export async function getSwapInstruction(
       quoteMint,
       payer: payer.publicKey,
       tokenBaseProgram,
-      tokenQuoteProgram: TOKEN_PROGRAM_ID,
+      tokenQuoteProgram,
       referralTokenAccount,
     }).remainingAccounts(
       [
@@ -403,10 +621,12 @@ export async function swapSimulate(
   const tokenBaseProgram =
     configState.tokenType == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
 
+  const tokenQuoteProgram = getTokenProgram(configState.quoteTokenFlag);
+
   const isInputBaseMint = inputTokenMint.equals(poolState.baseMint);
   const [inputTokenProgram, outputTokenProgram] = isInputBaseMint
-    ? [tokenBaseProgram, TOKEN_PROGRAM_ID]
-    : [TOKEN_PROGRAM_ID, tokenBaseProgram];
+    ? [tokenBaseProgram, tokenQuoteProgram]
+    : [tokenQuoteProgram, tokenBaseProgram];
 
   const quoteMint = isInputBaseMint ? outputTokenMint : inputTokenMint;
 
@@ -458,7 +678,7 @@ export async function swapSimulate(
       quoteMint,
       payer: payer.publicKey,
       tokenBaseProgram,
-      tokenQuoteProgram: TOKEN_PROGRAM_ID,
+      tokenQuoteProgram,
       referralTokenAccount,
     })
     .transaction();
@@ -483,6 +703,22 @@ export async function swapSimulate(
   };
 }
 
+export type VirtualPoolSocials = {
+  description: string;
+  twitter: string;
+  telegram: string;
+  discord: string;
+  tags: string[];
+};
+
+const EMPTY_VIRTUAL_POOL_SOCIALS: VirtualPoolSocials = {
+  description: "",
+  twitter: "",
+  telegram: "",
+  discord: "",
+  tags: [],
+};
+
 export async function createVirtualPoolMetadata(
   banksClient: BanksClient,
   program: VirtualCurveProgram,
@@ -491,11 +727,13 @@ export async function createVirtualPoolMetadata(
     name: string;
     website: string;
     logo: string;
+    socials?: VirtualPoolSocials;
     creator: Keypair;
     payer: Keypair;
   }
 ) {
   const { virtualPool, creator, payer, name, website, logo } = params;
+  const socials = params.socials ?? EMPTY_VIRTUAL_POOL_SOCIALS;
   const virtualPoolMetadata = deriveVirtualPoolMetadata(virtualPool);
   const transaction = await program.methods
     .createVirtualPoolMetadata({
@@ -503,6 +741,7 @@ export async function createVirtualPoolMetadata(
       name,
       website,
       logo,
+      socials,
     })
     .accountsPartial({
       virtualPool,
@@ -527,4 +766,156 @@ export async function createVirtualPoolMetadata(
   expect(metadataState.name.toString()).equal(name.toString());
   expect(metadataState.website.toString()).equal(website.toString());
   expect(metadataState.logo.toString()).equal(logo.toString());
+  expect(metadataState.version).equal(1);
+  expect(metadataState.socials).deep.equal(socials);
+}
+
+export async function updateVirtualPoolMetadata(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: {
+    virtualPool: PublicKey;
+    name: string;
+    website: string;
+    logo: string;
+    socials?: VirtualPoolSocials;
+    creator: Keypair;
+    payer: Keypair;
+  }
+) {
+  const { virtualPool, creator, payer, name, website, logo } = params;
+  const socials = params.socials ?? EMPTY_VIRTUAL_POOL_SOCIALS;
+  const virtualPoolMetadata = deriveVirtualPoolMetadata(virtualPool);
+  const transaction = await program.methods
+    .updateVirtualPoolMetadata({
+      name,
+      website,
+      logo,
+      socials,
+    })
+    .accountsPartial({
+      virtualPool,
+      virtualPoolMetadata,
+      creator: creator.publicKey,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer, creator);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+  //
+  const metadataState = await getVirtualPoolMetadata(
+    banksClient,
+    program,
+    virtualPoolMetadata
+  );
+  expect(metadataState.name.toString()).equal(name.toString());
+  expect(metadataState.website.toString()).equal(website.toString());
+  expect(metadataState.logo.toString()).equal(logo.toString());
+  expect(metadataState.version).equal(1);
+  expect(metadataState.socials).deep.equal(socials);
+}
+
+export async function closeVirtualPoolMetadata(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: {
+    virtualPool: PublicKey;
+    creator: Keypair;
+    rentReceiver: PublicKey;
+  }
+) {
+  const { virtualPool, creator, rentReceiver } = params;
+  const virtualPoolMetadata = deriveVirtualPoolMetadata(virtualPool);
+  const transaction = await program.methods
+    .closeVirtualPoolMetadata()
+    .accountsPartial({
+      virtualPool,
+      virtualPoolMetadata,
+      creator: creator.publicKey,
+      rentReceiver,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(creator);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function startRefund(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    virtualPool: PublicKey;
+    payer: Keypair;
+  }
+) {
+  const { config, virtualPool, payer } = params;
+  const transaction = await program.methods
+    .startRefund()
+    .accountsPartial({
+      config,
+      virtualPool,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(payer);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
+}
+
+export async function redeemRefund(
+  banksClient: BanksClient,
+  program: VirtualCurveProgram,
+  params: {
+    config: PublicKey;
+    virtualPool: PublicKey;
+    owner: Keypair;
+    baseAmount: BN;
+  }
+) {
+  const { config, virtualPool, owner, baseAmount } = params;
+  const poolState = await getVirtualPool(banksClient, program, virtualPool);
+  const configState = await getConfig(banksClient, program, config);
+  const tokenBaseProgram =
+    configState.tokenType == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+  const tokenQuoteProgram = getTokenProgram(configState.quoteTokenFlag);
+
+  const transaction = await program.methods
+    .redeemRefund(baseAmount)
+    .accountsPartial({
+      poolAuthority: derivePoolAuthority(),
+      config,
+      virtualPool,
+      baseTokenAccount: getAssociatedTokenAddressSync(
+        poolState.baseMint,
+        owner.publicKey,
+        true,
+        tokenBaseProgram
+      ),
+      quoteTokenAccount: getAssociatedTokenAddressSync(
+        configState.quoteMint,
+        owner.publicKey,
+        true,
+        tokenQuoteProgram
+      ),
+      quoteVault: poolState.quoteVault,
+      baseMint: poolState.baseMint,
+      quoteMint: configState.quoteMint,
+      owner: owner.publicKey,
+      tokenBaseProgram,
+      tokenQuoteProgram,
+    })
+    .transaction();
+
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(owner);
+
+  await processTransactionMaybeThrow(banksClient, transaction);
 }
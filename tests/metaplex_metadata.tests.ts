@@ -0,0 +1,212 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import { deserializeMetadata } from "@metaplex-foundation/mpl-token-metadata";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  deriveCollectionAuthority,
+  deriveCollectionAuthorityRecord,
+  deriveMasterEditionAccount,
+  deriveMetadataAccount,
+  designCurve,
+  fundSol,
+  startTest,
+} from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { expect } from "chai";
+import { createToken } from "./utils/token";
+import { approveCollectionAuthority, createCollection } from "./utils/metaplex";
+
+describe("Metaplex creators and collection", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let collectionMint: PublicKey;
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+    collectionMint = await createCollection(context.banksClient, partner);
+  });
+
+  async function createMetadataConfig(): Promise<PublicKey> {
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      1, // damm v2
+      6,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    instructionParams.metadataCreatorFlag = 1;
+    instructionParams.metadataCollectionMint = collectionMint;
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    return createConfig(context.banksClient, program, params);
+  }
+
+  async function getMetadata(baseMint: PublicKey) {
+    const metadataAddress = deriveMetadataAccount(baseMint);
+    const metadataAccount = await context.banksClient.getAccount(
+      metadataAddress
+    );
+    const data = {
+      executable: metadataAccount.executable,
+      owner: metadataAccount.owner,
+      lamports: metadataAccount.lamports,
+      rentEpoch: metadataAccount.rentEpoch,
+      data: metadataAccount.data,
+      publicKey: metadataAddress,
+    };
+    return deserializeMetadata(data as any);
+  }
+
+  const instructionParams = {
+    name: "test token",
+    symbol: "TEST",
+    uri: "abc.com",
+  };
+
+  async function approveConfigCollectionAuthority(
+    config: PublicKey
+  ): Promise<PublicKey[]> {
+    const collectionAuthority = deriveCollectionAuthority(config);
+    const collectionAuthorityRecord = await approveCollectionAuthority(
+      context.banksClient,
+      partner,
+      collectionMint,
+      collectionAuthority
+    );
+    return [
+      collectionMint,
+      deriveMetadataAccount(collectionMint),
+      deriveMasterEditionAccount(collectionMint),
+      collectionAuthorityRecord,
+      collectionAuthority,
+    ];
+  }
+
+  async function createPoolMaybeFail(
+    config: PublicKey,
+    metadataCollectionAccounts?: PublicKey[]
+  ): Promise<boolean> {
+    try {
+      await createPoolWithSplToken(context.banksClient, program, {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+        metadataCollectionAccounts,
+      });
+      return false;
+    } catch (e) {
+      return true;
+    }
+  }
+
+  it("Pool creation fails without approved collection authority of config", async () => {
+    const config = await createMetadataConfig();
+    expect(await createPoolMaybeFail(config)).eq(true);
+
+    const collectionAuthority = deriveCollectionAuthority(config);
+    expect(
+      await createPoolMaybeFail(config, [
+        collectionMint,
+        deriveMetadataAccount(collectionMint),
+        deriveMasterEditionAccount(collectionMint),
+        deriveCollectionAuthorityRecord(collectionMint, collectionAuthority),
+        collectionAuthority,
+      ])
+    ).eq(true);
+  });
+
+  it("Collection authority approved for another config can't verify collection", async () => {
+    const approvedConfig = await createMetadataConfig();
+    const metadataCollectionAccounts = await approveConfigCollectionAuthority(
+      approvedConfig
+    );
+    const config = await createMetadataConfig();
+    expect(await createPoolMaybeFail(config, metadataCollectionAccounts)).eq(
+      true
+    );
+  });
+
+  it("Creator and collection are verified with approved collection authority of config", async () => {
+    const config = await createMetadataConfig();
+    const metadataCollectionAccounts = await approveConfigCollectionAuthority(
+      config
+    );
+    const virtualPool = await createPoolWithSplToken(
+      context.banksClient,
+      program,
+      {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+        metadataCollectionAccounts,
+      }
+    );
+    const poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    const metadata = await getMetadata(poolState.baseMint);
+
+    expect(metadata.creators.__option).eq("Some");
+    if (metadata.creators.__option == "Some") {
+      expect(metadata.creators.value.length).eq(1);
+      expect(metadata.creators.value[0].address.toString()).eq(
+        poolCreator.publicKey.toString()
+      );
+      expect(metadata.creators.value[0].verified).eq(true);
+      expect(metadata.creators.value[0].share).eq(100);
+    }
+    expect(metadata.collection.__option).eq("Some");
+    if (metadata.collection.__option == "Some") {
+      expect(metadata.collection.value.key.toString()).eq(
+        collectionMint.toString()
+      );
+      expect(metadata.collection.value.verified).eq(true);
+    }
+  });
+});
@@ -0,0 +1,164 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { designCurve, fundSol, startTest } from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { assert, expect } from "chai";
+import {
+  createToken,
+  getOrCreateAssociatedTokenAccount,
+  getTokenAccount,
+  mintSplTokenTo,
+} from "./utils/token";
+
+describe("Pool creation fee", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let feeRecipient: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  const poolCreationFee = new BN(1_000_000);
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    feeRecipient = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      feeRecipient.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      admin,
+      quoteMint,
+      admin,
+      poolCreator.publicKey,
+      poolCreationFee.toNumber()
+    );
+  });
+
+  async function createConfigWithFee(
+    poolCreationFeeToken: number
+  ): Promise<PublicKey> {
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    instructionParams.poolCreationFeeToken = poolCreationFeeToken;
+    instructionParams.poolCreationFee = poolCreationFee;
+    instructionParams.poolCreationFeeRecipient = feeRecipient.publicKey;
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    return createConfig(context.banksClient, program, params);
+  }
+
+  const instructionParams = {
+    name: "test token",
+    symbol: "TEST",
+    uri: "abc.com",
+  };
+
+  it("Pool creation without fee accounts is rejected", async () => {
+    const config = await createConfigWithFee(0);
+    try {
+      await createPoolWithSplToken(context.banksClient, program, {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+
+  it("Payer pays pool creation fee in SOL", async () => {
+    const config = await createConfigWithFee(0);
+    const preBalance = await context.banksClient.getBalance(
+      feeRecipient.publicKey
+    );
+    await createPoolWithSplToken(context.banksClient, program, {
+      payer: poolCreator,
+      poolCreator,
+      quoteMint,
+      config,
+      instructionParams,
+      poolCreationFeeAccounts: [feeRecipient.publicKey],
+    });
+    const postBalance = await context.banksClient.getBalance(
+      feeRecipient.publicKey
+    );
+    expect((postBalance - preBalance).toString()).eq(
+      poolCreationFee.toString()
+    );
+  });
+
+  it("Payer pays pool creation fee in quote token", async () => {
+    const config = await createConfigWithFee(1);
+    const payerTokenAccount = await getOrCreateAssociatedTokenAccount(
+      context.banksClient,
+      admin,
+      quoteMint,
+      poolCreator.publicKey
+    );
+    const recipientTokenAccount = await getOrCreateAssociatedTokenAccount(
+      context.banksClient,
+      admin,
+      quoteMint,
+      feeRecipient.publicKey
+    );
+    await createPoolWithSplToken(context.banksClient, program, {
+      payer: poolCreator,
+      poolCreator,
+      quoteMint,
+      config,
+      instructionParams,
+      poolCreationFeeAccounts: [payerTokenAccount, recipientTokenAccount],
+    });
+    const recipientTokenState = await getTokenAccount(
+      context.banksClient,
+      recipientTokenAccount
+    );
+    expect(recipientTokenState.amount.toString()).eq(
+      poolCreationFee.toString()
+    );
+  });
+});
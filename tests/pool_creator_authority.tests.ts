@@ -0,0 +1,135 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  createPoolWithToken2022,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { designCurve, fundSol, startTest } from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { assert } from "chai";
+import { createToken } from "./utils/token";
+
+describe("Pool creator authority", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+  });
+
+  async function createGatedConfig(tokenType: number): Promise<PublicKey> {
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      0,
+      6,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    instructionParams.tokenType = tokenType;
+    instructionParams.poolCreatorAuthority = partner.publicKey;
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    return createConfig(context.banksClient, program, params);
+  }
+
+  const instructionParams = {
+    name: "test token",
+    symbol: "TEST",
+    uri: "abc.com",
+  };
+
+  it("Pool creation without approval is rejected", async () => {
+    const config = await createGatedConfig(0);
+    try {
+      await createPoolWithSplToken(context.banksClient, program, {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+
+  it("Pool creation with wrong authority is rejected", async () => {
+    const config = await createGatedConfig(0);
+    try {
+      await createPoolWithSplToken(context.banksClient, program, {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+        poolCreatorAuthority: Keypair.generate(),
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+
+  it("Pool creation co-signed by pool creator authority", async () => {
+    const config = await createGatedConfig(0);
+    await createPoolWithSplToken(context.banksClient, program, {
+      payer: poolCreator,
+      poolCreator,
+      quoteMint,
+      config,
+      instructionParams,
+      poolCreatorAuthority: partner,
+    });
+  });
+
+  it("Pool creation with permit signed by pool creator authority", async () => {
+    const config = await createGatedConfig(1);
+    await createPoolWithToken2022(context.banksClient, program, {
+      payer: poolCreator,
+      poolCreator,
+      quoteMint,
+      config,
+      instructionParams,
+      poolCreatorAuthority: partner,
+      usePoolCreatorPermit: true,
+    });
+  });
+});
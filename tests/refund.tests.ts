@@ -0,0 +1,212 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  redeemRefund,
+  startRefund,
+  swap,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  designCurve,
+  fundSol,
+  getTokenAccount,
+  startTest,
+  warpSlotBy,
+} from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { assert, expect } from "chai";
+import { createToken, mintSplTokenTo } from "./utils/token";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+
+describe("Refund of pool not completed before the deadline", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let config: PublicKey;
+  let virtualPool: PublicKey;
+  // refund deadline in slots after pool activation
+  const refundDeadlineDuration = new BN(1_000);
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    user = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      user.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      admin,
+      quoteMint,
+      admin,
+      user.publicKey,
+      1_000_000_000_000
+    );
+
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      1, // damm v2
+      6,
+      9,
+      0,
+      0, // collect fee on quote token
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    instructionParams.refundDeadlineDuration = refundDeadlineDuration;
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(context.banksClient, program, params);
+    virtualPool = await createPoolWithSplToken(context.banksClient, program, {
+      payer: poolCreator,
+      poolCreator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+  });
+
+  it("Pool can't start refund before the deadline", async () => {
+    await swap(context.banksClient, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: (
+        await getVirtualPool(context.banksClient, program, virtualPool)
+      ).baseMint,
+      amountIn: new BN(10_000_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    try {
+      await startRefund(context.banksClient, program, {
+        config,
+        virtualPool,
+        payer: user,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+
+  it("Holder redeems base token for quote reserve after the deadline", async () => {
+    await warpSlotBy(context, refundDeadlineDuration.addn(1));
+    await startRefund(context.banksClient, program, {
+      config,
+      virtualPool,
+      payer: user,
+    });
+
+    let poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    expect(poolState.migrationProgress).eq(4); // refunding
+    const refundQuoteAmount = poolState.refundQuoteAmount;
+    expect(refundQuoteAmount.toString()).eq(
+      poolState.quoteReserve.toString()
+    );
+
+    // swaps stop once the pool refunds
+    try {
+      await swap(context.banksClient, program, {
+        config,
+        payer: user,
+        pool: virtualPool,
+        inputTokenMint: quoteMint,
+        outputTokenMint: poolState.baseMint,
+        amountIn: new BN(1_000_000_000),
+        minimumAmountOut: new BN(0),
+        referralTokenAccount: null,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+
+    const userBaseTokenAccount = getAssociatedTokenAddressSync(
+      poolState.baseMint,
+      user.publicKey
+    );
+    const userQuoteTokenAccount = getAssociatedTokenAddressSync(
+      quoteMint,
+      user.publicKey
+    );
+    const baseAmount = new BN(
+      (
+        await getTokenAccount(context.banksClient, userBaseTokenAccount)
+      ).amount.toString()
+    );
+    // the only holder owns all redeemable base token
+    expect(baseAmount.toString()).eq(poolState.refundBaseAmount.toString());
+    const quoteBefore = new BN(
+      (
+        await getTokenAccount(context.banksClient, userQuoteTokenAccount)
+      ).amount.toString()
+    );
+
+    await redeemRefund(context.banksClient, program, {
+      config,
+      virtualPool,
+      owner: user,
+      baseAmount,
+    });
+
+    const quoteAfter = new BN(
+      (
+        await getTokenAccount(context.banksClient, userQuoteTokenAccount)
+      ).amount.toString()
+    );
+    expect(quoteAfter.sub(quoteBefore).toString()).eq(
+      refundQuoteAmount.toString()
+    );
+    expect(
+      (
+        await getTokenAccount(context.banksClient, userBaseTokenAccount)
+      ).amount.toString()
+    ).eq("0");
+    poolState = await getVirtualPool(context.banksClient, program, virtualPool);
+    expect(poolState.refundBaseAmount.toString()).eq("0");
+    expect(poolState.refundQuoteAmount.toString()).eq("0");
+  });
+});
@@ -0,0 +1,279 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import { unpack } from "@solana/spl-token-metadata";
+import {
+  ACCOUNT_SIZE,
+  ACCOUNT_TYPE_SIZE,
+  ExtensionType,
+  getExtensionData,
+} from "@solana/spl-token";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithToken2022,
+  transferCreator,
+  updateTokenMetadataFields,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { designCurve, fundSol, startTest } from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { assert, expect } from "chai";
+import { createToken } from "./utils/token";
+
+describe("Token2022 additional metadata fields", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+  });
+
+  async function createToken2022Config(
+    tokenUpdateAuthority: number
+  ): Promise<PublicKey> {
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      1, // damm v2
+      6,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    instructionParams.tokenType = 1; // token 2022
+    instructionParams.tokenUpdateAuthority = tokenUpdateAuthority;
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    return createConfig(context.banksClient, program, params);
+  }
+
+  async function getTokenMetadata(baseMint: PublicKey) {
+    const tlvData = (await context.banksClient.getAccount(baseMint)).data.slice(
+      ACCOUNT_SIZE + ACCOUNT_TYPE_SIZE
+    );
+    return unpack(
+      getExtensionData(ExtensionType.TokenMetadata, Buffer.from(tlvData))
+    );
+  }
+
+  const instructionParams = {
+    name: "test token",
+    symbol: "TEST",
+    uri: "abc.com",
+  };
+  const additionalMetadata = [
+    { key: "twitter", value: "https://x.com/test" },
+    { key: "telegram", value: "https://t.me/test" },
+    { key: "website", value: "https://test.com" },
+    { key: "description", value: "test token" },
+  ];
+
+  it("Creator sets and updates additional fields on mutable config", async () => {
+    const config = await createToken2022Config(0);
+    const virtualPool = await createPoolWithToken2022(
+      context.banksClient,
+      program,
+      {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+        additionalMetadata,
+      }
+    );
+    const poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    let metadata = await getTokenMetadata(poolState.baseMint);
+    expect(metadata.additionalMetadata).deep.eq(
+      additionalMetadata.map(({ key, value }) => [key, value])
+    );
+
+    await updateTokenMetadataFields(context.banksClient, program, {
+      updateAuthority: poolCreator,
+      virtualPool,
+      fields: [
+        { key: "description", value: "a much longer description of token" },
+        { key: "telegram", value: "" },
+        { key: "discord", value: "https://discord.gg/test" },
+      ],
+    });
+    metadata = await getTokenMetadata(poolState.baseMint);
+    expect(metadata.additionalMetadata).deep.eq([
+      ["twitter", "https://x.com/test"],
+      ["website", "https://test.com"],
+      ["description", "a much longer description of token"],
+      ["discord", "https://discord.gg/test"],
+    ]);
+  });
+
+  it("Additional fields can't be updated on immutable config", async () => {
+    const config = await createToken2022Config(1);
+    const virtualPool = await createPoolWithToken2022(
+      context.banksClient,
+      program,
+      {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+        additionalMetadata,
+      }
+    );
+    const poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    const metadata = await getTokenMetadata(poolState.baseMint);
+    expect(metadata.additionalMetadata.length).eq(additionalMetadata.length);
+
+    try {
+      await updateTokenMetadataFields(context.banksClient, program, {
+        updateAuthority: poolCreator,
+        virtualPool,
+        fields: [{ key: "twitter", value: "https://x.com/other" }],
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+  });
+
+  it("Total additional fields on the mint are capped", async () => {
+    const config = await createToken2022Config(0);
+    const virtualPool = await createPoolWithToken2022(
+      context.banksClient,
+      program,
+      {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+        additionalMetadata,
+      }
+    );
+
+    // 4 fields on the mint and 5 new fields exceed 8 fields
+    let updateFailed = false;
+    try {
+      await updateTokenMetadataFields(context.banksClient, program, {
+        updateAuthority: poolCreator,
+        virtualPool,
+        fields: [0, 1, 2, 3, 4].map((i) => ({ key: `key${i}`, value: "value" })),
+      });
+    } catch (e) {
+      updateFailed = true;
+    }
+    expect(updateFailed).eq(true);
+
+    // removing a field makes room for another one
+    await updateTokenMetadataFields(context.banksClient, program, {
+      updateAuthority: poolCreator,
+      virtualPool,
+      fields: [
+        { key: "twitter", value: "" },
+        ...[0, 1, 2, 3, 4].map((i) => ({ key: `key${i}`, value: "value" })),
+      ],
+    });
+    const poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    const metadata = await getTokenMetadata(poolState.baseMint);
+    expect(metadata.additionalMetadata.length).eq(8);
+  });
+
+  it("Metadata update authority is kept after pool creator transfer", async () => {
+    const config = await createToken2022Config(0);
+    const virtualPool = await createPoolWithToken2022(
+      context.banksClient,
+      program,
+      {
+        payer: poolCreator,
+        poolCreator,
+        quoteMint,
+        config,
+        instructionParams,
+        additionalMetadata,
+      }
+    );
+    const newCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [newCreator.publicKey]);
+    await transferCreator(
+      context.banksClient,
+      program,
+      virtualPool,
+      poolCreator,
+      newCreator.publicKey
+    );
+
+    // new pool creator isn't the metadata update authority
+    let updateFailed = false;
+    try {
+      await updateTokenMetadataFields(context.banksClient, program, {
+        updateAuthority: newCreator,
+        virtualPool,
+        fields: [{ key: "twitter", value: "https://x.com/new" }],
+      });
+    } catch (e) {
+      updateFailed = true;
+    }
+    expect(updateFailed).eq(true);
+
+    await updateTokenMetadataFields(context.banksClient, program, {
+      updateAuthority: poolCreator,
+      virtualPool,
+      fields: [{ key: "twitter", value: "https://x.com/new" }],
+    });
+    const poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    const metadata = await getTokenMetadata(poolState.baseMint);
+    expect(metadata.additionalMetadata[0]).deep.eq([
+      "twitter",
+      "https://x.com/new",
+    ]);
+  });
+});
@@ -0,0 +1,145 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  createConfig,
+  CreateConfigParams,
+  createPoolWithSplToken,
+  swap,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { designCurve, fundSol, startTest } from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { getVirtualPool } from "./utils/fetcher";
+import { expect } from "chai";
+import {
+  createToken2022WithTransferFee,
+  getTokenAccount,
+  mintSplTokenTo,
+} from "./utils/token";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("Token2022 quote mint with transfer fee", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let user: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let quoteMint: PublicKey;
+  let config: PublicKey;
+  let virtualPool: PublicKey;
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    user = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      user.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    // 1% transfer fee
+    quoteMint = await createToken2022WithTransferFee(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9,
+      100,
+      BigInt(1_000_000_000_000)
+    );
+    await mintSplTokenTo(
+      context.banksClient,
+      admin,
+      quoteMint,
+      admin,
+      user.publicKey,
+      1_000_000_000_000,
+      TOKEN_2022_PROGRAM_ID
+    );
+  });
+
+  it("Partner create config with transfer fee quote mint", async () => {
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      1, // damm v2
+      6,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    config = await createConfig(context.banksClient, program, params);
+    virtualPool = await createPoolWithSplToken(context.banksClient, program, {
+      payer: poolCreator,
+      poolCreator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+  });
+
+  it("Quote reserve matches vault balance after swaps", async () => {
+    let poolState = await getVirtualPool(
+      context.banksClient,
+      program,
+      virtualPool
+    );
+    await swap(context.banksClient, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: quoteMint,
+      outputTokenMint: poolState.baseMint,
+      amountIn: new BN(10_000_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    poolState = await getVirtualPool(context.banksClient, program, virtualPool);
+    await swap(context.banksClient, program, {
+      config,
+      payer: user,
+      pool: virtualPool,
+      inputTokenMint: poolState.baseMint,
+      outputTokenMint: quoteMint,
+      amountIn: poolState.baseReserve.divn(1_000_000),
+      minimumAmountOut: new BN(0),
+      referralTokenAccount: null,
+    });
+
+    poolState = await getVirtualPool(context.banksClient, program, virtualPool);
+    const quoteVault = await getTokenAccount(
+      context.banksClient,
+      poolState.quoteVault
+    );
+    const quoteFees = poolState.protocolQuoteFee.add(
+      poolState.partnerQuoteFee.add(poolState.creatorQuoteFee)
+    );
+    expect(quoteVault.amount.toString()).eq(
+      poolState.quoteReserve.add(quoteFees).toString()
+    );
+  });
+});
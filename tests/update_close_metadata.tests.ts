@@ -0,0 +1,212 @@
+import { BN } from "bn.js";
+import { ProgramTestContext } from "solana-bankrun";
+import {
+  closePartnerMetadata,
+  closeVirtualPoolMetadata,
+  createConfig,
+  CreateConfigParams,
+  createPartnerMetadata,
+  createPoolWithSplToken,
+  createVirtualPoolMetadata,
+  updatePartnerMetadata,
+  updateVirtualPoolMetadata,
+} from "./instructions";
+import { VirtualCurveProgram } from "./utils/types";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  derivePartnerMetadata,
+  deriveVirtualPoolMetadata,
+  designCurve,
+  fundSol,
+  startTest,
+} from "./utils";
+import { createVirtualCurveProgram } from "./utils";
+import { assert, expect } from "chai";
+import { createToken } from "./utils/token";
+
+describe("Update and close partner and pool metadata", () => {
+  let context: ProgramTestContext;
+  let admin: Keypair;
+  let partner: Keypair;
+  let poolCreator: Keypair;
+  let program: VirtualCurveProgram;
+  let virtualPool: PublicKey;
+
+  before(async () => {
+    context = await startTest();
+    admin = context.payer;
+    partner = Keypair.generate();
+    poolCreator = Keypair.generate();
+    await fundSol(context.banksClient, admin, [
+      partner.publicKey,
+      poolCreator.publicKey,
+    ]);
+    program = createVirtualCurveProgram();
+    const quoteMint = await createToken(
+      context.banksClient,
+      admin,
+      admin.publicKey,
+      9
+    );
+
+    const instructionParams = designCurve(
+      1_000_000_000,
+      10,
+      300,
+      1, // damm v2
+      6,
+      9,
+      0,
+      0,
+      {
+        amountPerPeriod: new BN(0),
+        cliffDurationFromMigrationTime: new BN(0),
+        frequency: new BN(0),
+        numberOfPeriod: new BN(0),
+        cliffUnlockAmount: new BN(0),
+      }
+    );
+    const params: CreateConfigParams = {
+      payer: partner,
+      leftoverReceiver: partner.publicKey,
+      feeClaimer: partner.publicKey,
+      quoteMint,
+      instructionParams,
+    };
+    const config = await createConfig(context.banksClient, program, params);
+    virtualPool = await createPoolWithSplToken(context.banksClient, program, {
+      payer: poolCreator,
+      poolCreator,
+      quoteMint,
+      config,
+      instructionParams: {
+        name: "test token",
+        symbol: "TEST",
+        uri: "abc.com",
+      },
+    });
+  });
+
+  it("Fee claimer updates and closes partner metadata", async () => {
+    await createPartnerMetadata(context.banksClient, program, {
+      name: "partner",
+      website: "partner.com",
+      logo: "partner.com/logo.png",
+      feeClaimer: partner,
+      payer: partner,
+    });
+    const partnerMetadata = derivePartnerMetadata(partner.publicKey);
+    const sizeBefore = (await context.banksClient.getAccount(partnerMetadata))
+      .data.length;
+
+    await updatePartnerMetadata(context.banksClient, program, {
+      name: "partner rebranded",
+      website: "https://partner-rebranded.com",
+      logo: "https://partner-rebranded.com/logo.png",
+      feeClaimer: partner,
+      payer: partner,
+    });
+    const sizeAfterGrow = (await context.banksClient.getAccount(partnerMetadata))
+      .data.length;
+    expect(sizeAfterGrow).gt(sizeBefore);
+
+    try {
+      await updatePartnerMetadata(context.banksClient, program, {
+        name: "a".repeat(65),
+        website: "p.com",
+        logo: "",
+        feeClaimer: partner,
+        payer: partner,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+
+    await updatePartnerMetadata(context.banksClient, program, {
+      name: "p",
+      website: "p.com",
+      logo: "",
+      feeClaimer: partner,
+      payer: partner,
+    });
+    const sizeAfterShrink = (
+      await context.banksClient.getAccount(partnerMetadata)
+    ).data.length;
+    expect(sizeAfterShrink).lt(sizeBefore);
+
+    await closePartnerMetadata(context.banksClient, program, {
+      feeClaimer: partner,
+      rentReceiver: partner.publicKey,
+    });
+    expect(await context.banksClient.getAccount(partnerMetadata)).eq(null);
+  });
+
+  it("Only pool creator updates and closes pool metadata", async () => {
+    await createVirtualPoolMetadata(context.banksClient, program, {
+      virtualPool,
+      name: "project",
+      website: "project.com",
+      logo: "project.com/logo.png",
+      creator: poolCreator,
+      payer: poolCreator,
+    });
+
+    try {
+      await updateVirtualPoolMetadata(context.banksClient, program, {
+        virtualPool,
+        name: "hijacked",
+        website: "hijacked.com",
+        logo: "",
+        creator: partner,
+        payer: partner,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+
+    const socials = {
+      description: "project description",
+      twitter: "https://x.com/project",
+      telegram: "https://t.me/project",
+      discord: "https://discord.gg/project",
+      tags: ["meme", "ai"],
+    };
+    try {
+      await updateVirtualPoolMetadata(context.banksClient, program, {
+        virtualPool,
+        name: "project",
+        website: "https://project.com",
+        logo: "https://project.com/logo.png",
+        socials: { ...socials, tags: ["a".repeat(33)] },
+        creator: poolCreator,
+        payer: poolCreator,
+      });
+      assert.ok(false);
+    } catch (e) {
+      //
+    }
+
+    await updateVirtualPoolMetadata(context.banksClient, program, {
+      virtualPool,
+      name: "project",
+      website: "https://project.com",
+      logo: "https://project.com/logo.png",
+      socials,
+      creator: poolCreator,
+      payer: poolCreator,
+    });
+
+    await closeVirtualPoolMetadata(context.banksClient, program, {
+      virtualPool,
+      creator: poolCreator,
+      rentReceiver: poolCreator.publicKey,
+    });
+    expect(
+      await context.banksClient.getAccount(
+        deriveVirtualPoolMetadata(virtualPool)
+      )
+    ).eq(null);
+  });
+});
@@ -37,6 +37,34 @@ export function deriveMetadataAccount(mint: PublicKey): PublicKey {
   )[0];
 }
 
+export function deriveMasterEditionAccount(mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      METAPLEX_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("edition"),
+    ],
+    METAPLEX_PROGRAM_ID
+  )[0];
+}
+
+export function deriveCollectionAuthorityRecord(
+  collectionMint: PublicKey,
+  collectionAuthority: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      METAPLEX_PROGRAM_ID.toBuffer(),
+      collectionMint.toBuffer(),
+      Buffer.from("collection_authority"),
+      collectionAuthority.toBuffer(),
+    ],
+    METAPLEX_PROGRAM_ID
+  )[0];
+}
+
 export function derivePoolAuthority(): PublicKey {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("pool_authority")],
@@ -44,6 +72,13 @@ export function derivePoolAuthority(): PublicKey {
   )[0];
 }
 
+export function deriveCollectionAuthority(config: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("collection_authority"), config.toBuffer()],
+    DYNAMIC_BONDING_CURVE_PROGRAM_ID
+  )[0];
+}
+
 export function deriveBaseKeyForLocker(virtualPool: PublicKey): PublicKey {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("base_locker"), virtualPool.toBuffer()],
@@ -6,6 +6,7 @@ import {
   LockedVestingParams,
 } from "../instructions";
 import Decimal from "decimal.js";
+import { PublicKey } from "@solana/web3.js";
 import { MAX_SQRT_PRICE, MIN_SQRT_PRICE } from "./constants";
 import { assert } from "chai";
 
@@ -474,8 +475,33 @@ export function designCurve(
       feePercentage: 0,
       creatorFeePercentage: 0,
     },
-    padding0: [],
-    padding1: [],
+    lockedVestingTranches: [],
+    lockedVestingUpdateRecipientMode: 0,
+    lockedVestingCancelMode: 0,
+    lockedVestingStartAnchor: 0,
+    lockedVestingStartTimestamp: new BN(0),
+    poolCreationFeeToken: 0,
+    baseTransferFeeBasisPoints: 0,
+    metadataCreatorFlag: 0,
+    closeAuthority: PublicKey.default,
+    poolCreatorAuthority: PublicKey.default,
+    poolCreationFee: new BN(0),
+    poolCreationFeeRecipient: PublicKey.default,
+    baseTransferFeeMaximumFee: new BN(0),
+    metadataCollectionMint: PublicKey.default,
+    migrationProgram: PublicKey.default,
+    migrationCrankReward: new BN(0),
+    migratedPoolFee: null,
+    migrationPriceRange: null,
+    dlmmBinStep: 0,
+    autoMigrationFlag: 0,
+    migrationBaseFeeBps: 0,
+    migrationPriceToleranceBps: 0,
+    refundDeadlineDuration: new BN(0),
+    refundFeeMode: 0,
+    baseInterestRate: 0,
+    baseNonTransferableFlag: 0,
+    padding: [],
     curve,
   };
   return instructionParams;
@@ -601,8 +627,33 @@ export function designGraphCurve(
       feePercentage: 0,
       creatorFeePercentage: 0,
     },
-    padding0: [],
-    padding1: [],
+    lockedVestingTranches: [],
+    lockedVestingUpdateRecipientMode: 0,
+    lockedVestingCancelMode: 0,
+    lockedVestingStartAnchor: 0,
+    lockedVestingStartTimestamp: new BN(0),
+    poolCreationFeeToken: 0,
+    baseTransferFeeBasisPoints: 0,
+    metadataCreatorFlag: 0,
+    closeAuthority: PublicKey.default,
+    poolCreatorAuthority: PublicKey.default,
+    poolCreationFee: new BN(0),
+    poolCreationFeeRecipient: PublicKey.default,
+    baseTransferFeeMaximumFee: new BN(0),
+    metadataCollectionMint: PublicKey.default,
+    migrationProgram: PublicKey.default,
+    migrationCrankReward: new BN(0),
+    migratedPoolFee: null,
+    migrationPriceRange: null,
+    dlmmBinStep: 0,
+    autoMigrationFlag: 0,
+    migrationBaseFeeBps: 0,
+    migrationPriceToleranceBps: 0,
+    refundDeadlineDuration: new BN(0),
+    refundFeeMode: 0,
+    baseInterestRate: 0,
+    baseNonTransferableFlag: 0,
+    padding: [],
     curve,
   };
   return instructionParams;
@@ -0,0 +1,143 @@
+import {
+  getApproveCollectionAuthorityInstructionDataSerializer,
+  getCreateMasterEditionV3InstructionDataSerializer,
+  getCreateMetadataAccountV3InstructionDataSerializer,
+} from "@metaplex-foundation/mpl-token-metadata";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { BanksClient } from "solana-bankrun";
+import {
+  deriveCollectionAuthorityRecord,
+  deriveMasterEditionAccount,
+  deriveMetadataAccount,
+} from "./accounts";
+import { METAPLEX_PROGRAM_ID } from "./constants";
+import { processTransactionMaybeThrow } from "./common";
+import { createToken, mintSplTokenTo } from "./token";
+
+/// Create a sized metaplex collection NFT, update authority is the authority
+export async function createCollection(
+  banksClient: BanksClient,
+  authority: Keypair
+): Promise<PublicKey> {
+  const collectionMint = await createToken(
+    banksClient,
+    authority,
+    authority.publicKey,
+    0
+  );
+  await mintSplTokenTo(
+    banksClient,
+    authority,
+    collectionMint,
+    authority,
+    authority.publicKey,
+    1
+  );
+  const metadata = deriveMetadataAccount(collectionMint);
+  const masterEdition = deriveMasterEditionAccount(collectionMint);
+
+  const createMetadataIx = new TransactionInstruction({
+    programId: METAPLEX_PROGRAM_ID,
+    keys: [
+      { pubkey: metadata, isSigner: false, isWritable: true },
+      { pubkey: collectionMint, isSigner: false, isWritable: false },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from(
+      getCreateMetadataAccountV3InstructionDataSerializer().serialize({
+        data: {
+          name: "collection",
+          symbol: "COL",
+          uri: "collection.com",
+          sellerFeeBasisPoints: 0,
+          creators: null,
+          collection: null,
+          uses: null,
+        },
+        isMutable: true,
+        collectionDetails: { __kind: "V1", size: 0 },
+      })
+    ),
+  });
+  const createMasterEditionIx = new TransactionInstruction({
+    programId: METAPLEX_PROGRAM_ID,
+    keys: [
+      { pubkey: masterEdition, isSigner: false, isWritable: true },
+      { pubkey: collectionMint, isSigner: false, isWritable: true },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      { pubkey: metadata, isSigner: false, isWritable: true },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from(
+      getCreateMasterEditionV3InstructionDataSerializer().serialize({
+        maxSupply: 0,
+      })
+    ),
+  });
+
+  const transaction = new Transaction().add(
+    createMetadataIx,
+    createMasterEditionIx
+  );
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(authority);
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return collectionMint;
+}
+
+/// Approve new collection authority with a legacy collection authority record
+export async function approveCollectionAuthority(
+  banksClient: BanksClient,
+  authority: Keypair,
+  collectionMint: PublicKey,
+  newCollectionAuthority: PublicKey
+): Promise<PublicKey> {
+  const collectionAuthorityRecord = deriveCollectionAuthorityRecord(
+    collectionMint,
+    newCollectionAuthority
+  );
+  const approveIx = new TransactionInstruction({
+    programId: METAPLEX_PROGRAM_ID,
+    keys: [
+      { pubkey: collectionAuthorityRecord, isSigner: false, isWritable: true },
+      { pubkey: newCollectionAuthority, isSigner: false, isWritable: false },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      {
+        pubkey: deriveMetadataAccount(collectionMint),
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: collectionMint, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from(
+      getApproveCollectionAuthorityInstructionDataSerializer().serialize({})
+    ),
+  });
+
+  const transaction = new Transaction().add(approveIx);
+  transaction.recentBlockhash = (await banksClient.getLatestBlockhash())[0];
+  transaction.sign(authority);
+  await processTransactionMaybeThrow(banksClient, transaction);
+
+  return collectionAuthorityRecord;
+}
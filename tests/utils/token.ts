@@ -3,6 +3,7 @@ import {
   createAssociatedTokenAccountInstruction,
   createInitializeMint2Instruction,
   createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
   createMintToInstruction,
   createSyncNativeInstruction,
   ExtensionType,
@@ -90,6 +91,60 @@ export async function createToken(
   return mintKeypair.publicKey;
 }
 
+export async function createToken2022WithTransferFee(
+  banksClient: BanksClient,
+  payer: Keypair,
+  mintAuthority: PublicKey,
+  decimal: number,
+  transferFeeBasisPoints: number,
+  maximumFee: bigint
+): Promise<PublicKey> {
+  const mintKeypair = Keypair.generate();
+  const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+  const rent = await banksClient.getRent();
+  const lamports = rent.minimumBalance(BigInt(mintLen));
+
+  const createAccountIx = SystemProgram.createAccount({
+    fromPubkey: payer.publicKey,
+    newAccountPubkey: mintKeypair.publicKey,
+    space: mintLen,
+    lamports: Number(lamports.toString()),
+    programId: TOKEN_2022_PROGRAM_ID,
+  });
+
+  const initializeTransferFeeConfigIx =
+    createInitializeTransferFeeConfigInstruction(
+      mintKeypair.publicKey,
+      mintAuthority,
+      mintAuthority,
+      transferFeeBasisPoints,
+      maximumFee,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+  const initializeMintIx = createInitializeMint2Instruction(
+    mintKeypair.publicKey,
+    decimal,
+    mintAuthority,
+    null,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  let transaction = new Transaction();
+  const [recentBlockhash] = await banksClient.getLatestBlockhash();
+  transaction.recentBlockhash = recentBlockhash;
+  transaction.add(
+    createAccountIx,
+    initializeTransferFeeConfigIx,
+    initializeMintIx
+  );
+  transaction.sign(payer, mintKeypair);
+
+  await banksClient.processTransaction(transaction);
+
+  return mintKeypair.publicKey;
+}
+
 export async function wrapSOL(
   banksClient: BanksClient,
   payer: Keypair,
@@ -126,19 +181,23 @@ export async function mintSplTokenTo(
   mintAuthority: Keypair,
   toWallet: PublicKey,
   rawAmount: number,
+  tokenProgram = TOKEN_PROGRAM_ID
 ) {
   const destination = await getOrCreateAssociatedTokenAccount(
     banksClient,
     payer,
     mint,
-    toWallet
+    toWallet,
+    tokenProgram
   );
 
   const mintIx = createMintToInstruction(
     mint,
     destination,
     mintAuthority.publicKey,
-    rawAmount
+    rawAmount,
+    [],
+    tokenProgram
   );
 
   let transaction = new Transaction();